@@ -9,6 +9,8 @@ pub struct EscrowMeta {
     pub storage_key: Option<Expr>,
     pub id: Expr,
     pub state: Option<Expr>,
+    #[darling(default)]
+    pub enumerable: bool,
 
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -25,6 +27,7 @@ pub fn expand(meta: EscrowMeta) -> Result<TokenStream, darling::Error> {
         storage_key,
         id,
         state,
+        enumerable,
 
         ident,
         generics,
@@ -45,12 +48,21 @@ pub fn expand(meta: EscrowMeta) -> Result<TokenStream, darling::Error> {
 
     let state = state.map_or_else(|| quote! { () }, |state| quote! { #state });
 
+    let enumerable = enumerable.then(|| {
+        quote! {
+            fn enumerable() -> bool {
+                true
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::escrow::EscrowInternal for #ident #ty #wher {
             type Id = #id;
             type State = #state;
 
             #root
+            #enumerable
         }
     })
 }
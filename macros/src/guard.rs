@@ -0,0 +1,45 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Expr;
+
+/// Access check for generated privileged entrypoints. Parsed from
+/// `"owner"` or `"role(<expression>)"`.
+#[derive(Debug, Clone)]
+pub enum Guard {
+    Owner,
+    Role(Box<Expr>),
+}
+
+impl FromMeta for Guard {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        if value == "owner" {
+            Ok(Self::Owner)
+        } else if let Some(g) = value
+            .strip_prefix("role(")
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| syn::parse_str::<Expr>(s).ok())
+            .map(|e| Self::Role(Box::new(e)))
+        {
+            Ok(g)
+        } else {
+            Err(darling::Error::custom(format!(
+                r#"Invalid value "{value}", expected "owner" or "role(...)""#,
+            )))
+        }
+    }
+}
+
+impl Guard {
+    /// Statement that panics unless the predecessor passes the check.
+    pub fn expand(&self, me: &syn::Path) -> TokenStream {
+        match self {
+            Self::Owner => quote! {
+                <Self as #me::owner::Owner>::require_owner();
+            },
+            Self::Role(role) => quote! {
+                <Self as #me::rbac::Rbac>::require_role(&#role);
+            },
+        }
+    }
+}
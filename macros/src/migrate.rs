@@ -7,6 +7,8 @@ use quote::{quote, ToTokens};
 pub struct MigrateMeta {
     pub from: syn::Type,
     pub to: Option<syn::Type>,
+    #[darling(default)]
+    pub dry_run: bool,
 
     pub ident: syn::Ident,
     pub generics: syn::Generics,
@@ -22,6 +24,7 @@ pub fn expand(meta: MigrateMeta) -> Result<TokenStream, darling::Error> {
     let MigrateMeta {
         from,
         to,
+        dry_run,
 
         ident,
         generics,
@@ -37,6 +40,19 @@ pub fn expand(meta: MigrateMeta) -> Result<TokenStream, darling::Error> {
         |t| t.to_token_stream(),
     );
 
+    let dry_run = dry_run.then(|| {
+        quote! {
+            /// Returns `Ok(())` if the migration would succeed, or the reason
+            /// it would fail.
+            #[handle_result]
+            pub fn migrate_dry_run() -> Result<(), String> {
+                <#ident as #me::migrate::MigrateHook>::dry_run_migrate()
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::migrate::MigrateController for #ident #ty #wh {
             type OldSchema = #from;
@@ -52,6 +68,8 @@ pub fn expand(meta: MigrateMeta) -> Result<TokenStream, darling::Error> {
                     old_state,
                 )
             }
+
+            #dry_run
         }
     })
 }
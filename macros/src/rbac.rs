@@ -8,6 +8,8 @@ use syn::Expr;
 pub struct RbacMeta {
     pub storage_key: Option<Expr>,
     pub roles: Expr,
+    #[darling(default)]
+    pub ordered: bool,
 
     // darling
     pub ident: syn::Ident,
@@ -22,6 +24,7 @@ pub fn expand(meta: RbacMeta) -> Result<TokenStream, darling::Error> {
     let RbacMeta {
         storage_key,
         roles,
+        ordered,
 
         ident,
         generics,
@@ -39,11 +42,20 @@ pub fn expand(meta: RbacMeta) -> Result<TokenStream, darling::Error> {
         }
     });
 
+    let ordered = ordered.then(|| {
+        quote! {
+            fn ordered_members() -> bool {
+                true
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::rbac::RbacInternal for #ident #ty #wher {
             type Role = #roles;
 
             #root
+            #ordered
         }
     })
 }
@@ -12,6 +12,7 @@ pub struct EventAttributeMeta {
     pub rename: Option<RenameStrategy>,
     pub rename_all: Option<RenameStrategy>,
     pub name: Option<String>,
+    pub allowed_chars: Option<String>,
 
     #[darling(rename = "crate", default = "crate::default_crate_name")]
     pub me: syn::Path,
@@ -31,6 +32,7 @@ pub fn event_attribute(
         rename,
         rename_all,
         name,
+        allowed_chars,
         serde,
         me,
         macros,
@@ -58,6 +60,7 @@ pub fn event_attribute(
     });
 
     let name = name.map(|n| quote! { name = #n, });
+    let allowed_chars = allowed_chars.map(|c| quote! { allowed_chars = #c, });
 
     let serde_str = quote! { #serde }.to_string();
     let me_str = quote! { #me }.to_string();
@@ -68,7 +71,7 @@ pub fn event_attribute(
             crate = #me_str,
             standard = #standard,
             version = #version,
-            #rename #rename_all #default_rename #name
+            #rename #rename_all #default_rename #name #allowed_chars
         )]
         #[serde(crate = #serde_str)]
         #serde_untagged
@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Expr, Type};
 
-use crate::unitify;
+use crate::{guard::Guard, unitify};
 
 use super::{nep141, nep145, nep148};
 
@@ -16,6 +16,15 @@ pub struct FungibleTokenMeta {
     pub mint_hook: Option<Type>,
     pub transfer_hook: Option<Type>,
     pub burn_hook: Option<Type>,
+    pub observer: Option<Type>,
+    #[darling(default)]
+    pub pausable: bool,
+    #[darling(default)]
+    pub burnable: bool,
+    pub burn_from: Option<Guard>,
+    pub max_balance_batch_len: Option<Expr>,
+    pub account_key_hashing: Option<Expr>,
+    pub refund_fallback: Option<Expr>,
 
     // NEP-148 fields
     pub metadata_storage_key: Option<Expr>,
@@ -23,6 +32,9 @@ pub struct FungibleTokenMeta {
     // NEP-145 fields
     pub storage_management_storage_key: Option<Expr>,
     pub force_unregister_hook: Option<Type>,
+    pub unregister_hook: Option<Type>,
+    #[darling(default)]
+    pub storage_deposit_batch: bool,
 
     // darling
     pub generics: syn::Generics,
@@ -42,11 +54,20 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
         mint_hook,
         transfer_hook,
         burn_hook,
+        observer,
+        pausable,
+        burnable,
+        burn_from,
+        max_balance_batch_len,
+        account_key_hashing,
+        refund_fallback,
 
         metadata_storage_key,
 
         storage_management_storage_key,
         force_unregister_hook,
+        unregister_hook,
+        storage_deposit_batch,
 
         generics,
         ident,
@@ -57,6 +78,7 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
 
     let all_hooks_or_unit = unitify(all_hooks.clone());
     let force_unregister_hook_or_unit = unitify(force_unregister_hook);
+    let unregister_hook_or_unit = unitify(unregister_hook);
 
     let expand_nep141 = nep141::expand(nep141::Nep141Meta {
         storage_key: core_storage_key,
@@ -66,6 +88,14 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
         mint_hook,
         transfer_hook,
         burn_hook,
+        observer,
+        pausable,
+        burnable,
+        burn_from,
+        max_balance_batch_len,
+        account_key_hashing,
+        refund_requires_registration: true,
+        refund_fallback,
 
         generics: generics.clone(),
         ident: ident.clone(),
@@ -80,6 +110,10 @@ pub fn expand(meta: FungibleTokenMeta) -> Result<TokenStream, darling::Error> {
         force_unregister_hook: Some(
             syn::parse_quote! { (#force_unregister_hook_or_unit, #me::standard::nep141::hooks::BurnNep141OnForceUnregisterHook) },
         ),
+        unregister_hook: Some(
+            syn::parse_quote! { (#unregister_hook_or_unit, #me::standard::nep141::hooks::RequireZeroNep141BalanceOnUnregisterHook) },
+        ),
+        storage_deposit_batch,
         generics: generics.clone(),
         ident: ident.clone(),
 
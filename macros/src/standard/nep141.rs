@@ -3,6 +3,8 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Expr, Type};
 
+use crate::guard::Guard;
+
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(nep141), supports(struct_named))]
 pub struct Nep141Meta {
@@ -11,6 +13,17 @@ pub struct Nep141Meta {
     pub mint_hook: Option<Type>,
     pub transfer_hook: Option<Type>,
     pub burn_hook: Option<Type>,
+    pub observer: Option<Type>,
+    #[darling(default)]
+    pub pausable: bool,
+    #[darling(default)]
+    pub burnable: bool,
+    pub burn_from: Option<Guard>,
+    pub max_balance_batch_len: Option<Expr>,
+    pub account_key_hashing: Option<Expr>,
+    #[darling(default)]
+    pub refund_requires_registration: bool,
+    pub refund_fallback: Option<Expr>,
     pub generics: syn::Generics,
     pub ident: syn::Ident,
 
@@ -28,6 +41,14 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
         mint_hook,
         transfer_hook,
         burn_hook,
+        observer,
+        pausable,
+        burnable,
+        burn_from,
+        max_balance_batch_len,
+        account_key_hashing,
+        refund_requires_registration,
+        refund_fallback,
         generics,
         ident,
 
@@ -45,19 +66,139 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
         }
     });
 
+    let max_balance_batch_len = max_balance_batch_len.map(|max_balance_batch_len| {
+        quote! {
+            fn max_balance_batch_len() -> usize {
+                #max_balance_batch_len
+            }
+        }
+    });
+
+    let account_key_hashing = account_key_hashing.map(|account_key_hashing| {
+        quote! {
+            fn account_key_hashing() -> #me::slot::KeyHashing {
+                #account_key_hashing
+            }
+        }
+    });
+
+    let can_receive_refund = refund_requires_registration.then(|| {
+        quote! {
+            fn can_receive_refund(account_id: &#near_sdk::AccountIdRef) -> bool {
+                <Self as #me::standard::nep145::Nep145ControllerInternal>::slot_account(account_id)
+                    .exists()
+            }
+        }
+    });
+
+    let refund_fallback = refund_fallback.map(|refund_fallback| {
+        quote! {
+            fn refund_fallback_account() -> Option<#near_sdk::AccountId> {
+                Some(#refund_fallback)
+            }
+        }
+    });
+
     let mint_hook = mint_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
     let transfer_hook = transfer_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
     let burn_hook = burn_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+    let observer = observer.map_or_else(|| quote! { () }, |o| quote! { #o });
 
     let default_hook = all_hooks.map_or_else(|| quote! { () }, |h| quote! { #h });
 
+    let require_unpaused = pausable.then(|| {
+        quote! {
+            <Self as #me::pause::Pause>::require_unpaused();
+        }
+    });
+
+    let burn = burnable.then(|| {
+        quote! {
+            #[payable]
+            pub fn ft_burn(
+                &mut self,
+                amount: #near_sdk::json_types::U128,
+                memo: Option<String>,
+            ) {
+                use #me::standard::nep141::*;
+
+                #require_unpaused
+
+                #near_sdk::assert_one_yocto();
+
+                let mut burn = Nep141Burn::new(amount.0, #near_sdk::env::predecessor_account_id());
+                burn.memo = memo.map(Into::into);
+
+                Nep141Controller::burn(self, &burn)
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+        }
+    });
+
+    let burn_from = burn_from.map(|guard| {
+        let guard = guard.expand(&me);
+
+        quote! {
+            #[payable]
+            pub fn ft_burn_from(
+                &mut self,
+                account_id: #near_sdk::AccountId,
+                amount: #near_sdk::json_types::U128,
+                memo: Option<String>,
+            ) {
+                use #me::standard::nep141::*;
+
+                #require_unpaused
+
+                #guard
+
+                #near_sdk::assert_one_yocto();
+
+                let mut burn = Nep141Burn::new(amount.0, account_id);
+                burn.memo = memo.map(Into::into);
+
+                Nep141Controller::burn(self, &burn)
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+            }
+        }
+    });
+
+    let burn_entrypoints = (burn.is_some() || burn_from.is_some()).then(|| {
+        quote! {
+            #[#near_sdk::near]
+            impl #imp #ident #ty #wher {
+                #burn
+                #burn_from
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::standard::nep141::Nep141ControllerInternal for #ident #ty #wher {
             type MintHook = (#mint_hook, #default_hook);
             type TransferHook = (#transfer_hook, #default_hook);
             type BurnHook = (#burn_hook, #default_hook);
+            type Observer = #observer;
 
             #root
+            #max_balance_batch_len
+            #account_key_hashing
+            #can_receive_refund
+            #refund_fallback
+        }
+
+        #[#near_sdk::near]
+        impl #imp #ident #ty #wher {
+            pub fn ft_balance_of_batch(
+                &self,
+                account_ids: Vec<#near_sdk::AccountId>,
+            ) -> Vec<#near_sdk::json_types::U128> {
+                #me::standard::nep141::Nep141Controller::balance_of_batch(self, &account_ids)
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()))
+                    .into_iter()
+                    .map(Into::into)
+                    .collect()
+            }
         }
 
         #[#near_sdk::near]
@@ -71,6 +212,8 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
             ) {
                 use #me::standard::nep141::*;
 
+                #require_unpaused
+
                 #near_sdk::assert_one_yocto();
                 let sender_id = #near_sdk::env::predecessor_account_id();
                 let amount: u128 = amount.into();
@@ -98,6 +241,8 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
             ) -> #near_sdk::Promise {
                 use #me::standard::nep141::*;
 
+                #require_unpaused
+
                 let prepaid_gas = #near_sdk::env::prepaid_gas();
 
                 #near_sdk::require!(
@@ -181,19 +326,48 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                     let receiver_balance = Nep141Controller::balance_of(self, &receiver_id);
                     if receiver_balance > 0 {
                         let refund_amount = std::cmp::min(receiver_balance, unused_amount);
-                        let transfer = Nep141Transfer {
-                            sender_id: receiver_id.into(),
-                            receiver_id: sender_id.into(),
-                            amount: refund_amount,
-                            memo: None,
-                            msg: None,
-                            revert: true,
-                        };
-
-                        Nep141Controller::transfer(self, &transfer)
-                            .unwrap_or_else(|e| env::panic_str(&e.to_string()));
-
-                        refund_amount
+
+                        if <Self as Nep141ControllerInternal>::can_receive_refund(&sender_id) {
+                            let transfer = Nep141Transfer {
+                                sender_id: receiver_id.into(),
+                                receiver_id: sender_id.into(),
+                                amount: refund_amount,
+                                memo: Some(REFUND_MEMO.into()),
+                                msg: None,
+                                revert: true,
+                            };
+
+                            Nep141Controller::transfer(self, &transfer)
+                                .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+
+                            refund_amount
+                        } else {
+                            // The sender is gone, so the tokens are not
+                            // refunded to them. They count as used.
+                            if let Some(fallback_id) =
+                                <Self as Nep141ControllerInternal>::refund_fallback_account()
+                            {
+                                let transfer = Nep141Transfer {
+                                    sender_id: receiver_id.into(),
+                                    receiver_id: fallback_id.into(),
+                                    amount: refund_amount,
+                                    memo: Some(UNREFUNDABLE_MEMO.into()),
+                                    msg: None,
+                                    revert: true,
+                                };
+
+                                Nep141Controller::transfer(self, &transfer)
+                                    .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+                            } else {
+                                let burn = Nep141Burn::new(refund_amount, receiver_id)
+                                    .memo(UNREFUNDABLE_MEMO);
+
+                                Nep141Controller::burn(self, &burn)
+                                    .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+                            }
+
+                            0
+                        }
                     } else {
                         0
                     }
@@ -205,5 +379,7 @@ pub fn expand(meta: Nep141Meta) -> Result<TokenStream, darling::Error> {
                 U128(amount - refunded_amount)
             }
         }
+
+        #burn_entrypoints
     })
 }
@@ -9,6 +9,9 @@ pub struct Nep145Meta {
     pub storage_key: Option<Expr>,
     pub all_hooks: Option<Type>,
     pub force_unregister_hook: Option<Type>,
+    pub unregister_hook: Option<Type>,
+    #[darling(default)]
+    pub storage_deposit_batch: bool,
     pub generics: syn::Generics,
     pub ident: syn::Ident,
 
@@ -24,6 +27,8 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
         storage_key,
         all_hooks,
         force_unregister_hook,
+        unregister_hook,
+        storage_deposit_batch,
         generics,
         ident,
 
@@ -44,14 +49,53 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
     let all_hooks = all_hooks.map_or_else(|| quote! { () }, |h| quote! { #h });
     let force_unregister_hook =
         force_unregister_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+    let unregister_hook = unregister_hook.map_or_else(|| quote! { () }, |h| quote! { #h });
+
+    let storage_deposit_batch = storage_deposit_batch.then(|| {
+        quote! {
+            #[#near_sdk::near]
+            impl #imp #ident #ty #wher {
+                #[payable]
+                pub fn storage_deposit_batch(
+                    &mut self,
+                    accounts: Vec<(#near_sdk::AccountId, Option<bool>)>,
+                ) -> Vec<#me::standard::nep145::StorageBalance> {
+                    use #me::standard::nep145::*;
+                    use #near_sdk::{env, Promise};
+
+                    // Registered accounts are never charged again, so
+                    // `registration_only` has no effect here.
+                    let accounts = accounts
+                        .into_iter()
+                        .map(|(account_id, _registration_only)| account_id)
+                        .collect::<Vec<_>>();
+
+                    let attached = env::attached_deposit();
+                    let (amount, storage_balances) =
+                        Nep145Controller::deposit_to_storage_accounts_batch(self, &accounts, attached)
+                            .unwrap_or_else(|e| env::panic_str(&format!("Storage deposit error: {}", e)));
+
+                    let refund = attached.saturating_sub(amount);
+                    if !refund.is_zero() {
+                        Promise::new(env::predecessor_account_id()).transfer(refund);
+                    }
+
+                    storage_balances
+                }
+            }
+        }
+    });
 
     Ok(quote! {
         impl #imp #me::standard::nep145::Nep145ControllerInternal for #ident #ty #wher {
             type ForceUnregisterHook = (#force_unregister_hook, #all_hooks);
+            type UnregisterHook = #unregister_hook;
 
             #root
         }
 
+        #storage_deposit_batch
+
         #[#near_sdk::near]
         impl #imp #me::standard::nep145::Nep145 for #ident #ty #wher {
             #[payable]
@@ -64,20 +108,29 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
                 use #near_sdk::{env, Promise};
 
                 let bounds = Nep145Controller::get_storage_balance_bounds(self);
+                let predecessor = env::predecessor_account_id();
+                let account_id = account_id.unwrap_or_else(|| predecessor.clone());
+
+                let current_total = Nep145Controller::get_storage_balance(self, &account_id)
+                    .ok()
+                    .map(|balance| balance.total);
 
                 let attached = env::attached_deposit();
-                let amount = bounds.bound(attached, registration_only.unwrap_or(false));
+                let amount = bounds.bound_deposit(
+                    current_total,
+                    attached,
+                    registration_only.unwrap_or(false),
+                );
                 let refund = attached.checked_sub(amount).unwrap_or_else(|| {
                     env::panic_str(&format!(
                         "Attached deposit {} is less than required {}",
                         attached, amount,
                     ))
                 });
-                let predecessor = env::predecessor_account_id();
 
                 let storage_balance = Nep145Controller::deposit_to_storage_account(
                     self,
-                    &account_id.unwrap_or_else(|| predecessor.clone()),
+                    &account_id,
                     amount,
                 )
                 .unwrap_or_else(|e| env::panic_str(&format!("Storage deposit error: {}", e)));
@@ -94,20 +147,23 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
                 use #me::standard::nep145::*;
                 use #near_sdk::{env, Promise};
 
-                near_sdk::assert_one_yocto();
+                #near_sdk::assert_one_yocto();
 
                 let predecessor = env::predecessor_account_id();
 
-                let balance = Nep145Controller::get_storage_balance(self, &predecessor)
-                    .unwrap_or_else(|e| env::panic_str(&e.to_string()));
-
-                let amount = amount.unwrap_or(balance.available);
-
-                if amount.is_zero() {
-                    return balance;
-                }
+                let result = match amount {
+                    Some(amount) if amount.is_zero() => {
+                        return Nep145Controller::get_storage_balance(self, &predecessor)
+                            .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+                    }
+                    Some(amount) => {
+                        Nep145Controller::withdraw_from_storage_account(self, &predecessor, amount)
+                            .map(|balance| (amount, balance))
+                    }
+                    None => Nep145Controller::withdraw_available_from_storage_account(self, &predecessor),
+                };
 
-                let new_balance = Nep145Controller::withdraw_from_storage_account(self, &predecessor, amount)
+                let (amount, new_balance) = result
                     .unwrap_or_else(|e| env::panic_str(&format!("Storage withdraw error: {}", e)));
 
                 Promise::new(predecessor).transfer(amount);
@@ -115,11 +171,12 @@ pub fn expand(meta: Nep145Meta) -> Result<TokenStream, darling::Error> {
                 new_balance
             }
 
+            #[payable]
             fn storage_unregister(&mut self, force: Option<bool>) -> bool {
                 use #me::standard::nep145::*;
                 use #near_sdk::{env, Promise};
 
-                near_sdk::assert_one_yocto();
+                #near_sdk::assert_one_yocto();
 
                 let predecessor = env::predecessor_account_id();
 
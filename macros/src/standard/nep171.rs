@@ -1,4 +1,4 @@
-use darling::FromDeriveInput;
+use darling::{FromDeriveInput, FromMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse_quote, Expr, Type};
@@ -15,6 +15,16 @@ pub struct Nep171Meta {
     pub burn_hook: Option<Type>,
     pub check_external_transfer: Option<Type>,
     pub token_data: Option<Type>,
+    #[darling(default)]
+    pub token_loader: TokenLoader,
+    pub burnable: Option<Burnable>,
+    #[darling(default)]
+    pub pausable: bool,
+    #[darling(default)]
+    pub track_supply: bool,
+    pub max_token_id_len: Option<Expr>,
+    pub max_memo_len: Option<Expr>,
+    pub receiver_gas_share: Option<Expr>,
 
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -26,6 +36,56 @@ pub struct Nep171Meta {
     pub near_sdk: syn::Path,
 }
 
+/// Built-in token metadata loaders composed into `nft_token`. Parsed from a
+/// comma-separated list of `"metadata"` and `"approvals"`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokenLoader {
+    pub metadata: bool,
+    pub approvals: bool,
+}
+
+impl FromMeta for TokenLoader {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let mut loader = Self::default();
+        for item in value.split(',').map(str::trim) {
+            match item {
+                "metadata" => loader.metadata = true,
+                "approvals" => loader.approvals = true,
+                _ => {
+                    return Err(darling::Error::custom(format!(
+                        r#"Invalid token loader "{item}", expected "metadata" or "approvals""#,
+                    )))
+                }
+            }
+        }
+        Ok(loader)
+    }
+}
+
+/// What `nft_burn` removes. Parsed from a bare `burnable` flag, or from
+/// `burnable = "metadata"` to also remove NEP-177 token metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Burnable {
+    Tokens,
+    WithMetadata,
+}
+
+impl FromMeta for Burnable {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::Tokens)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        if value == "metadata" {
+            Ok(Self::WithMetadata)
+        } else {
+            Err(darling::Error::custom(format!(
+                r#"Invalid value "{value}", expected "metadata""#,
+            )))
+        }
+    }
+}
+
 pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
     let Nep171Meta {
         storage_key,
@@ -35,6 +95,13 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
         burn_hook,
         check_external_transfer,
         token_data,
+        token_loader,
+        burnable,
+        pausable,
+        track_supply,
+        max_token_id_len,
+        max_memo_len,
+        receiver_gas_share,
 
         generics,
         ident,
@@ -46,6 +113,16 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
     let (imp, ty, wher) = generics.split_for_impl();
 
     let token_data = unitify(token_data);
+    let metadata_loader: Type = if token_loader.metadata {
+        parse_quote! { #me::standard::nep177::TokenMetadata }
+    } else {
+        parse_quote! { () }
+    };
+    let approvals_loader: Type = if token_loader.approvals {
+        parse_quote! { #me::standard::nep178::TokenApprovals }
+    } else {
+        parse_quote! { () }
+    };
 
     let check_external_transfer = check_external_transfer.unwrap_or_else(|| {
         parse_quote! { #me::standard::nep171::DefaultCheckExternalTransfer }
@@ -59,6 +136,102 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
         }
     });
 
+    let max_token_id_len = max_token_id_len.map(|max_token_id_len| {
+        quote! {
+            fn max_token_id_len() -> usize {
+                #max_token_id_len
+            }
+
+            fn max_transfer_token_id_len() -> Option<usize> {
+                Some(#max_token_id_len)
+            }
+        }
+    });
+
+    let max_memo_len = max_memo_len.map(|max_memo_len| {
+        quote! {
+            fn max_memo_len() -> usize {
+                #max_memo_len
+            }
+        }
+    });
+
+    let receiver_gas_share = receiver_gas_share.map(|receiver_gas_share| {
+        quote! {
+            fn receiver_gas_share() -> u8 {
+                #receiver_gas_share
+            }
+        }
+    });
+
+    let (track_supply, total_supply) = if track_supply {
+        (
+            Some(quote! {
+                fn track_supply() -> bool {
+                    true
+                }
+            }),
+            Some(quote! {
+                #[#near_sdk::near]
+                impl #imp #ident #ty #wher {
+                    pub fn nft_total_supply(&self) -> #near_sdk::json_types::U128 {
+                        u128::from(
+                            <Self as #me::standard::nep171::Nep171Controller>::total_supply(self),
+                        )
+                        .into()
+                    }
+                }
+            }),
+        )
+    } else {
+        (None, None)
+    };
+
+    let require_unpaused = pausable.then(|| {
+        quote! {
+            <Self as #me::pause::Pause>::require_unpaused();
+        }
+    });
+
+    let burn = burnable.map(|burnable| {
+        let burn_tokens = if burnable == Burnable::WithMetadata || token_loader.metadata {
+            quote! {
+                <Self as #me::standard::nep177::Nep177Controller>::burn_batch_with_metadata(
+                    self,
+                    &token_ids,
+                    &#near_sdk::env::predecessor_account_id(),
+                )
+            }
+        } else {
+            quote! {
+                <Self as Nep171Controller>::burn(
+                    self,
+                    &action::Nep171Burn::new(
+                        token_ids,
+                        #near_sdk::env::predecessor_account_id(),
+                    ),
+                )
+            }
+        };
+
+        quote! {
+            #[#near_sdk::near]
+            impl #imp #ident #ty #wher {
+                #[payable]
+                pub fn nft_burn(&mut self, token_ids: Vec<#me::standard::nep171::TokenId>) {
+                    use #me::standard::nep171::*;
+
+                    #require_unpaused
+
+                    #near_sdk::assert_one_yocto();
+
+                    #burn_tokens
+                        .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+                }
+            }
+        }
+    });
+
     let all_hooks = unitify(all_hooks);
     let mint_hook = unitify(mint_hook);
     let transfer_hook = unitify(transfer_hook);
@@ -71,9 +244,13 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
             type BurnHook = (#burn_hook, #all_hooks);
 
             type CheckExternalTransfer = #check_external_transfer;
-            type LoadTokenMetadata = #token_data;
+            type LoadTokenMetadata = (#token_data, (#metadata_loader, #approvals_loader));
 
             #root
+            #track_supply
+            #max_token_id_len
+            #max_memo_len
+            #receiver_gas_share
         }
 
         #[#near_sdk::near]
@@ -134,6 +311,8 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
             ) {
                 use #me::standard::nep171::*;
 
+                #require_unpaused
+
                 #near_sdk::assert_one_yocto();
 
                 let sender_id = #near_sdk::env::predecessor_account_id();
@@ -163,12 +342,15 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
             ) -> #near_sdk::PromiseOrValue<bool> {
                 use #me::standard::nep171::*;
 
+                #require_unpaused
+
                 #near_sdk::assert_one_yocto();
 
-                #near_sdk::require!(
-                    #near_sdk::env::prepaid_gas() >= GAS_FOR_NFT_TRANSFER_CALL,
-                    INSUFFICIENT_GAS_MESSAGE,
-                );
+                let gas = TransferCallGas::allocate(
+                    #near_sdk::env::prepaid_gas(),
+                    <Self as Nep171ControllerInternal>::receiver_gas_share(),
+                )
+                .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
 
                 let sender_id = #near_sdk::env::predecessor_account_id();
 
@@ -186,7 +368,7 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
                     .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
 
                 ext_nep171_receiver::ext(receiver_id.clone().into())
-                    .with_static_gas(#near_sdk::env::prepaid_gas().saturating_sub(GAS_FOR_NFT_TRANSFER_CALL))
+                    .with_static_gas(gas.receiver)
                     .nft_on_transfer(
                         sender_id.clone().into(),
                         sender_id.clone().into(),
@@ -195,7 +377,7 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
                     )
                     .then(
                         ext_nep171_resolver::ext(#near_sdk::env::current_account_id())
-                            .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                            .with_static_gas(gas.resolver)
                             .nft_resolve_transfer(
                                 sender_id.clone().into(),
                                 receiver_id.clone().into(),
@@ -213,5 +395,9 @@ pub fn expand(meta: Nep171Meta) -> Result<TokenStream, darling::Error> {
                 <Self as #me::standard::nep171::Nep171Controller>::load_token(self, &token_id)
             }
         }
+
+        #burn
+
+        #total_supply
     })
 }
@@ -3,10 +3,13 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Expr;
 
+use crate::guard::Guard;
+
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(nep177), supports(struct_named))]
 pub struct Nep177Meta {
     pub storage_key: Option<Expr>,
+    pub update_metadata: Option<Guard>,
 
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -21,6 +24,7 @@ pub struct Nep177Meta {
 pub fn expand(meta: Nep177Meta) -> Result<TokenStream, darling::Error> {
     let Nep177Meta {
         storage_key,
+        update_metadata,
 
         generics,
         ident,
@@ -39,11 +43,53 @@ pub fn expand(meta: Nep177Meta) -> Result<TokenStream, darling::Error> {
         }
     });
 
+    let update_metadata = update_metadata.map(|guard| {
+        let guard = guard.expand(&me);
+
+        quote! {
+            #[#near_sdk::near]
+            impl #imp #ident #ty #wher {
+                #[payable]
+                pub fn update_contract_metadata(
+                    &mut self,
+                    patch: #me::standard::nep177::ContractMetadataPatch,
+                ) {
+                    #guard
+
+                    #near_sdk::assert_one_yocto();
+
+                    #me::standard::nep177::Nep177Controller::update_contract_metadata(self, patch)
+                        .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+                }
+
+                #[payable]
+                pub fn update_token_metadata(
+                    &mut self,
+                    token_id: #me::standard::nep171::TokenId,
+                    metadata: #me::standard::nep177::TokenMetadata,
+                ) {
+                    #guard
+
+                    #near_sdk::assert_one_yocto();
+
+                    #me::standard::nep177::Nep177Controller::set_token_metadata(
+                        self,
+                        &token_id,
+                        &metadata,
+                    )
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+                }
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::standard::nep177::Nep177ControllerInternal for #ident #ty #wher {
             #root
         }
 
+        #update_metadata
+
         #[#near_sdk::near]
         impl #imp #me::standard::nep177::Nep177 for #ident #ty #wher {
             fn nft_metadata(&self) -> #me::standard::nep177::ContractMetadata {
@@ -13,6 +13,8 @@ pub struct Nep178Meta {
     pub approve_hook: Option<Type>,
     pub revoke_hook: Option<Type>,
     pub revoke_all_hook: Option<Type>,
+    #[darling(default)]
+    pub batch_approvals: bool,
 
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -31,6 +33,7 @@ pub fn expand(meta: Nep178Meta) -> Result<TokenStream, darling::Error> {
         approve_hook,
         revoke_hook,
         revoke_all_hook,
+        batch_approvals,
 
         generics,
         ident,
@@ -54,6 +57,77 @@ pub fn expand(meta: Nep178Meta) -> Result<TokenStream, darling::Error> {
     let revoke_hook = unitify(revoke_hook);
     let revoke_all_hook = unitify(revoke_all_hook);
 
+    let batch_approvals = batch_approvals.then(|| {
+        quote! {
+            #[#near_sdk::near]
+            impl #imp #ident #ty #wher {
+                #[payable]
+                pub fn nft_approve_batch(
+                    &mut self,
+                    token_ids: Vec<#me::standard::nep171::TokenId>,
+                    account_id: #near_sdk::AccountId,
+                    msg: Option<String>,
+                ) -> Vec<#me::standard::nep178::ApprovalId> {
+                    use #me::standard::nep178::*;
+
+                    #me::utils::assert_nonzero_deposit();
+
+                    let predecessor = #near_sdk::env::predecessor_account_id();
+
+                    let actions = token_ids
+                        .iter()
+                        .map(|token_id| action::Nep178Approve {
+                            token_id: token_id.clone(),
+                            current_owner_id: predecessor.clone().into(),
+                            account_id: account_id.clone().into(),
+                        })
+                        .collect::<Vec<_>>();
+
+                    let approval_ids = Nep178Controller::approve_batch(self, &actions)
+                        .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+
+                    if let Some(msg) = msg {
+                        for (token_id, approval_id) in token_ids.into_iter().zip(approval_ids.iter()) {
+                            let _ = ext_nep178_receiver::ext(account_id.clone()).nft_on_approve(
+                                token_id,
+                                predecessor.clone(),
+                                *approval_id,
+                                msg.clone(),
+                            );
+                        }
+                    }
+
+                    approval_ids
+                }
+
+                #[payable]
+                pub fn nft_revoke_batch(
+                    &mut self,
+                    token_ids: Vec<#me::standard::nep171::TokenId>,
+                    account_id: #near_sdk::AccountId,
+                ) {
+                    use #me::standard::nep178::*;
+
+                    #near_sdk::assert_one_yocto();
+
+                    let predecessor = #near_sdk::env::predecessor_account_id();
+
+                    let actions = token_ids
+                        .into_iter()
+                        .map(|token_id| action::Nep178Revoke {
+                            token_id,
+                            current_owner_id: predecessor.clone().into(),
+                            account_id: account_id.clone().into(),
+                        })
+                        .collect::<Vec<_>>();
+
+                    Nep178Controller::revoke_batch(self, &actions)
+                        .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+                }
+            }
+        }
+    });
+
     Ok(quote! {
         impl #imp #me::standard::nep178::Nep178ControllerInternal for #ident #ty #wher {
             type ApproveHook = (#approve_hook, #all_hooks);
@@ -117,7 +191,10 @@ pub fn expand(meta: Nep178Meta) -> Result<TokenStream, darling::Error> {
             }
 
             #[payable]
-            fn nft_revoke_all(&mut self, token_id: #me::standard::nep171::TokenId) {
+            fn nft_revoke_all(
+                &mut self,
+                token_id: #me::standard::nep171::TokenId,
+            ) -> Vec<#near_sdk::AccountId> {
                 use #me::standard::nep178::*;
 
                 #near_sdk::assert_one_yocto();
@@ -130,7 +207,7 @@ pub fn expand(meta: Nep178Meta) -> Result<TokenStream, darling::Error> {
                 };
 
                 Nep178Controller::revoke_all(self, &action)
-                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
+                    .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()))
             }
 
             fn nft_is_approved(
@@ -153,5 +230,7 @@ pub fn expand(meta: Nep178Meta) -> Result<TokenStream, darling::Error> {
                 }
             }
         }
+
+        #batch_approvals
     })
 }
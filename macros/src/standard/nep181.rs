@@ -4,10 +4,14 @@ use quote::quote;
 use syn::Expr;
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(nep181), supports(struct_named))]
+#[darling(attributes(nep181), forward_attrs(nep171), supports(struct_named))]
 pub struct Nep181Meta {
     pub storage_key: Option<Expr>,
 
+    /// `#[nep171(...)]` attributes, checked for options that conflict with
+    /// NEP-181.
+    pub attrs: Vec<syn::Attribute>,
+
     pub generics: syn::Generics,
     pub ident: syn::Ident,
 
@@ -21,6 +25,7 @@ pub struct Nep181Meta {
 pub fn expand(meta: Nep181Meta) -> Result<TokenStream, darling::Error> {
     let Nep181Meta {
         storage_key,
+        attrs,
 
         generics,
         ident,
@@ -29,6 +34,13 @@ pub fn expand(meta: Nep181Meta) -> Result<TokenStream, darling::Error> {
         near_sdk,
     } = meta;
 
+    if let Some(track_supply) = attrs.iter().find_map(find_track_supply) {
+        return Err(darling::Error::custom(
+            "`#[nep171(track_supply)]` cannot be used with `Nep181`: both export `nft_total_supply`. NEP-181 already tracks the total supply.",
+        )
+        .with_span(&track_supply));
+    }
+
     let (imp, ty, wher) = generics.split_for_impl();
 
     let root = storage_key.map(|storage_key| {
@@ -114,3 +126,21 @@ pub fn expand(meta: Nep181Meta) -> Result<TokenStream, darling::Error> {
         }
     })
 }
+
+fn find_track_supply(attr: &syn::Attribute) -> Option<syn::Path> {
+    let mut found = None;
+
+    let _ = attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("track_supply") {
+            found = Some(meta.path.clone());
+        }
+
+        if meta.input.peek(syn::Token![=]) {
+            meta.value()?.parse::<Expr>()?;
+        }
+
+        Ok(())
+    });
+
+    found
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 use darling::{FromDeriveInput, FromVariant};
 use proc_macro2::TokenStream;
@@ -18,6 +18,7 @@ pub struct Nep297Meta {
     pub name: Option<String>,
     pub rename: Option<RenameStrategy>,
     pub rename_all: Option<RenameStrategy>,
+    pub allowed_chars: Option<String>,
     pub ident: syn::Ident,
     pub generics: syn::Generics,
     pub data: darling::ast::Data<EventVariantReceiver, ()>,
@@ -66,6 +67,10 @@ pub struct EventVariantReceiver {
     pub name: Option<String>,
 }
 
+/// Characters other than ASCII alphanumerics that may appear in event names
+/// by default. Covers the separators produced by all rename strategies.
+const DEFAULT_ALLOWED_CHARS: &str = "_- ";
+
 pub fn expand(meta: Nep297Meta) -> Result<TokenStream, darling::Error> {
     let Nep297Meta {
         standard,
@@ -73,12 +78,15 @@ pub fn expand(meta: Nep297Meta) -> Result<TokenStream, darling::Error> {
         name,
         rename,
         rename_all,
+        allowed_chars,
         ident,
         generics,
         data,
         me,
     } = meta;
 
+    let allowed_chars = allowed_chars.as_deref().unwrap_or(DEFAULT_ALLOWED_CHARS);
+
     let (imp, ty, wher) = generics.split_for_impl();
 
     // Variant attributes
@@ -92,7 +100,10 @@ pub fn expand(meta: Nep297Meta) -> Result<TokenStream, darling::Error> {
                 ident.to_string()
             };
 
-            (quote! { #transformed_name }, vec![transformed_name])
+            (
+                quote! { #transformed_name },
+                vec![(transformed_name, ident.clone())],
+            )
         }
         darling::ast::Data::Enum(variants) => {
             let (arms, used_names) = variants
@@ -121,7 +132,7 @@ pub fn expand(meta: Nep297Meta) -> Result<TokenStream, darling::Error> {
                                 quote! { Self :: #i  => #transformed_name , }
                             }
                         },
-                        transformed_name,
+                        (transformed_name, i.clone()),
                     )
                 })
                 .unzip::<_, _, Vec<_>, Vec<_>>();
@@ -139,13 +150,27 @@ pub fn expand(meta: Nep297Meta) -> Result<TokenStream, darling::Error> {
 
     let mut e = darling::Error::accumulator();
 
-    let mut no_duplicate_names = HashSet::<&String>::new();
-    for used_name in &used_names {
-        let fresh_insertion = no_duplicate_names.insert(used_name);
-        if !fresh_insertion {
-            e.push(darling::Error::custom(format!(
-                "Event name collision: `{used_name}`",
-            )));
+    let mut first_use = HashMap::<&String, &syn::Ident>::new();
+    for (used_name, used_by) in &used_names {
+        if let Some(c) = used_name
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && !allowed_chars.contains(*c))
+        {
+            e.push(
+                darling::Error::custom(format!(
+                    "Event name `{used_name}` contains disallowed character {c:?}",
+                ))
+                .with_span(used_by),
+            );
+        }
+
+        if let Some(first) = first_use.insert(used_name, used_by) {
+            e.push(
+                darling::Error::custom(format!(
+                    "Event name collision: `{used_name}` is used by both `{first}` and `{used_by}`",
+                ))
+                .with_span(used_by),
+            );
         }
     }
 
@@ -191,4 +216,96 @@ mod tests {
         let meta = Nep297Meta::from_derive_input(&ast).unwrap();
         super::expand(meta).unwrap();
     }
+
+    #[test]
+    #[should_panic = "Event name collision: `hello_world` is used by both `HelloWorld` and `Hello_World`"]
+    fn disallow_duplicate_names_after_rename() {
+        let ast = syn::parse_str(
+            r#"
+            #[derive(Nep297)]
+            #[nep297(standard = "x-name-collision", version = "1.0.0", rename_all = "snake_case")]
+            enum NameCollision {
+                HelloWorld,
+                Hello_World,
+            }
+        "#,
+        )
+        .unwrap();
+
+        let meta = Nep297Meta::from_derive_input(&ast).unwrap();
+        super::expand(meta).unwrap();
+    }
+
+    #[test]
+    fn allow_distinct_names_after_rename() {
+        let ast = syn::parse_str(
+            r#"
+            #[derive(Nep297)]
+            #[nep297(standard = "x-name-collision", version = "1.0.0", rename_all = "snake_case")]
+            enum NoCollision {
+                HelloWorld,
+                #[nep297(name = "hello_world_2")]
+                Hello_World,
+            }
+        "#,
+        )
+        .unwrap();
+
+        let meta = Nep297Meta::from_derive_input(&ast).unwrap();
+        super::expand(meta).unwrap();
+    }
+
+    #[test]
+    #[should_panic = "Event name `nft.mint` contains disallowed character '.'"]
+    fn disallow_custom_name_characters() {
+        let ast = syn::parse_str(
+            r#"
+            #[derive(Nep297)]
+            #[nep297(standard = "x-chars", version = "1.0.0", name = "nft.mint")]
+            struct NftMint;
+        "#,
+        )
+        .unwrap();
+
+        let meta = Nep297Meta::from_derive_input(&ast).unwrap();
+        super::expand(meta).unwrap();
+    }
+
+    #[test]
+    fn allow_custom_name_characters() {
+        let ast = syn::parse_str(
+            r#"
+            #[derive(Nep297)]
+            #[nep297(standard = "x-chars", version = "1.0.0", allowed_chars = "_.")]
+            enum Chars {
+                #[nep297(name = "nft.mint")]
+                NftMint,
+                #[nep297(name = "nft_burn")]
+                NftBurn,
+            }
+        "#,
+        )
+        .unwrap();
+
+        let meta = Nep297Meta::from_derive_input(&ast).unwrap();
+        super::expand(meta).unwrap();
+    }
+
+    #[test]
+    #[should_panic = "Event name `nft-transfer` contains disallowed character '-'"]
+    fn allowed_chars_replaces_default() {
+        let ast = syn::parse_str(
+            r#"
+            #[derive(Nep297)]
+            #[nep297(standard = "x-chars", version = "1.0.0", rename_all = "kebab-case", allowed_chars = "_")]
+            enum Chars {
+                NftTransfer,
+            }
+        "#,
+        )
+        .unwrap();
+
+        let meta = Nep297Meta::from_derive_input(&ast).unwrap();
+        super::expand(meta).unwrap();
+    }
 }
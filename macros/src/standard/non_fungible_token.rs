@@ -1,9 +1,9 @@
-use darling::FromDeriveInput;
+use darling::{FromDeriveInput, FromMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse_quote, Expr, Type};
 
-use crate::unitify;
+use crate::{guard::Guard, unitify};
 
 use super::{nep145, nep171, nep177, nep178, nep181};
 
@@ -15,6 +15,7 @@ pub struct NonFungibleTokenMeta {
     // NEP-145 fields
     pub storage_management_storage_key: Option<Expr>,
     pub force_unregister_hook: Option<Type>,
+    pub unregister_hook: Option<Type>,
 
     // NEP-171 fields
     pub core_storage_key: Option<Expr>,
@@ -23,9 +24,15 @@ pub struct NonFungibleTokenMeta {
     pub burn_hook: Option<Type>,
     pub token_data: Option<Type>,
     pub check_external_transfer: Option<Type>,
+    #[darling(default)]
+    pub pausable: bool,
+    pub max_token_id_len: Option<Expr>,
+    pub max_memo_len: Option<Expr>,
+    pub receiver_gas_share: Option<Expr>,
 
     // NEP-177 fields
     pub metadata_storage_key: Option<Expr>,
+    pub update_metadata: Option<Guard>,
 
     // NEP-178 fields
     pub approval_storage_key: Option<Expr>,
@@ -36,6 +43,10 @@ pub struct NonFungibleTokenMeta {
     // NEP-181 fields
     pub enumeration_storage_key: Option<Expr>,
 
+    // Optional entrypoints
+    #[darling(default)]
+    pub entrypoints: Entrypoints,
+
     // darling
     pub generics: syn::Generics,
     pub ident: syn::Ident,
@@ -47,12 +58,27 @@ pub struct NonFungibleTokenMeta {
     pub near_sdk: syn::Path,
 }
 
+/// Optional entrypoints, e.g. `entrypoints(burn, batch_approvals)`.
+#[derive(Debug, Default, FromMeta)]
+pub struct Entrypoints {
+    /// `nft_burn` (NEP-171).
+    #[darling(default)]
+    pub burn: bool,
+    /// `nft_approve_batch` and `nft_revoke_batch` (NEP-178).
+    #[darling(default)]
+    pub batch_approvals: bool,
+    /// `storage_deposit_batch` (NEP-145).
+    #[darling(default)]
+    pub storage_deposit_batch: bool,
+}
+
 pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error> {
     let NonFungibleTokenMeta {
         all_hooks,
 
         storage_management_storage_key,
         force_unregister_hook,
+        unregister_hook,
 
         core_storage_key,
         mint_hook,
@@ -60,8 +86,13 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
         burn_hook,
         token_data,
         check_external_transfer,
+        pausable,
+        max_token_id_len,
+        max_memo_len,
+        receiver_gas_share,
 
         metadata_storage_key,
+        update_metadata,
 
         approval_storage_key,
         approve_hook,
@@ -70,6 +101,8 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
 
         enumeration_storage_key,
 
+        entrypoints,
+
         generics,
         ident,
 
@@ -86,14 +119,14 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
         force_unregister_hook: Some(
             parse_quote! { (#force_unregister_hook, #me::standard::nep171::hooks::BurnNep171OnForceUnregisterHook) },
         ),
+        unregister_hook,
+        storage_deposit_batch: entrypoints.storage_deposit_batch,
         generics: generics.clone(),
         ident: ident.clone(),
         me: me.clone(),
         near_sdk: near_sdk.clone(),
     });
 
-    let token_data = unitify(token_data);
-
     let expand_nep171 = nep171::expand(nep171::Nep171Meta {
         storage_key: core_storage_key,
         all_hooks: Some(parse_quote! { (
@@ -112,10 +145,17 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
         check_external_transfer: Some(check_external_transfer.unwrap_or_else(|| {
             parse_quote! { #me::standard::nep178::TokenApprovals }
         })),
-        token_data: Some(syn::parse_quote! { (
-            #token_data,
-            (#me::standard::nep177::TokenMetadata, #me::standard::nep178::TokenApprovals),
-        ) }),
+        token_data,
+        token_loader: nep171::TokenLoader {
+            metadata: true,
+            approvals: true,
+        },
+        burnable: entrypoints.burn.then_some(nep171::Burnable::WithMetadata),
+        pausable,
+        track_supply: false,
+        max_token_id_len,
+        max_memo_len,
+        receiver_gas_share,
 
         generics: generics.clone(),
         ident: ident.clone(),
@@ -126,6 +166,7 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
 
     let expand_nep177 = nep177::expand(nep177::Nep177Meta {
         storage_key: metadata_storage_key,
+        update_metadata,
 
         generics: generics.clone(),
         ident: ident.clone(),
@@ -140,6 +181,7 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
         approve_hook,
         revoke_hook,
         revoke_all_hook,
+        batch_approvals: entrypoints.batch_approvals,
 
         generics: generics.clone(),
         ident: ident.clone(),
@@ -149,6 +191,7 @@ pub fn expand(meta: NonFungibleTokenMeta) -> Result<TokenStream, darling::Error>
 
     let expand_nep181 = nep181::expand(nep181::Nep181Meta {
         storage_key: enumeration_storage_key,
+        attrs: vec![],
         generics,
         ident,
         me,
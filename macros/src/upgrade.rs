@@ -5,6 +5,7 @@ use syn::Expr;
 
 #[derive(Debug, Clone)]
 pub enum HookBody {
+    Auto,
     Empty,
     Custom,
     Owner,
@@ -12,13 +13,13 @@ pub enum HookBody {
 }
 
 impl FromMeta for HookBody {
-    fn from_none() -> Option<Self> {
-        Some(Self::Custom)
-    }
-
     fn from_string(value: &str) -> darling::Result<Self> {
-        if value == "empty" {
+        if value == "auto" {
+            Ok(HookBody::Auto)
+        } else if value == "empty" {
             Ok(HookBody::Empty)
+        } else if value == "custom" {
+            Ok(HookBody::Custom)
         } else if value == "owner" {
             Ok(HookBody::Owner)
         } else if let Some(b) = value
@@ -30,7 +31,7 @@ impl FromMeta for HookBody {
             Ok(b)
         } else {
             Err(darling::Error::custom(format!(
-                r#"Invalid value "{value}", expected "empty", "owner", or "role(...)""#,
+                r#"Invalid value "{value}", expected "auto", "empty", "custom", "owner", or "role(...)""#,
             )))
         }
     }
@@ -40,6 +41,7 @@ impl FromMeta for HookBody {
 pub enum Serializer {
     Borsh,
     JsonBase64,
+    Raw,
 }
 
 impl FromMeta for Serializer {
@@ -47,22 +49,27 @@ impl FromMeta for Serializer {
         match value {
             "borsh" => Ok(Self::Borsh),
             "jsonbase64" => Ok(Self::JsonBase64),
+            "raw" => Ok(Self::Raw),
             _ => Err(darling::Error::custom(format!(
-                r#"Invalid value "{value}", expected "borsh" or "jsonbase64""#
+                r#"Invalid value "{value}", expected "borsh", "jsonbase64", or "raw""#
             ))),
         }
     }
 }
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(upgrade), supports(struct_named))]
+#[darling(attributes(upgrade), forward_attrs(owner), supports(struct_named))]
 pub struct UpgradeMeta {
-    pub hook: HookBody,
+    pub hook: Option<HookBody>,
     pub serializer: Option<Serializer>,
     pub migrate_method_name: Option<String>,
     pub migrate_method_args: Option<Expr>,
     pub migrate_minimum_gas: Option<Expr>,
+    pub gas_leftover: Option<Expr>,
+    #[darling(default)]
+    pub validate_code: bool,
 
+    pub attrs: Vec<syn::Attribute>,
     pub generics: syn::Generics,
     pub ident: syn::Ident,
 
@@ -80,7 +87,10 @@ pub fn expand(meta: UpgradeMeta) -> Result<TokenStream, darling::Error> {
         migrate_method_name,
         migrate_method_args,
         migrate_minimum_gas,
+        gas_leftover,
+        validate_code,
 
+        attrs,
         ident,
         generics,
 
@@ -106,15 +116,26 @@ pub fn expand(meta: UpgradeMeta) -> Result<TokenStream, darling::Error> {
         |e| quote! { #e },
     );
 
+    let gas_leftover = gas_leftover.map_or_else(
+        || quote! { #me::upgrade::DEFAULT_UPGRADE_GAS_LEFTOVER },
+        |e| quote! { #e },
+    );
+
+    let hook = match hook.unwrap_or(HookBody::Custom) {
+        HookBody::Auto if attrs.iter().any(|attr| attr.path().is_ident("owner")) => HookBody::Owner,
+        HookBody::Auto => HookBody::Custom,
+        hook => hook,
+    };
+
     let hook_implementation = match &hook {
         // Should we generate an UpgradeHook implementation with body?
         HookBody::Empty => Some(quote! {}), // empty implementation
-        HookBody::Custom => None,           // user-provided implementation
+        HookBody::Auto | HookBody::Custom => None, // user-provided implementation
         HookBody::Owner => Some(quote! {
-            <Self as #me::owner::Owner>::require_owner();
+            #me::upgrade::serialized::owner_hook::<Self>();
         }),
         HookBody::Role(role) => Some(quote! {
-            #me::rbac::Rbac::require_role(self, &#role);
+            <Self as #me::rbac::Rbac>::require_role(&#role);
         }),
     }
     .map(|body| {
@@ -128,19 +149,73 @@ pub fn expand(meta: UpgradeMeta) -> Result<TokenStream, darling::Error> {
         }
     });
 
-    let (serializer_attribute, code_type, code_conversion) =
-        match serializer.unwrap_or(Serializer::JsonBase64) {
-            Serializer::Borsh => (
-                quote! { #[serializer(borsh)] },
-                quote! { Vec<u8> },
-                quote! {},
-            ),
-            Serializer::JsonBase64 => (
-                quote! {},
-                quote! { #near_sdk::json_types::Base64VecU8 },
-                quote! { let code: Vec<u8> = code.into(); },
-            ),
-        };
+    let validate_code = validate_code.then(|| {
+        quote! {
+            #me::upgrade::serialized::UpgradeCodeHook::on_upgrade_code(self, &code);
+        }
+    });
+
+    let post_upgrade = quote! {
+        #me::upgrade::PostUpgrade {
+            method: #migrate_method_name.to_string(),
+            args: #migrate_method_args,
+            minimum_gas: #migrate_minimum_gas,
+        }
+    };
+
+    let serializer = serializer.unwrap_or(Serializer::JsonBase64);
+
+    if let Serializer::Raw = serializer {
+        if validate_code.is_some() {
+            return Err(darling::Error::custom(
+                r#"`validate_code` is not supported with `serializer = "raw"`"#,
+            ));
+        }
+
+        if !generics.params.is_empty() {
+            return Err(darling::Error::custom(
+                r#"`serializer = "raw"` is not supported for generic contracts"#,
+            ));
+        }
+
+        // Deploys the code straight from the input register. This cannot go
+        // through `#[near]`, which would read the input into memory and set
+        // its own return value.
+        return Ok(quote! {
+            #me::__require_raw_upgrade!();
+
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            pub extern "C" fn upgrade() {
+                #near_sdk::env::setup_panic_hook();
+
+                let contract: #ident = #near_sdk::env::state_read()
+                    .unwrap_or_else(|| #near_sdk::env::panic_str("Contract is not initialized"));
+
+                #me::upgrade::serialized::UpgradeHook::on_upgrade(&contract);
+
+                unsafe {
+                    #me::upgrade::raw::upgrade_with_gas_leftover(#post_upgrade, #gas_leftover);
+                }
+            }
+
+            #hook_implementation
+        });
+    }
+
+    let (serializer_attribute, code_type, code_conversion) = match serializer {
+        Serializer::Borsh => (
+            quote! { #[serializer(borsh)] },
+            quote! { Vec<u8> },
+            quote! {},
+        ),
+        Serializer::JsonBase64 => (
+            quote! {},
+            quote! { #near_sdk::json_types::Base64VecU8 },
+            quote! { let code: Vec<u8> = code.into(); },
+        ),
+        Serializer::Raw => unreachable!("handled above"),
+    };
 
     Ok(quote! {
         #[#near_sdk::near]
@@ -148,14 +223,13 @@ pub fn expand(meta: UpgradeMeta) -> Result<TokenStream, darling::Error> {
             pub fn upgrade(&mut self, #serializer_attribute code: #code_type) {
                 #me::upgrade::serialized::UpgradeHook::on_upgrade(self);
                 #code_conversion
-                #me::upgrade::serialized::upgrade(
+                #validate_code
+                #me::upgrade::serialized::try_upgrade(
                     code,
-                    #me::upgrade::PostUpgrade {
-                        method: #migrate_method_name.to_string(),
-                        args: #migrate_method_args,
-                        minimum_gas: #migrate_minimum_gas,
-                    },
-                );
+                    #post_upgrade,
+                    #gas_leftover,
+                )
+                .unwrap_or_else(|e| #near_sdk::env::panic_str(&e.to_string()));
             }
         }
 
@@ -0,0 +1,178 @@
+//! Approval action type that bundles several promise-producing actions into
+//! a single request, e.g. a transfer and a function call.
+//!
+//! # Chaining
+//!
+//! The promises produced by the inner actions are combined according to
+//! [`Chaining`]:
+//!
+//! - [`Chaining::Sequential`] joins them with [`Promise::then`], so each
+//!   action is only started after the previous one has completed. A failing
+//!   action does _not_ prevent later actions from running, and actions that
+//!   have already completed are not rolled back.
+//! - [`Chaining::Independent`] joins them with [`Promise::and`], so all of
+//!   the actions are started at once, in no guaranteed order.
+//!
+//! In neither case is the batch atomic: each inner promise succeeds or fails
+//! on its own.
+
+use near_sdk::{near, AccountId, Promise};
+
+use super::Action;
+
+/// How the promises of a [`BatchAction`] are combined.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[near(serializers = [borsh, json])]
+pub enum Chaining {
+    /// Each action starts after the previous one completes
+    /// ([`Promise::then`]).
+    #[default]
+    Sequential,
+    /// All actions start at the same time ([`Promise::and`]).
+    Independent,
+}
+
+/// A list of actions executed as one approval request. Typically
+/// `BatchAction<NativeTransactionAction>`, or a batch of a user-defined enum
+/// of action types.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[near(serializers = [borsh, json])]
+pub struct BatchAction<A> {
+    /// Actions to execute, in order.
+    pub actions: Vec<A>,
+    /// How the resulting promises are combined.
+    pub chaining: Chaining,
+}
+
+impl<A> BatchAction<A> {
+    /// Creates a new batch with the given chaining semantics.
+    #[must_use]
+    pub fn new(actions: Vec<A>, chaining: Chaining) -> Self {
+        Self { actions, chaining }
+    }
+}
+
+impl<C, A: Action<C, Output = Promise>> Action<C> for BatchAction<A> {
+    /// The combined promise, or `None` if the batch is empty.
+    type Output = Option<Promise>;
+
+    fn execute(self, contract: &mut C) -> Self::Output {
+        let chaining = self.chaining;
+
+        self.actions
+            .into_iter()
+            .map(|action| action.execute(contract))
+            .reduce(|combined, next| match chaining {
+                Chaining::Sequential => combined.then(next),
+                Chaining::Independent => combined.and(next),
+            })
+    }
+
+    /// An account is authorized for the batch only if it is authorized for
+    /// every action in it.
+    fn is_account_authorized(&self, account_id: &AccountId) -> bool {
+        self.actions
+            .iter()
+            .all(|action| action.is_account_authorized(account_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::{
+        json_types::Base64VecU8,
+        mock::MockAction,
+        near,
+        test_utils::{get_created_receipts, VMContextBuilder},
+        testing_env, AccountId, Gas, NearToken, PanicOnDefault,
+    };
+
+    use super::*;
+    use crate::{
+        approval::{
+            native_transaction_action::{NativeTransactionAction, PromiseAction},
+            simple_multisig::{AccountAuthorizer, ApprovalState, Configuration},
+            ApprovalManager, ApprovalManagerInternal,
+        },
+        slot::Slot,
+    };
+
+    type Batch = BatchAction<NativeTransactionAction>;
+
+    #[derive(PanicOnDefault)]
+    #[near(contract_state)]
+    struct Contract {}
+
+    impl ApprovalManagerInternal<Batch, ApprovalState, Configuration<Self>> for Contract {
+        fn root() -> Slot<()> {
+            Slot::new(b"m")
+        }
+    }
+
+    impl AccountAuthorizer for Contract {
+        type AuthorizationError = ();
+
+        fn is_account_authorized(_account_id: &AccountId) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn execute_transfer_and_function_call() {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id("alice".parse().unwrap())
+            .build());
+
+        <Contract as ApprovalManager<Batch, _, _>>::init(Configuration::new(1, 0));
+        let mut contract = Contract {};
+
+        let batch = BatchAction::new(
+            vec![
+                NativeTransactionAction {
+                    receiver_id: "bob".parse().unwrap(),
+                    actions: vec![PromiseAction::Transfer {
+                        amount: NearToken::from_yoctonear(10),
+                    }],
+                },
+                NativeTransactionAction {
+                    receiver_id: "charlie".parse().unwrap(),
+                    actions: vec![PromiseAction::FunctionCall {
+                        function_name: "ping".to_string(),
+                        arguments: Base64VecU8(b"{}".to_vec()),
+                        amount: NearToken::from_yoctonear(0),
+                        gas: Gas::from_tgas(5),
+                    }],
+                },
+            ],
+            Chaining::Sequential,
+        );
+
+        let request_id = contract
+            .create_request(batch, ApprovalState::new())
+            .unwrap();
+        contract.approve_request(request_id).unwrap();
+        let promise = contract.execute_request(request_id).unwrap();
+
+        assert!(promise.is_some());
+        drop(promise);
+
+        let receipts = get_created_receipts();
+
+        assert!(receipts.iter().any(|action| matches!(
+            action,
+            MockAction::Transfer { deposit, .. } if *deposit == NearToken::from_yoctonear(10),
+        )));
+        assert!(receipts.iter().any(|action| matches!(
+            action,
+            MockAction::FunctionCallWeight { method_name, .. } if method_name == b"ping",
+        )));
+        assert!(Contract::get_request(request_id).is_none());
+    }
+
+    #[test]
+    fn empty_batch() {
+        let batch: Batch = BatchAction::new(vec![], Chaining::Independent);
+
+        assert!(Action::<Contract>::execute(batch, &mut Contract {}).is_none());
+    }
+}
@@ -1,4 +1,13 @@
 //! Queue and approve actions
+//!
+//! # Vetoes
+//!
+//! Besides approving, an authorized account may veto (reject) a pending
+//! request with [`ApprovalManager::reject_request`]. A single veto is
+//! sufficient: once vetoed, a request can never be executed, regardless of
+//! how many approvals it has or later receives. Vetoes are tracked
+//! separately from the approval state, and a vetoed request may still be
+//! removed under the usual removal rules.
 
 use near_sdk::{
     borsh::{BorshDeserialize, BorshSerialize},
@@ -8,13 +17,15 @@ use near_sdk::{
 };
 use thiserror::Error;
 
-use crate::{slot::Slot, DefaultStorageKey};
+use crate::{slot::Slot, standard::nep297::Event, DefaultStorageKey};
+use near_sdk_contract_tools_macros::event;
 
 /// Error message emitted when the component is used before it is initialized
 pub const NOT_INITIALIZED: &str = "init must be called before use";
 /// Error message emitted when the init function is called multiple times
 pub const ALREADY_INITIALIZED: &str = "init can only be called once";
 
+pub mod batch_action;
 pub mod native_transaction_action;
 pub mod simple_multisig;
 
@@ -24,6 +35,15 @@ pub trait Action<Cont: ?Sized> {
     type Output;
     /// Perform the action. One time only.
     fn execute(self, contract: &mut Cont) -> Self::Output;
+
+    /// Action-specific authorization, checked in addition to
+    /// [`ApprovalConfiguration::is_account_authorized`] when approving a
+    /// request. Allows e.g. different actions to require different roles.
+    /// By default, all accounts are authorized.
+    fn is_account_authorized(&self, account_id: &AccountId) -> bool {
+        let _ = account_id;
+        true
+    }
 }
 
 /// Defines the operating parameters for an `ApprovalManager` and performs
@@ -76,6 +96,27 @@ pub trait ApprovalConfiguration<A, S> {
         account_id: AccountId,
         action_request: &mut ActionRequest<A, S>,
     ) -> Result<(), Self::ApprovalError>;
+
+    /// Called once when a request is created, before it is stored. Allows
+    /// the configuration to snapshot parameters into the approval state.
+    fn initialize_request(&self, action_request: &mut ActionRequest<A, S>) {
+        let _ = action_request;
+    }
+
+    /// Is the account allowed to veto this request? Defaults to
+    /// [`ApprovalConfiguration::is_account_authorized`]; override to restrict
+    /// vetoes to e.g. a distinct role.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account is not allowed to veto the request.
+    fn is_account_authorized_to_reject(
+        &self,
+        account_id: &AccountId,
+        action_request: &ActionRequest<A, S>,
+    ) -> Result<(), Self::AuthorizationError> {
+        self.is_account_authorized(account_id, action_request)
+    }
 }
 
 /// An action request is composed of an action that will be executed when the
@@ -95,6 +136,22 @@ enum ApprovalStorageKey {
     NextRequestId,
     Config,
     Request(u32),
+    Veto(u32),
+}
+
+/// Emitted when a request is vetoed.
+#[event(
+    standard = "x-approval",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+#[derive(Debug, Clone)]
+pub struct RequestRejected {
+    /// The vetoed request.
+    pub request_id: u32,
+    /// The account that vetoed the request.
+    pub rejected_by: AccountId,
 }
 
 /// The account is ineligile to perform an action for some reason
@@ -102,17 +159,46 @@ enum ApprovalStorageKey {
 #[error("Unauthorized account: '{0}' for {1}")]
 pub struct UnauthorizedAccountError<AuthErr>(AccountId, AuthErr);
 
+/// The account is not authorized to act on this particular action
+#[derive(Error, Clone, Debug)]
+#[error("Account '{0}' is not authorized for this action")]
+pub struct UnauthorizedActionError(pub AccountId);
+
 /// Top-level errors that may occur when attempting to approve a request
 #[derive(Error, Clone, Debug)]
 pub enum ApprovalError<AuthErr, AppErr> {
     /// The account is not allowed to act on requests
     #[error(transparent)]
     UnauthorizedAccount(#[from] UnauthorizedAccountError<AuthErr>),
+    /// The account is not allowed to approve this action
+    #[error(transparent)]
+    UnauthorizedAction(#[from] UnauthorizedActionError),
     /// The approval function encountered another error
     #[error("Approval error: {0}")]
     ApprovalError(AppErr),
 }
 
+/// The request has been vetoed and can never be executed
+#[derive(Error, Clone, Debug)]
+#[error("Request {request_id} was rejected by '{rejected_by}'")]
+pub struct RequestVetoedError {
+    /// The vetoed request
+    pub request_id: u32,
+    /// The account that vetoed the request
+    pub rejected_by: AccountId,
+}
+
+/// Errors that may occur when evaluating a request for execution
+#[derive(Error, Clone, Debug)]
+pub enum EligibilityError<ExecErr> {
+    /// Vetoed requests cannot be executed
+    #[error(transparent)]
+    Vetoed(#[from] RequestVetoedError),
+    /// The approval state does not permit execution
+    #[error("Request not approved: {0}")]
+    NotApproved(ExecErr),
+}
+
 /// Errors that may occur when trying to execute a request
 #[derive(Error, Clone, Debug)]
 pub enum ExecutionError<AuthErr, ExecErr> {
@@ -122,6 +208,29 @@ pub enum ExecutionError<AuthErr, ExecErr> {
     /// Unapproved requests cannot be executed
     #[error("Request not approved: {0}")]
     ExecutionEligibility(ExecErr),
+    /// Vetoed requests cannot be executed
+    #[error(transparent)]
+    Vetoed(#[from] RequestVetoedError),
+}
+
+impl<AuthErr, ExecErr> From<EligibilityError<ExecErr>> for ExecutionError<AuthErr, ExecErr> {
+    fn from(value: EligibilityError<ExecErr>) -> Self {
+        match value {
+            EligibilityError::Vetoed(e) => Self::Vetoed(e),
+            EligibilityError::NotApproved(e) => Self::ExecutionEligibility(e),
+        }
+    }
+}
+
+/// Errors that may occur when trying to veto a request
+#[derive(Error, Clone, Debug)]
+pub enum RejectionError<AuthErr> {
+    /// The account is not allowed to veto requests
+    #[error(transparent)]
+    UnauthorizedAccount(#[from] UnauthorizedAccountError<AuthErr>),
+    /// The request has already been vetoed
+    #[error(transparent)]
+    AlreadyVetoed(#[from] RequestVetoedError),
 }
 
 /// Errors that may occur when trying to create a request
@@ -174,6 +283,12 @@ where
     fn slot_request(request_id: u32) -> Slot<ActionRequest<A, S>> {
         Self::root().field(ApprovalStorageKey::Request(request_id))
     }
+
+    /// The account that vetoed a request, if any.
+    #[must_use]
+    fn slot_veto(request_id: u32) -> Slot<AccountId> {
+        Self::root().field(ApprovalStorageKey::Veto(request_id))
+    }
 }
 
 /// Collection of action requests that manages their approval state and
@@ -191,10 +306,21 @@ where
     /// Get a request by ID
     fn get_request(request_id: u32) -> Option<ActionRequest<A, S>>;
 
+    /// Get the approval state of a request by ID, e.g. to display which
+    /// accounts have approved it.
+    fn get_approval_state(request_id: u32) -> Option<S>;
+
     /// Must be called before using the Approval construct. Can only be called
     /// once.
     fn init(config: C);
 
+    /// Replaces the configuration of an initialized component. Requests
+    /// that were created before the change may retain parameters that were
+    /// snapshotted at creation (see
+    /// [`ApprovalConfiguration::initialize_request`]). Performs no access
+    /// control.
+    fn set_config(&mut self, config: C);
+
     /// Creates a new action request initialized with the given approval state.
     ///
     /// # Errors
@@ -223,8 +349,14 @@ where
     ///
     /// # Errors
     ///
+    /// - If the request has been vetoed.
     /// - If the request is ineligible for execution.
-    fn is_approved_for_execution(request_id: u32) -> Result<(), C::ExecutionEligibilityError>;
+    fn is_approved_for_execution(
+        request_id: u32,
+    ) -> Result<(), EligibilityError<C::ExecutionEligibilityError>>;
+
+    /// Returns the account that vetoed the request, if any.
+    fn get_veto(request_id: u32) -> Option<AccountId>;
 
     /// Tries to approve the action request designated by the given request ID
     /// with the given arguments. Panics if the request ID does not exist.
@@ -238,6 +370,19 @@ where
         request_id: u32,
     ) -> Result<(), ApprovalError<C::AuthorizationError, C::ApprovalError>>;
 
+    /// Vetoes the action request designated by the given request ID, so that
+    /// it can never be executed. A single veto is sufficient. Emits a
+    /// [`RequestRejected`] event. Panics if the request ID does not exist.
+    ///
+    /// # Errors
+    ///
+    /// - If the acting account is not authorized to veto the request.
+    /// - If the request has already been vetoed.
+    fn reject_request(
+        &mut self,
+        request_id: u32,
+    ) -> Result<(), RejectionError<C::AuthorizationError>>;
+
     /// Tries to remove the action request indicated by `request_id`.
     ///
     /// # Errors
@@ -266,6 +411,10 @@ where
         Self::slot_request(request_id).read()
     }
 
+    fn get_approval_state(request_id: u32) -> Option<S> {
+        Self::get_request(request_id).map(|request| request.approval_state)
+    }
+
     fn init(config: C) {
         require!(
             Self::slot_config().swap(&config).is_none(),
@@ -273,6 +422,10 @@ where
         );
     }
 
+    fn set_config(&mut self, config: C) {
+        require!(Self::slot_config().swap(&config).is_some(), NOT_INITIALIZED);
+    }
+
     fn create_request(
         &mut self,
         action: A,
@@ -280,7 +433,7 @@ where
     ) -> Result<u32, CreationError<C::AuthorizationError>> {
         let request_id = Self::slot_next_request_id().read().unwrap_or(0);
 
-        let request = ActionRequest {
+        let mut request = ActionRequest {
             action,
             approval_state,
         };
@@ -292,6 +445,8 @@ where
             .is_account_authorized(&predecessor, &request)
             .map_err(|e| UnauthorizedAccountError(predecessor, e))?;
 
+        config.initialize_request(&mut request);
+
         Self::slot_next_request_id().write(&(request_id + 1));
         Self::slot_request(request_id).write(&request);
 
@@ -303,8 +458,7 @@ where
         request_id: u32,
     ) -> Result<A::Output, ExecutionError<C::AuthorizationError, C::ExecutionEligibilityError>>
     {
-        Self::is_approved_for_execution(request_id)
-            .map_err(ExecutionError::ExecutionEligibility)?;
+        Self::is_approved_for_execution(request_id)?;
 
         let predecessor = env::predecessor_account_id();
         let config = Self::get_config();
@@ -322,11 +476,27 @@ where
         Ok(result)
     }
 
-    fn is_approved_for_execution(request_id: u32) -> Result<(), C::ExecutionEligibilityError> {
+    fn is_approved_for_execution(
+        request_id: u32,
+    ) -> Result<(), EligibilityError<C::ExecutionEligibilityError>> {
         let request = Self::slot_request(request_id).read().unwrap();
 
+        if let Some(rejected_by) = Self::get_veto(request_id) {
+            return Err(RequestVetoedError {
+                request_id,
+                rejected_by,
+            }
+            .into());
+        }
+
         let config = Self::get_config();
-        config.is_approved_for_execution(&request)
+        config
+            .is_approved_for_execution(&request)
+            .map_err(EligibilityError::NotApproved)
+    }
+
+    fn get_veto(request_id: u32) -> Option<AccountId> {
+        Self::slot_veto(request_id).read()
     }
 
     fn approve_request(
@@ -343,6 +513,10 @@ where
             .is_account_authorized(&predecessor, &request)
             .map_err(|e| UnauthorizedAccountError(predecessor.clone(), e))?;
 
+        if !request.action.is_account_authorized(&predecessor) {
+            return Err(UnauthorizedActionError(predecessor).into());
+        }
+
         config
             .try_approve_with_authorized_account(predecessor, &mut request)
             .map_err(ApprovalError::ApprovalError)?;
@@ -352,6 +526,40 @@ where
         Ok(())
     }
 
+    fn reject_request(
+        &mut self,
+        request_id: u32,
+    ) -> Result<(), RejectionError<C::AuthorizationError>> {
+        let request = Self::slot_request(request_id).read().unwrap();
+
+        let predecessor = env::predecessor_account_id();
+        let config = Self::get_config();
+
+        config
+            .is_account_authorized_to_reject(&predecessor, &request)
+            .map_err(|e| UnauthorizedAccountError(predecessor.clone(), e))?;
+
+        let mut veto_slot = Self::slot_veto(request_id);
+
+        if let Some(rejected_by) = veto_slot.read() {
+            return Err(RequestVetoedError {
+                request_id,
+                rejected_by,
+            }
+            .into());
+        }
+
+        veto_slot.write(&predecessor);
+
+        RequestRejected {
+            request_id,
+            rejected_by: predecessor,
+        }
+        .emit();
+
+        Ok(())
+    }
+
     fn remove_request(
         &mut self,
         request_id: u32,
@@ -371,6 +579,7 @@ where
             .map_err(|e| UnauthorizedAccountError(predecessor, e))?;
 
         request_slot.remove();
+        Self::slot_veto(request_id).remove();
 
         Ok(())
     }
@@ -387,12 +596,14 @@ mod tests {
 
     use super::{
         Action, ActionRequest, ApprovalConfiguration, ApprovalManager, ApprovalManagerInternal,
+        EligibilityError, ExecutionError, RejectionError,
     };
 
     #[derive(BorshStorageKey)]
     #[near]
     enum Role {
         Multisig,
+        Loud,
     }
 
     #[derive(Debug, PartialEq, Eq, Clone)]
@@ -400,6 +611,7 @@ mod tests {
     enum MyAction {
         SayHello,
         SayGoodbye,
+        Shout,
     }
 
     impl Action<Contract> for MyAction {
@@ -415,6 +627,17 @@ mod tests {
                     println!("Goodbye!");
                     "goodbye"
                 }
+                Self::Shout => {
+                    println!("HEY!");
+                    "hey"
+                }
+            }
+        }
+
+        fn is_account_authorized(&self, account_id: &AccountId) -> bool {
+            match self {
+                Self::Shout => Contract::has_role(account_id, &Role::Loud),
+                _ => true,
             }
         }
     }
@@ -651,4 +874,116 @@ mod tests {
 
         assert!(Contract::is_approved_for_execution(request_id).is_ok());
     }
+
+    #[test]
+    fn action_specific_authorization() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+
+        let mut contract = Contract::new(1);
+
+        contract.add_role(&alice, &Role::Multisig);
+        contract.add_role(&alice, &Role::Loud);
+        contract.add_role(&bob, &Role::Multisig);
+
+        predecessor(&alice);
+        let shout_id = contract
+            .create_request(MyAction::Shout, MultisigApprovalState::default())
+            .unwrap();
+        let hello_id = contract
+            .create_request(MyAction::SayHello, MultisigApprovalState::default())
+            .unwrap();
+
+        predecessor(&bob);
+        assert!(matches!(
+            contract.approve_request(shout_id),
+            Err(super::ApprovalError::UnauthorizedAction(_)),
+        ));
+        assert!(Contract::is_approved_for_execution(shout_id).is_err());
+
+        contract.approve_request(hello_id).unwrap();
+        assert!(Contract::is_approved_for_execution(hello_id).is_ok());
+
+        predecessor(&alice);
+        contract.approve_request(shout_id).unwrap();
+        assert!(Contract::is_approved_for_execution(shout_id).is_ok());
+        assert_eq!(contract.execute_request(shout_id).unwrap(), "hey");
+    }
+
+    #[test]
+    fn vetoed_request_cannot_execute() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+        let charlie: AccountId = "charlie".parse().unwrap();
+
+        let mut contract = Contract::new(2);
+
+        contract.add_role(&alice, &Role::Multisig);
+        contract.add_role(&bob, &Role::Multisig);
+        contract.add_role(&charlie, &Role::Multisig);
+
+        predecessor(&alice);
+        let request_id = contract
+            .create_request(MyAction::SayHello, MultisigApprovalState::default())
+            .unwrap();
+        contract.approve_request(request_id).unwrap();
+
+        predecessor(&charlie);
+        contract.reject_request(request_id).unwrap();
+
+        let event = crate::testing::expect_event(
+            near_sdk::test_utils::get_logs(),
+            "x-approval",
+            "request_rejected",
+        );
+        assert_eq!(
+            event.data,
+            near_sdk::serde_json::json!({ "request_id": 0, "rejected_by": "charlie" }),
+        );
+        assert_eq!(Contract::get_veto(request_id), Some(charlie.clone()));
+
+        predecessor(&bob);
+        contract.approve_request(request_id).unwrap();
+
+        assert!(matches!(
+            Contract::is_approved_for_execution(request_id),
+            Err(EligibilityError::Vetoed(_)),
+        ));
+        assert!(matches!(
+            contract.execute_request(request_id),
+            Err(ExecutionError::Vetoed(e)) if e.rejected_by == charlie,
+        ));
+
+        predecessor(&alice);
+        assert!(matches!(
+            contract.reject_request(request_id),
+            Err(RejectionError::AlreadyVetoed(_)),
+        ));
+    }
+
+    #[test]
+    fn reject_unauthorized() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+
+        let mut contract = Contract::new(1);
+
+        contract.add_role(&alice, &Role::Multisig);
+
+        predecessor(&alice);
+        let request_id = contract
+            .create_request(MyAction::SayHello, MultisigApprovalState::default())
+            .unwrap();
+
+        predecessor(&bob);
+        assert!(matches!(
+            contract.reject_request(request_id),
+            Err(RejectionError::UnauthorizedAccount(_)),
+        ));
+        assert_eq!(Contract::get_veto(request_id), None);
+
+        predecessor(&alice);
+        contract.approve_request(request_id).unwrap();
+        assert_eq!(contract.execute_request(request_id).unwrap(), "hello");
+    }
 }
@@ -3,9 +3,12 @@
 //! [`NativeTransactionAction`](super::native_transaction_action::NativeTransactionAction)
 //! for multisig over native transactions.
 
-use std::marker::PhantomData;
+use std::{io, marker::PhantomData};
 
-use near_sdk::{env, near, AccountId};
+use near_sdk::{
+    borsh::{BorshDeserialize, BorshSerialize},
+    env, near, AccountId, AccountIdRef,
+};
 use thiserror::Error;
 
 use super::{ActionRequest, ApprovalConfiguration};
@@ -25,8 +28,17 @@ pub trait AccountAuthorizer {
 }
 
 /// M (threshold) of N approval scheme
+///
+/// # Storage layout
+///
+/// The Borsh encoding is versioned: it begins with a zero byte (which is
+/// never a valid [`Configuration::threshold`]) followed by a version byte.
+/// Configurations stored before strict mode was introduced lack this header;
+/// they are still readable, decode with [`Configuration::strict`] set to
+/// `false`, and are rewritten in the current layout the next time they are
+/// written.
 #[derive(Clone, Debug)]
-#[near(serializers = [borsh, json])]
+#[near(serializers = [json])]
 pub struct Configuration<Au: AccountAuthorizer> {
     /// How many approvals are required?
     pub threshold: u8,
@@ -34,11 +46,56 @@ pub struct Configuration<Au: AccountAuthorizer> {
     /// approval-eligible member after this period has elapsed.
     /// 0 = perpetual validity, no deletion
     pub validity_period_nanoseconds: u64,
-    #[borsh(skip)]
+    /// Strict mode: when a request is evaluated for execution, only
+    /// approvals from accounts that are still authorized (per
+    /// [`AccountAuthorizer::is_account_authorized`]) count, and they must
+    /// meet the current [`Configuration::threshold`]. In lenient mode (the
+    /// default), an approval counts even if the approving account has since
+    /// lost its authorization.
+    #[serde(default)]
+    pub strict: bool,
     #[serde(skip)]
     _authorizer: PhantomData<Au>,
 }
 
+const CONFIGURATION_LAYOUT_MARKER: u8 = 0;
+const CONFIGURATION_LAYOUT_VERSION_1: u8 = 1;
+
+impl<Au: AccountAuthorizer> BorshSerialize for Configuration<Au> {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        CONFIGURATION_LAYOUT_MARKER.serialize(writer)?;
+        CONFIGURATION_LAYOUT_VERSION_1.serialize(writer)?;
+        self.threshold.serialize(writer)?;
+        self.validity_period_nanoseconds.serialize(writer)?;
+        self.strict.serialize(writer)
+    }
+}
+
+impl<Au: AccountAuthorizer> BorshDeserialize for Configuration<Au> {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let header = u8::deserialize_reader(reader)?;
+
+        if header != CONFIGURATION_LAYOUT_MARKER {
+            // Legacy layout: the header is the threshold, and there is no
+            // strict flag.
+            return Ok(Self::new(header, u64::deserialize_reader(reader)?));
+        }
+
+        match u8::deserialize_reader(reader)? {
+            CONFIGURATION_LAYOUT_VERSION_1 => Ok(Self {
+                threshold: u8::deserialize_reader(reader)?,
+                validity_period_nanoseconds: u64::deserialize_reader(reader)?,
+                strict: bool::deserialize_reader(reader)?,
+                _authorizer: PhantomData,
+            }),
+            version => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unknown Configuration layout version: {version}"),
+            )),
+        }
+    }
+}
+
 impl<Au: AccountAuthorizer> Configuration<Au> {
     /// Create an approval scheme with the given threshold
     #[must_use]
@@ -46,10 +103,37 @@ impl<Au: AccountAuthorizer> Configuration<Au> {
         Self {
             threshold,
             validity_period_nanoseconds,
+            strict: false,
             _authorizer: PhantomData,
         }
     }
 
+    /// Enables strict mode. See [`Configuration::strict`].
+    #[must_use]
+    pub fn with_strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
+        }
+    }
+
+    /// Changes the number of approvals required. Only affects requests
+    /// created after the change; existing requests keep the threshold they
+    /// were created with.
+    ///
+    /// # Errors
+    ///
+    /// - If the threshold is zero.
+    pub fn set_threshold(&mut self, threshold: u8) -> Result<(), InvalidThresholdError> {
+        if threshold == 0 {
+            return Err(InvalidThresholdError);
+        }
+
+        self.threshold = threshold;
+
+        Ok(())
+    }
+
     /// Is the given approval state still considered valid?
     ///
     /// # Panics
@@ -57,25 +141,75 @@ impl<Au: AccountAuthorizer> Configuration<Au> {
     /// - If the request timestamp is in the future.
     #[must_use]
     pub fn is_within_validity_period(&self, approval_state: &ApprovalState) -> bool {
-        if self.validity_period_nanoseconds == 0 {
-            true
-        } else {
-            env::block_timestamp()
-                .checked_sub(approval_state.created_at_nanoseconds)
-                .unwrap() // inconsistent state if a request timestamp is in the future
-                < self.validity_period_nanoseconds
-        }
+        !approval_state.is_expired(self)
     }
 }
 
 /// Approval state for simple multisig
+///
+/// # Storage layout
+///
+/// The Borsh encoding is versioned: it begins with [`u32::MAX`] (which can
+/// never be the length of `approved_by` in practice) followed by a version
+/// byte. Requests stored before the threshold snapshot was introduced lack
+/// this header; they are still readable, decode with
+/// [`ApprovalState::threshold`] set to `None`, and are rewritten in the
+/// current layout the next time they are written (e.g. on approval).
 #[derive(Clone, Debug)]
-#[near(serializers = [borsh, json])]
+#[near(serializers = [json])]
 pub struct ApprovalState {
     /// List of accounts that have approved an action thus far
     pub approved_by: Vec<AccountId>,
     /// Network timestamp when the request was created
     pub created_at_nanoseconds: u64,
+    /// Threshold of the configuration when the request was created. If
+    /// `None`, the current configuration threshold is used.
+    pub threshold: Option<u8>,
+}
+
+const LAYOUT_MARKER: u32 = u32::MAX;
+const LAYOUT_VERSION_1: u8 = 1;
+
+impl BorshSerialize for ApprovalState {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        LAYOUT_MARKER.serialize(writer)?;
+        LAYOUT_VERSION_1.serialize(writer)?;
+        self.approved_by.serialize(writer)?;
+        self.created_at_nanoseconds.serialize(writer)?;
+        self.threshold.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for ApprovalState {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let header = u32::deserialize_reader(reader)?;
+
+        if header != LAYOUT_MARKER {
+            // Legacy layout: the header is the length of `approved_by`, and
+            // there is no threshold snapshot.
+            let approved_by = (0..header)
+                .map(|_| AccountId::deserialize_reader(reader))
+                .collect::<io::Result<_>>()?;
+
+            return Ok(Self {
+                approved_by,
+                created_at_nanoseconds: u64::deserialize_reader(reader)?,
+                threshold: None,
+            });
+        }
+
+        match u8::deserialize_reader(reader)? {
+            LAYOUT_VERSION_1 => Ok(Self {
+                approved_by: Vec::deserialize_reader(reader)?,
+                created_at_nanoseconds: u64::deserialize_reader(reader)?,
+                threshold: Option::deserialize_reader(reader)?,
+            }),
+            version => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unknown ApprovalState layout version: {version}"),
+            )),
+        }
+    }
 }
 
 impl Default for ApprovalState {
@@ -91,10 +225,73 @@ impl ApprovalState {
         Self {
             approved_by: Vec::new(),
             created_at_nanoseconds: env::block_timestamp(),
+            threshold: None,
         }
     }
+
+    /// Accounts that currently approve the request, in the order they
+    /// approved. Accounts whose approval has been withdrawn are not included.
+    #[must_use]
+    pub fn approved_by(&self) -> &[AccountId] {
+        &self.approved_by
+    }
+
+    /// Whether `account_id` currently approves the request.
+    #[must_use]
+    pub fn is_approved_by(&self, account_id: &AccountIdRef) -> bool {
+        self.approved_by.iter().any(|a| **a == *account_id)
+    }
+
+    /// Network timestamp (in nanoseconds) at which the request expires under
+    /// `config`, computed from the creation timestamp stamped by
+    /// [`ApprovalState::new`]. `None` if `config` has perpetual validity.
+    #[must_use]
+    pub fn expires_at<Au: AccountAuthorizer>(&self, config: &Configuration<Au>) -> Option<u64> {
+        if config.validity_period_nanoseconds == 0 {
+            None
+        } else {
+            Some(
+                self.created_at_nanoseconds
+                    .saturating_add(config.validity_period_nanoseconds),
+            )
+        }
+    }
+
+    /// Whether the validity period of `config` has elapsed since the request
+    /// was created. Expired requests can no longer be approved or executed.
+    ///
+    /// # Panics
+    ///
+    /// - If the request timestamp is in the future.
+    #[must_use]
+    pub fn is_expired<Au: AccountAuthorizer>(&self, config: &Configuration<Au>) -> bool {
+        if config.validity_period_nanoseconds == 0 {
+            false
+        } else {
+            env::block_timestamp()
+                .checked_sub(self.created_at_nanoseconds)
+                .unwrap() // inconsistent state if a request timestamp is in the future
+                >= config.validity_period_nanoseconds
+        }
+    }
+
+    /// The number of additional approvals required before the request can be
+    /// executed. Uses the threshold snapshotted when the request was created,
+    /// falling back to the threshold of `config`.
+    #[must_use]
+    pub fn approvals_remaining<Au: AccountAuthorizer>(&self, config: &Configuration<Au>) -> u8 {
+        let current = u8::try_from(self.approved_by.len()).unwrap_or(u8::MAX);
+        self.threshold
+            .unwrap_or(config.threshold)
+            .saturating_sub(current)
+    }
 }
 
+/// The approval threshold must be at least 1.
+#[derive(Error, Clone, Debug)]
+#[error("Threshold must be at least 1")]
+pub struct InvalidThresholdError;
+
 /// If a request has expired, some actions may not be performed.
 #[derive(Error, Clone, Debug)]
 #[error("Validity period exceeded")]
@@ -152,13 +349,30 @@ where
             return Err(RequestExpiredError.into());
         }
 
-        let current = action_request.approval_state.approved_by.len();
-        let required = self.threshold as usize;
+        let approved_by = &action_request.approval_state.approved_by;
+
+        let current = approved_by.len();
+        let required = action_request
+            .approval_state
+            .threshold
+            .unwrap_or(self.threshold) as usize;
 
         if current < required {
             return Err(ExecutionEligibilityError::InsufficientApprovals { current, required });
         }
 
+        if self.strict {
+            let current = approved_by
+                .iter()
+                .filter(|account_id| Au::is_account_authorized(account_id).is_ok())
+                .count();
+            let required = self.threshold as usize;
+
+            if current < required {
+                return Err(ExecutionEligibilityError::InsufficientApprovals { current, required });
+            }
+        }
+
         Ok(())
     }
 
@@ -202,6 +416,10 @@ where
 
         Ok(())
     }
+
+    fn initialize_request(&self, action_request: &mut ActionRequest<Ac, ApprovalState>) {
+        action_request.approval_state.threshold = Some(self.threshold);
+    }
 }
 
 /// Types used by near-sdk-contract-tools-macros
@@ -224,7 +442,9 @@ mod tests {
 
     use crate::{
         approval::{
-            simple_multisig::{AccountAuthorizer, ApprovalState, Configuration},
+            simple_multisig::{
+                AccountAuthorizer, ApprovalState, Configuration, ExecutionEligibilityError,
+            },
             ApprovalManager, ApprovalManagerInternal,
         },
         rbac::Rbac,
@@ -315,6 +535,18 @@ mod tests {
         pub fn remove(&mut self, request_id: u32) {
             self.remove_request(request_id).unwrap();
         }
+
+        pub fn set_threshold(&mut self, threshold: u8) {
+            let mut config = Self::get_config();
+            config.set_threshold(threshold).unwrap();
+            self.set_config(config);
+        }
+
+        pub fn set_strict_authorization(&mut self, strict: bool) {
+            let mut config = Self::get_config();
+            config.strict = strict;
+            self.set_config(config);
+        }
     }
 
     fn predecessor(account_id: &AccountId) {
@@ -361,6 +593,83 @@ mod tests {
         assert_eq!(contract.execute(request_id), "hello");
     }
 
+    #[test]
+    fn approval_state_introspection() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+
+        let mut contract = Contract::new();
+
+        predecessor(&alice);
+        contract.obtain_multisig_permission();
+        predecessor(&bob);
+        contract.obtain_multisig_permission();
+
+        let request_id = contract.create(true);
+        let config = Contract::get_config();
+
+        let state = Contract::get_approval_state(request_id).unwrap();
+        assert!(state.approved_by().is_empty());
+        assert_eq!(state.approvals_remaining(&config), 2);
+
+        predecessor(&alice);
+        contract.approve(request_id);
+
+        let state = Contract::get_approval_state(request_id).unwrap();
+        assert_eq!(state.approved_by(), &[alice.clone()]);
+        assert!(state.is_approved_by(&alice));
+        assert!(!state.is_approved_by(&bob));
+        assert_eq!(state.approvals_remaining(&config), 1);
+
+        predecessor(&bob);
+        contract.approve(request_id);
+
+        let state = Contract::get_approval_state(request_id).unwrap();
+        assert_eq!(state.approved_by(), &[alice.clone(), bob.clone()]);
+        assert!(state.is_approved_by(&alice));
+        assert!(state.is_approved_by(&bob));
+        assert_eq!(state.approvals_remaining(&config), 0);
+
+        contract.execute(request_id);
+
+        assert!(Contract::get_approval_state(request_id).is_none());
+    }
+
+    #[test]
+    fn expiry() {
+        let alice: AccountId = "alice".parse().unwrap();
+
+        let mut contract = Contract::new();
+
+        predecessor(&alice);
+        contract.obtain_multisig_permission();
+
+        let request_id = contract.create(true);
+        let config = Contract::get_config();
+        let state = Contract::get_approval_state(request_id).unwrap();
+
+        assert_eq!(state.created_at_nanoseconds, env::block_timestamp());
+        assert_eq!(
+            state.expires_at(&config),
+            Some(state.created_at_nanoseconds + 10000),
+        );
+        assert!(!state.is_expired(&config));
+
+        let mut context = VMContextBuilder::new();
+        context
+            .predecessor_account_id(alice.clone())
+            .block_timestamp(state.created_at_nanoseconds + 10000);
+        testing_env!(context.build());
+
+        assert!(state.is_expired(&config));
+        assert!(matches!(
+            contract.approve_request(request_id),
+            Err(crate::approval::ApprovalError::ApprovalError(
+                super::ApprovalError::RequestExpired(_)
+            )),
+        ));
+    }
+
     #[test]
     fn successful_removal() {
         let alice: AccountId = "alice".parse().unwrap();
@@ -444,4 +753,222 @@ mod tests {
 
         contract.remove(request_id);
     }
+
+    #[test]
+    fn threshold_change_does_not_affect_existing_requests() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+        let charlie: AccountId = "charlie".parse().unwrap();
+
+        let mut contract = Contract::new();
+
+        predecessor(&alice);
+        contract.obtain_multisig_permission();
+        predecessor(&bob);
+        contract.obtain_multisig_permission();
+        predecessor(&charlie);
+        contract.obtain_multisig_permission();
+
+        let old_request_id = contract.create(true);
+
+        contract.set_threshold(3);
+        assert_eq!(Contract::get_config().threshold, 3);
+
+        let new_request_id = contract.create(false);
+
+        assert_eq!(
+            Contract::get_request(old_request_id)
+                .unwrap()
+                .approval_state
+                .threshold,
+            Some(2),
+        );
+        assert_eq!(
+            Contract::get_request(new_request_id)
+                .unwrap()
+                .approval_state
+                .threshold,
+            Some(3),
+        );
+
+        predecessor(&alice);
+        contract.approve(old_request_id);
+        contract.approve(new_request_id);
+        predecessor(&bob);
+        contract.approve(old_request_id);
+        contract.approve(new_request_id);
+
+        assert!(Contract::is_approved_for_execution(old_request_id).is_ok());
+        assert!(Contract::is_approved_for_execution(new_request_id).is_err());
+
+        predecessor(&charlie);
+        contract.approve(new_request_id);
+
+        assert!(Contract::is_approved_for_execution(new_request_id).is_ok());
+        assert_eq!(contract.execute(old_request_id), "hello");
+        assert_eq!(contract.execute(new_request_id), "goodbye");
+    }
+
+    #[test]
+    fn strict_authorization() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+
+        let mut contract = Contract::new();
+
+        predecessor(&alice);
+        contract.obtain_multisig_permission();
+        predecessor(&bob);
+        contract.obtain_multisig_permission();
+
+        let strict_request_id = contract.create(true);
+        let lenient_request_id = contract.create(false);
+
+        predecessor(&alice);
+        contract.approve(strict_request_id);
+        contract.approve(lenient_request_id);
+        predecessor(&bob);
+        contract.approve(strict_request_id);
+        contract.approve(lenient_request_id);
+
+        contract.remove_role(&bob, &Role::Multisig);
+
+        contract.set_strict_authorization(true);
+        predecessor(&alice);
+        assert!(matches!(
+            Contract::is_approved_for_execution(strict_request_id),
+            Err(crate::approval::EligibilityError::NotApproved(
+                ExecutionEligibilityError::InsufficientApprovals {
+                    current: 1,
+                    required: 2,
+                },
+            )),
+        ));
+        assert!(contract.execute_request(strict_request_id).is_err());
+
+        contract.set_strict_authorization(false);
+        assert_eq!(contract.execute(lenient_request_id), "goodbye");
+    }
+
+    #[test]
+    fn strict_authorization_uses_current_threshold() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+
+        let mut contract = Contract::new();
+
+        predecessor(&alice);
+        contract.obtain_multisig_permission();
+        predecessor(&bob);
+        contract.obtain_multisig_permission();
+
+        let request_id = contract.create(true);
+
+        predecessor(&alice);
+        contract.approve(request_id);
+        predecessor(&bob);
+        contract.approve(request_id);
+
+        contract.set_threshold(3);
+        assert!(Contract::is_approved_for_execution(request_id).is_ok());
+
+        contract.set_strict_authorization(true);
+        assert!(Contract::get_config().strict);
+        assert!(matches!(
+            Contract::is_approved_for_execution(request_id),
+            Err(crate::approval::EligibilityError::NotApproved(
+                ExecutionEligibilityError::InsufficientApprovals {
+                    current: 2,
+                    required: 3,
+                },
+            )),
+        ));
+
+        contract.set_threshold(2);
+        assert_eq!(contract.execute(request_id), "hello");
+    }
+
+    #[test]
+    fn legacy_approval_state_layout() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob_acct".parse().unwrap();
+
+        let mut contract = Contract::new();
+
+        predecessor(&alice);
+        contract.obtain_multisig_permission();
+        predecessor(&bob);
+        contract.obtain_multisig_permission();
+
+        // A request as stored before the threshold snapshot was introduced.
+        let legacy_request = near_sdk::borsh::to_vec(&(
+            Action::SayHello,
+            vec![alice.clone()],
+            env::block_timestamp(),
+        ))
+        .unwrap();
+        Contract::slot_request(0).write_raw(&legacy_request);
+        Contract::slot_next_request_id().write(&1);
+
+        let state = Contract::get_approval_state(0).unwrap();
+        assert_eq!(state.approved_by(), &[alice.clone()]);
+        assert_eq!(state.created_at_nanoseconds, env::block_timestamp());
+        assert_eq!(state.threshold, None);
+
+        contract.set_threshold(3);
+        assert_eq!(state.approvals_remaining(&Contract::get_config()), 2);
+
+        contract.set_threshold(2);
+        predecessor(&bob);
+        contract.approve(0);
+
+        let state = Contract::get_approval_state(0).unwrap();
+        assert_eq!(state.approved_by(), &[alice, bob]);
+        assert_eq!(state.threshold, None);
+        assert_eq!(contract.execute(0), "hello");
+    }
+
+    #[test]
+    fn approval_state_borsh_round_trip() {
+        let alice: AccountId = "alice".parse().unwrap();
+
+        let state = ApprovalState {
+            approved_by: vec![alice],
+            created_at_nanoseconds: 42,
+            threshold: Some(3),
+        };
+
+        let decoded: ApprovalState =
+            near_sdk::borsh::from_slice(&near_sdk::borsh::to_vec(&state).unwrap()).unwrap();
+
+        assert_eq!(decoded.approved_by, state.approved_by);
+        assert_eq!(decoded.created_at_nanoseconds, 42);
+        assert_eq!(decoded.threshold, Some(3));
+    }
+
+    #[test]
+    fn legacy_configuration_layout() {
+        // A configuration as stored before strict mode was introduced.
+        let legacy_config = near_sdk::borsh::to_vec(&(2u8, 100u64)).unwrap();
+
+        let config: Configuration<Contract> = near_sdk::borsh::from_slice(&legacy_config).unwrap();
+        assert_eq!(config.threshold, 2);
+        assert_eq!(config.validity_period_nanoseconds, 100);
+        assert!(!config.strict);
+
+        let decoded: Configuration<Contract> =
+            near_sdk::borsh::from_slice(&near_sdk::borsh::to_vec(&config.with_strict()).unwrap())
+                .unwrap();
+        assert_eq!(decoded.threshold, 2);
+        assert_eq!(decoded.validity_period_nanoseconds, 100);
+        assert!(decoded.strict);
+    }
+
+    #[test]
+    #[should_panic = "InvalidThresholdError"]
+    fn set_threshold_zero_fail() {
+        let mut contract = Contract::new();
+
+        contract.set_threshold(0);
+    }
 }
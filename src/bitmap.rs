@@ -0,0 +1,156 @@
+//! Compact, index-based membership sets
+//!
+//! A [`Bitmap`] tracks one bit per `u64` index, packed into blocks of 2048
+//! bits (32 `u64` words, 256 bytes) that are each stored in their own storage
+//! record. Only blocks with at least one set bit occupy storage, so sparse
+//! indices are cheap. Every record is charged for its value, its key (the
+//! bitmap's prefix followed by an 8-byte block index), and NEAR's 40 bytes of
+//! per-record overhead, i.e. about 304 bytes plus the prefix: a dense set of
+//! one million entries (489 blocks) costs roughly 150 KB. This makes it a good
+//! fit for "already claimed" tracking in airdrops and allowlists, where an
+//! `UnorderedSet<AccountId>` would store a full account ID per entry.
+use near_sdk::{near, IntoStorageKey};
+
+use crate::slot::Slot;
+
+const WORD_BITS: u64 = 64;
+const BLOCK_WORDS: usize = 32;
+const BLOCK_BITS: u64 = WORD_BITS * BLOCK_WORDS as u64;
+
+type Block = [u64; BLOCK_WORDS];
+
+/// A set of `u64` indices backed by packed blocks in contract storage.
+#[derive(Clone, Debug)]
+#[near]
+pub struct Bitmap {
+    root: Slot<()>,
+}
+
+impl Bitmap {
+    /// Creates a bitmap that stores its blocks under the given key prefix.
+    pub fn new(key: impl IntoStorageKey) -> Self {
+        Self {
+            root: Slot::root(key),
+        }
+    }
+
+    fn slot_block(&self, block: u64) -> Slot<Block> {
+        self.root.field(block.to_be_bytes().to_vec())
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // the word index is < BLOCK_WORDS
+    fn locate(index: u64) -> (u64, usize, u64) {
+        let bit = index % BLOCK_BITS;
+        (
+            index / BLOCK_BITS,
+            (bit / WORD_BITS) as usize,
+            1 << (bit % WORD_BITS),
+        )
+    }
+
+    /// Whether the bit at `index` is set.
+    #[must_use]
+    pub fn get(&self, index: u64) -> bool {
+        let (block, word, mask) = Self::locate(index);
+        self.slot_block(block)
+            .read()
+            .is_some_and(|bits| bits[word] & mask != 0)
+    }
+
+    /// Sets the bit at `index`. Returns `true` if the bit was previously
+    /// unset.
+    pub fn set(&mut self, index: u64) -> bool {
+        let (block, word, mask) = Self::locate(index);
+        let mut slot = self.slot_block(block);
+        let mut bits = slot.read().unwrap_or([0; BLOCK_WORDS]);
+
+        if bits[word] & mask != 0 {
+            return false;
+        }
+
+        bits[word] |= mask;
+        slot.write(&bits);
+        true
+    }
+
+    /// Clears the bit at `index`, removing its block from storage once no
+    /// bits in it remain set. Returns `true` if the bit was previously set.
+    pub fn clear(&mut self, index: u64) -> bool {
+        let (block, word, mask) = Self::locate(index);
+        let mut slot = self.slot_block(block);
+        let Some(mut bits) = slot.read() else {
+            return false;
+        };
+
+        if bits[word] & mask == 0 {
+            return false;
+        }
+
+        bits[word] &= !mask;
+        if bits.iter().all(|&w| w == 0) {
+            slot.remove();
+        } else {
+            slot.write(&bits);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bitmap;
+
+    #[test]
+    fn set_get_clear_across_word_and_block_boundaries() {
+        let mut bitmap = Bitmap::new(b"b");
+        let indices = [0, 1, 63, 64, 65, 127, 128, 2047, 2048, 2049];
+
+        for &index in &indices {
+            assert!(!bitmap.get(index));
+            assert!(bitmap.set(index));
+            assert!(!bitmap.set(index));
+        }
+
+        for index in 0..=2100 {
+            assert_eq!(bitmap.get(index), indices.contains(&index), "{index}");
+        }
+
+        assert!(bitmap.clear(63));
+        assert!(!bitmap.clear(63));
+        assert!(!bitmap.get(63));
+        assert!(!bitmap.get(62));
+        assert!(bitmap.get(64));
+
+        assert!(bitmap.clear(64));
+        assert!(bitmap.get(65));
+        assert!(!bitmap.clear(66));
+
+        assert!(bitmap.clear(2047));
+        assert!(bitmap.get(2048));
+        assert!(bitmap.clear(2048));
+        assert!(bitmap.get(2049));
+    }
+
+    #[test]
+    fn sparse_indices_only_allocate_touched_blocks() {
+        let mut bitmap = Bitmap::new(b"b");
+
+        bitmap.set(5);
+        bitmap.set(1_000_000);
+        bitmap.set(u64::MAX);
+
+        assert!(bitmap.slot_block(0).exists());
+        assert!(!bitmap.slot_block(1).exists());
+        assert!(bitmap.slot_block(1_000_000 / 2048).exists());
+        assert!(bitmap.slot_block(u64::MAX / 2048).exists());
+        assert!(bitmap.get(u64::MAX));
+
+        bitmap.set(2000);
+        bitmap.clear(5);
+        assert!(bitmap.slot_block(0).exists());
+
+        bitmap.clear(2000);
+        assert!(!bitmap.slot_block(0).exists());
+        assert!(!bitmap.get(2000));
+    }
+}
@@ -14,24 +14,48 @@
 //! [`root`][EscrowInternal::root], make sure you don't accidentally collide
 //! these storage entries in your contract. You can change the key this is
 //! stored under by providing `storage_key` to the macro.
+//!
+//! [`EscrowContributions`] optionally tracks per-depositor contributions
+//! toward a locked item, e.g. for crowdfunding.
+//!
+//! [`derive_escrow_id`] derives collision-resistant IDs from structured
+//! inputs, and [`Escrow::try_lock`] refuses to lock an ID that is already
+//! locked, instead of panicking.
+//!
+//! [`Escrow::reset_escrow`] removes all storage of an item, e.g. to tear
+//! down the escrow namespace before repurposing it.
+//!
+//! [`EscrowEnumeration`] lists the currently-locked items if enumeration is
+//! enabled with [`EscrowInternal::enumerable`] (`#[escrow(enumerable)]`).
+//! Only items locked while enumeration is enabled are listed: other items
+//! remain accessible via [`Escrow::get_escrow`], but will not appear in
+//! [`EscrowEnumeration::list_escrows`] until they are unlocked and locked
+//! again.
 use crate::{event, standard::nep297::Event};
 use crate::{slot::Slot, DefaultStorageKey};
 use near_sdk::{
-    borsh::{BorshDeserialize, BorshSerialize},
-    env::panic_str,
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    collections::{UnorderedMap, UnorderedSet},
+    env::{self, panic_str},
+    json_types::U128,
     require,
     serde::Serialize,
-    BorshStorageKey,
+    AccountId, AccountIdRef, BorshStorageKey, CryptoHash,
 };
+use thiserror::Error;
 
 const ESCROW_ALREADY_LOCKED_MESSAGE: &str = "Already locked";
 const ESCROW_NOT_LOCKED_MESSAGE: &str = "Lock required";
 const ESCROW_UNLOCK_HANDLER_FAILED_MESSAGE: &str = "Unlock handler failed";
+const ESCROW_CONTRIBUTION_OVERFLOW_MESSAGE: &str = "Contribution overflow";
 
 #[derive(BorshSerialize, BorshStorageKey)]
 #[borsh(crate = "near_sdk::borsh")]
 enum StorageKey<'a, T> {
     Locked(&'a T),
+    Contributions(&'a T),
+    ContributionTotal(&'a T),
+    LockedIds,
 }
 
 /// Emit the state of an escrow lock and whether it was locked or unlocked.
@@ -48,6 +72,86 @@ pub struct Lock<Id: Serialize, State: Serialize> {
     pub locked: Option<State>,
 }
 
+/// Emitted when an account contributes toward an escrowed item.
+#[event(
+    standard = "x-escrow",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+pub struct Deposit<Id: Serialize> {
+    /// The identifier for a lock.
+    pub id: Id,
+    /// The depositing account.
+    pub account_id: AccountId,
+    /// The amount deposited in this call.
+    pub amount: U128,
+    /// The total contribution of the account after this deposit.
+    pub contribution: U128,
+}
+
+/// Emitted when a depositor's contribution toward an escrowed item is
+/// refunded.
+#[event(
+    standard = "x-escrow",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+pub struct Refund<Id: Serialize> {
+    /// The identifier for a lock.
+    pub id: Id,
+    /// The refunded account.
+    pub account_id: AccountId,
+    /// The refunded amount.
+    pub amount: U128,
+}
+
+/// Emitted when contributions toward an escrowed item are released.
+#[event(
+    standard = "x-escrow",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "crate"
+)]
+pub struct Release<Id: Serialize> {
+    /// The identifier for a lock.
+    pub id: Id,
+    /// The number of depositors whose contributions were released.
+    pub contributors: u64,
+    /// The released amount.
+    pub amount: U128,
+}
+
+/// Derives an escrow ID by hashing the Borsh serialization of `parts`,
+/// e.g. a tuple of the fields that identify the escrowed item. Because Borsh
+/// is unambiguous, distinct inputs of the same type derive distinct IDs.
+///
+/// # Examples
+///
+/// ```
+/// use near_sdk::AccountId;
+/// use near_sdk_contract_tools::escrow::derive_escrow_id;
+///
+/// let seller: AccountId = "seller.near".parse().unwrap();
+/// let id = derive_escrow_id(&("order", &seller, 42u64));
+///
+/// assert_ne!(id, derive_escrow_id(&("order", &seller, 43u64)));
+/// ```
+///
+/// # Panics
+///
+/// - If `parts` fails to serialize.
+#[must_use]
+pub fn derive_escrow_id(parts: &impl BorshSerialize) -> CryptoHash {
+    env::sha256_array(&borsh::to_vec(parts).unwrap())
+}
+
+/// The item is already locked.
+#[derive(Error, Clone, Debug)]
+#[error("{}", ESCROW_ALREADY_LOCKED_MESSAGE)]
+pub struct EscrowExistsError;
+
 /// Inner storage modifiers and functionality required for escrow to succeed.
 pub trait EscrowInternal {
     /// Identifier over which the escrow exists.
@@ -80,6 +184,64 @@ pub trait EscrowInternal {
     fn set_unlocked(&mut self, id: &Self::Id) {
         self.locked_slot(id).remove();
     }
+
+    /// Whether to track the IDs of locked items for [`EscrowEnumeration`].
+    /// Tracking costs an additional storage write per lock and unlock.
+    #[must_use]
+    fn enumerable() -> bool {
+        false
+    }
+
+    /// Storage slot for the backing `UnorderedSet` of the Borsh-serialized
+    /// IDs of all locked items. Only maintained if
+    /// [`EscrowInternal::enumerable`] is `true`.
+    fn locked_ids_slot(&self) -> Slot<UnorderedSet<Vec<u8>>> {
+        Self::root().field(StorageKey::<Self::Id>::LockedIds)
+    }
+
+    /// Deserializes the backing `UnorderedSet` of locked IDs, executes
+    /// predicate on it, and writes the set back to storage.
+    fn with_locked_ids_mut<T>(&mut self, f: impl FnOnce(&mut UnorderedSet<Vec<u8>>) -> T) -> T {
+        let mut slot = self.locked_ids_slot();
+        let mut ids = slot
+            .read()
+            .unwrap_or_else(|| UnorderedSet::new(slot.key.clone()));
+        let value = f(&mut ids);
+        slot.write(&ids);
+        value
+    }
+
+    /// Storage slot for the backing `UnorderedMap` of the contributions of
+    /// each account to `id`.
+    fn contributions_slot(&self, id: &Self::Id) -> Slot<UnorderedMap<AccountId, u128>> {
+        Self::root().field(StorageKey::Contributions(id))
+    }
+
+    /// Deserializes the backing `UnorderedMap` of contributions to `id`,
+    /// executes predicate on it, and writes the map back to storage, or
+    /// removes it if it is empty.
+    fn with_contributions_mut<T>(
+        &mut self,
+        id: &Self::Id,
+        f: impl FnOnce(&mut UnorderedMap<AccountId, u128>) -> T,
+    ) -> T {
+        let mut slot = self.contributions_slot(id);
+        let mut contributions = slot
+            .read()
+            .unwrap_or_else(|| UnorderedMap::new(slot.key.clone()));
+        let value = f(&mut contributions);
+        if contributions.is_empty() {
+            slot.remove();
+        } else {
+            slot.write(&contributions);
+        }
+        value
+    }
+
+    /// Slot for the sum of all contributions to `id`.
+    fn contribution_total_slot(&self, id: &Self::Id) -> Slot<u128> {
+        Self::root().field(StorageKey::ContributionTotal(id))
+    }
 }
 
 /// Some escrowable capabilities, with a simple locking/unlocking mechanism.
@@ -93,9 +255,18 @@ pub trait Escrow {
     type State: BorshSerialize + BorshDeserialize;
 
     /// Lock some [`State`](Escrow::State) by its [`Id`](Escrow::Id) within the
-    /// store.
+    /// store. Panics if the item is already locked.
     fn lock(&mut self, id: &Self::Id, state: &Self::State);
 
+    /// Lock some [`State`](Escrow::State) by its [`Id`](Escrow::Id) within the
+    /// store, leaving the existing lock untouched if the item is already
+    /// locked.
+    ///
+    /// # Errors
+    ///
+    /// - If the item is already locked.
+    fn try_lock(&mut self, id: &Self::Id, state: &Self::State) -> Result<(), EscrowExistsError>;
+
     /// Unlock and release some [`State`](Escrow::State) by its
     /// [`Id`](Escrow::Id).
     ///
@@ -105,6 +276,20 @@ pub trait Escrow {
 
     /// Check if the item is locked
     fn is_locked(&self, id: &Self::Id) -> bool;
+
+    /// The state the item is locked with, if it is locked.
+    fn get_escrow(&self, id: &Self::Id) -> Option<Self::State>;
+
+    /// Removes all storage for `id`: its lock, its contributions (draining
+    /// the backing `UnorderedMap` so that no entries are orphaned) and their
+    /// total, and its enumeration entry. Storage of other items is not
+    /// touched. Does not emit events.
+    ///
+    /// The gas cost is proportional to the number of contributors. If it is
+    /// unbounded, clear the contributions in pages with
+    /// [`EscrowContributions::refund`] or [`EscrowContributions::release`]
+    /// first.
+    fn reset_escrow(&mut self, id: &Self::Id);
 }
 
 impl<T> Escrow for T
@@ -115,9 +300,22 @@ where
     type State = <Self as EscrowInternal>::State;
 
     fn lock(&mut self, id: &Self::Id, state: &Self::State) {
-        require!(self.get_locked(id).is_none(), ESCROW_ALREADY_LOCKED_MESSAGE);
+        self.try_lock(id, state)
+            .unwrap_or_else(|e| panic_str(&e.to_string()));
+    }
+
+    fn try_lock(&mut self, id: &Self::Id, state: &Self::State) -> Result<(), EscrowExistsError> {
+        if self.locked_slot(id).exists() {
+            return Err(EscrowExistsError);
+        }
 
         self.set_locked(id, state);
+
+        if Self::enumerable() {
+            self.with_locked_ids_mut(|ids| ids.insert(&borsh::to_vec(id).unwrap()));
+        }
+
+        Ok(())
     }
 
     fn unlock(&mut self, id: &Self::Id, unlock_handler: impl FnOnce(&Self::State) -> bool) {
@@ -127,6 +325,10 @@ where
 
         if unlock_handler(&lock) {
             self.set_unlocked(id);
+
+            if Self::enumerable() {
+                self.with_locked_ids_mut(|ids| ids.remove(&borsh::to_vec(id).unwrap()));
+            }
         } else {
             panic_str(ESCROW_UNLOCK_HANDLER_FAILED_MESSAGE)
         }
@@ -135,6 +337,276 @@ where
     fn is_locked(&self, id: &Self::Id) -> bool {
         self.get_locked(id).is_some()
     }
+
+    fn get_escrow(&self, id: &Self::Id) -> Option<Self::State> {
+        self.get_locked(id)
+    }
+
+    fn reset_escrow(&mut self, id: &Self::Id) {
+        self.set_unlocked(id);
+
+        let mut slot = self.contributions_slot(id);
+        if let Some(mut contributions) = slot.read() {
+            contributions.clear();
+        }
+        slot.remove();
+        self.contribution_total_slot(id).remove();
+
+        if Self::enumerable() {
+            let empty = self.with_locked_ids_mut(|ids| {
+                ids.remove(&borsh::to_vec(id).unwrap());
+                ids.is_empty()
+            });
+            if empty {
+                self.locked_ids_slot().remove();
+            }
+        }
+    }
+}
+
+/// Enumerates the currently-locked items. Always empty unless
+/// [`EscrowInternal::enumerable`] is `true`.
+///
+/// Ordering is not guaranteed to be stable: unlocking an item may move
+/// another item into its position.
+pub trait EscrowEnumeration {
+    /// Identifier over which the escrow exists.
+    type Id: BorshSerialize + BorshDeserialize;
+    /// State stored inside the lock.
+    type State: BorshSerialize + BorshDeserialize;
+
+    /// The number of currently-locked items.
+    fn escrow_count(&self) -> u64;
+
+    /// Up to `limit` locked items and their states, skipping the first
+    /// `from_index` items.
+    fn list_escrows(&self, from_index: usize, limit: usize) -> Vec<(Self::Id, Self::State)>;
+}
+
+impl<T> EscrowEnumeration for T
+where
+    T: EscrowInternal,
+    <T as EscrowInternal>::Id: BorshDeserialize,
+{
+    type Id = <Self as EscrowInternal>::Id;
+    type State = <Self as EscrowInternal>::State;
+
+    fn escrow_count(&self) -> u64 {
+        self.locked_ids_slot().read().map_or(0, |ids| ids.len())
+    }
+
+    fn list_escrows(&self, from_index: usize, limit: usize) -> Vec<(Self::Id, Self::State)> {
+        let Some(ids) = self.locked_ids_slot().read() else {
+            return vec![];
+        };
+
+        ids.iter()
+            .skip(from_index)
+            .take(limit)
+            .filter_map(|bytes| {
+                let id = Self::Id::try_from_slice(&bytes).ok()?;
+                let state = self.get_locked(&id)?;
+                Some((id, state))
+            })
+            .collect()
+    }
+}
+
+/// Tracks contributions from multiple depositors toward a single escrowed
+/// item. Amounts are purely accounting: this trait does not move funds, so
+/// the caller is responsible for e.g. transferring refunds.
+///
+/// Contributions are stored in an `UnorderedMap` under the escrow
+/// [`root`](EscrowInternal::root), so refunds can be processed in pages of
+/// bounded size with [`EscrowContributions::refund`].
+pub trait EscrowContributions {
+    /// Identifier over which the escrow exists.
+    type Id: BorshSerialize + Serialize;
+
+    /// Records a contribution of `amount` by the predecessor toward the
+    /// locked item `id`. Repeated deposits by the same account accumulate.
+    /// Returns the account's total contribution. Emits a [`Deposit`] event.
+    fn deposit_to(&mut self, id: &Self::Id, amount: u128) -> u128;
+
+    /// The total contribution of `account_id` toward `id`.
+    fn contribution_of(&self, id: &Self::Id, account_id: &AccountIdRef) -> u128;
+
+    /// The number of accounts with an outstanding contribution toward `id`.
+    fn contributor_count(&self, id: &Self::Id) -> u64;
+
+    /// Up to `limit` accounts with an outstanding contribution toward `id`,
+    /// skipping the first `from_index` accounts.
+    ///
+    /// Ordering is not guaranteed to be stable: refunding an account may move
+    /// another account into its position.
+    fn contributors(&self, id: &Self::Id, from_index: usize, limit: usize) -> Vec<AccountId>;
+
+    /// The sum of all contributions toward `id`.
+    fn total_contributions(&self, id: &Self::Id) -> u128;
+
+    /// Whether the contributions toward `id` have reached `target`.
+    fn is_target_reached(&self, id: &Self::Id, target: u128) -> bool {
+        self.total_contributions(id) >= target
+    }
+
+    /// Clears the contributions of up to `limit` depositors toward `id`,
+    /// returning each depositor's exact contribution so that it can be
+    /// refunded. Emits a [`Refund`] event per depositor. Call repeatedly
+    /// until [`EscrowContributions::contributor_count`] is `0` to refund
+    /// every depositor.
+    fn refund(&mut self, id: &Self::Id, limit: usize) -> Vec<(AccountId, u128)>;
+
+    /// Clears all contributions toward `id`, returning each depositor's
+    /// exact contribution so that it can be refunded. Emits a [`Refund`]
+    /// event per depositor.
+    ///
+    /// The cost grows with the number of depositors; prefer
+    /// [`EscrowContributions::refund`] if it is unbounded.
+    fn refund_all(&mut self, id: &Self::Id) -> Vec<(AccountId, u128)> {
+        self.refund(id, usize::MAX)
+    }
+
+    /// Clears the contributions of up to `limit` depositors toward `id`,
+    /// returning their sum so that it can be released. Emits a [`Release`]
+    /// event if any contributions were cleared. Call repeatedly until
+    /// [`EscrowContributions::contributor_count`] is `0` to release every
+    /// contribution.
+    fn release(&mut self, id: &Self::Id, limit: usize) -> u128;
+
+    /// Clears all contributions toward `id`, returning the pooled total so
+    /// that it can be released. Emits a [`Release`] event if any
+    /// contributions were cleared.
+    ///
+    /// The cost grows with the number of depositors; prefer
+    /// [`EscrowContributions::release`] if it is unbounded.
+    fn release_all(&mut self, id: &Self::Id) -> u128 {
+        self.release(id, usize::MAX)
+    }
+}
+
+/// Clears the contributions of up to `limit` depositors toward `id`,
+/// updating the total, and returns the cleared contributions.
+fn take_contributions<T: EscrowInternal>(
+    contract: &mut T,
+    id: &T::Id,
+    limit: usize,
+) -> Vec<(AccountId, u128)> {
+    let taken = contract.with_contributions_mut(id, |contributions| {
+        let taken = contributions.iter().take(limit).collect::<Vec<_>>();
+        for (account_id, _) in &taken {
+            contributions.remove(account_id);
+        }
+        taken
+    });
+
+    let amount = taken.iter().map(|(_, amount)| amount).sum::<u128>();
+    let mut total_slot = contract.contribution_total_slot(id);
+    let total = total_slot.read().unwrap_or(0).saturating_sub(amount);
+    if total == 0 {
+        total_slot.remove();
+    } else {
+        total_slot.write(&total);
+    }
+
+    taken
+}
+
+impl<T> EscrowContributions for T
+where
+    T: EscrowInternal,
+    <T as EscrowInternal>::Id: Serialize,
+{
+    type Id = <Self as EscrowInternal>::Id;
+
+    fn deposit_to(&mut self, id: &Self::Id, amount: u128) -> u128 {
+        require!(self.get_locked(id).is_some(), ESCROW_NOT_LOCKED_MESSAGE);
+
+        let account_id = env::predecessor_account_id();
+
+        let contribution = self.with_contributions_mut(id, |contributions| {
+            let contribution = contributions
+                .get(&account_id)
+                .unwrap_or(0)
+                .checked_add(amount)
+                .unwrap_or_else(|| panic_str(ESCROW_CONTRIBUTION_OVERFLOW_MESSAGE));
+            contributions.insert(&account_id, &contribution);
+            contribution
+        });
+
+        let mut total_slot = self.contribution_total_slot(id);
+        let total = total_slot
+            .read()
+            .unwrap_or(0)
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_str(ESCROW_CONTRIBUTION_OVERFLOW_MESSAGE));
+        total_slot.write(&total);
+
+        Deposit {
+            id,
+            account_id,
+            amount: amount.into(),
+            contribution: contribution.into(),
+        }
+        .emit();
+
+        contribution
+    }
+
+    fn contribution_of(&self, id: &Self::Id, account_id: &AccountIdRef) -> u128 {
+        self.contributions_slot(id)
+            .read()
+            .and_then(|contributions| contributions.get(&account_id.to_owned()))
+            .unwrap_or(0)
+    }
+
+    fn contributor_count(&self, id: &Self::Id) -> u64 {
+        self.contributions_slot(id)
+            .read()
+            .map_or(0, |contributions| contributions.len())
+    }
+
+    fn contributors(&self, id: &Self::Id, from_index: usize, limit: usize) -> Vec<AccountId> {
+        let Some(contributions) = self.contributions_slot(id).read() else {
+            return vec![];
+        };
+
+        contributions.keys().skip(from_index).take(limit).collect()
+    }
+
+    fn total_contributions(&self, id: &Self::Id) -> u128 {
+        self.contribution_total_slot(id).read().unwrap_or(0)
+    }
+
+    fn refund(&mut self, id: &Self::Id, limit: usize) -> Vec<(AccountId, u128)> {
+        let refunds = take_contributions(self, id, limit);
+
+        for (account_id, amount) in &refunds {
+            Refund {
+                id,
+                account_id: account_id.clone(),
+                amount: (*amount).into(),
+            }
+            .emit();
+        }
+
+        refunds
+    }
+
+    fn release(&mut self, id: &Self::Id, limit: usize) -> u128 {
+        let released = take_contributions(self, id, limit);
+        let amount = released.iter().map(|(_, amount)| amount).sum::<u128>();
+
+        if !released.is_empty() {
+            Release {
+                id,
+                contributors: released.len() as u64,
+                amount: amount.into(),
+            }
+            .emit();
+        }
+
+        amount
+    }
 }
 
 /// A wrapper trait allowing all implementations of `State` and `Id` that
@@ -173,7 +645,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::Escrow;
+    use super::{derive_escrow_id, Escrow, EscrowContributions, EscrowEnumeration};
     use crate::escrow::EscrowInternal;
     use near_sdk::{
         near, test_utils::VMContextBuilder, testing_env, AccountId, NearToken, PanicOnDefault,
@@ -186,10 +658,15 @@ mod tests {
     const ONE_YOCTO: u128 = 1;
 
     #[derive(Escrow, PanicOnDefault)]
-    #[escrow(id = "u64", state = "bool", crate = "crate")]
+    #[escrow(id = "u64", state = "bool", enumerable, crate = "crate")]
     #[near(contract_state)]
     struct Contract {}
 
+    #[derive(Escrow, PanicOnDefault)]
+    #[escrow(id = "u64", state = "bool", crate = "crate")]
+    #[near(contract_state)]
+    struct NonEnumerableContract {}
+
     #[near]
     impl Contract {
         #[init]
@@ -230,6 +707,27 @@ mod tests {
         contract.lock(&ID, &IS_NOT_READY);
     }
 
+    #[test]
+    fn test_try_lock_twice() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+
+        contract.try_lock(&ID, &IS_NOT_READY).unwrap();
+        assert!(contract.try_lock(&ID, &true).is_err());
+        assert_eq!(contract.get_escrow(&ID), Some(IS_NOT_READY));
+    }
+
+    #[test]
+    fn test_derive_escrow_id() {
+        let a = derive_escrow_id(&("ab", "c", 1u64));
+        let b = derive_escrow_id(&("a", "bc", 1u64));
+        let c = derive_escrow_id(&("ab", "c", 2u64));
+
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a, derive_escrow_id(&("ab", "c", 1u64)));
+    }
+
     #[test]
     fn test_can_unlock() {
         testing_env!(get_context(ONE_YOCTO, None));
@@ -242,6 +740,45 @@ mod tests {
         assert!(contract.get_locked(&ID).is_none());
     }
 
+    #[test]
+    fn test_list_and_get_escrows() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+
+        contract.lock(&1, &false);
+        contract.lock(&2, &true);
+
+        assert_eq!(contract.escrow_count(), 2);
+        let mut escrows = contract.list_escrows(0, 10);
+        escrows.sort_unstable();
+        assert_eq!(escrows, vec![(1, false), (2, true)]);
+        assert_eq!(contract.list_escrows(1, 10).len(), 1);
+        assert_eq!(contract.list_escrows(0, 1).len(), 1);
+
+        assert_eq!(contract.get_escrow(&2), Some(true));
+        assert_eq!(contract.get_escrow(&3), None);
+
+        contract.unlock(&2, |_| true);
+
+        assert_eq!(contract.escrow_count(), 1);
+        assert_eq!(contract.list_escrows(0, 10), vec![(1, false)]);
+        assert_eq!(contract.get_escrow(&2), None);
+        assert!(!contract.is_locked(&2));
+    }
+
+    #[test]
+    fn test_enumeration_is_opt_in() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = NonEnumerableContract {};
+
+        contract.lock(&1, &false);
+
+        assert_eq!(contract.escrow_count(), 0);
+        assert!(contract.list_escrows(0, 10).is_empty());
+        assert!(!contract.locked_ids_slot().exists());
+        assert_eq!(contract.get_escrow(&1), Some(false));
+    }
+
     #[test]
     #[should_panic(expected = "Unlock handler failed")]
     fn test_cannot_unlock_until_ready() {
@@ -254,4 +791,169 @@ mod tests {
 
         assert!(contract.get_locked(&ID).is_none());
     }
+
+    #[test]
+    fn test_contributions_accumulate() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+
+        contract.lock(&ID, &IS_NOT_READY);
+        contract.deposit_to(&ID, 10);
+        assert_eq!(contract.deposit_to(&ID, 5), 15);
+
+        assert_eq!(contract.contribution_of(&ID, &alice()), 15);
+        assert_eq!(contract.contributors(&ID, 0, 10), vec![alice()]);
+        assert_eq!(contract.contributor_count(&ID), 1);
+        assert_eq!(contract.total_contributions(&ID), 15);
+        assert!(contract.is_target_reached(&ID, 15));
+        assert!(!contract.is_target_reached(&ID, 16));
+    }
+
+    #[test]
+    fn test_refund_all_returns_exact_contributions() {
+        let bob: AccountId = "bob".parse().unwrap();
+
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+        contract.lock(&ID, &IS_NOT_READY);
+        contract.deposit_to(&ID, 7);
+
+        testing_env!(get_context(ONE_YOCTO, Some(bob.clone())));
+        contract.deposit_to(&ID, 20);
+
+        testing_env!(get_context(ONE_YOCTO, None));
+        contract.deposit_to(&ID, 3);
+
+        assert_eq!(contract.total_contributions(&ID), 30);
+
+        let refunds = contract.refund_all(&ID);
+
+        assert_eq!(refunds, vec![(alice(), 10), (bob.clone(), 20)]);
+        assert_eq!(contract.contribution_of(&ID, &alice()), 0);
+        assert_eq!(contract.contribution_of(&ID, &bob), 0);
+        assert_eq!(contract.total_contributions(&ID), 0);
+    }
+
+    #[test]
+    fn test_refund_is_paginated() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+        contract.lock(&ID, &IS_NOT_READY);
+
+        for (account_id, amount) in [("alice", 1), ("bob", 2), ("carol", 4)] {
+            testing_env!(get_context(ONE_YOCTO, Some(account_id.parse().unwrap())));
+            contract.deposit_to(&ID, amount);
+        }
+
+        let first = contract.refund(&ID, 2);
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(contract.contributor_count(&ID), 1);
+        assert_eq!(
+            contract.total_contributions(&ID),
+            7 - first.iter().map(|(_, amount)| amount).sum::<u128>(),
+        );
+
+        let rest = contract.refund(&ID, 2);
+
+        assert_eq!(rest.len(), 1);
+        assert_eq!(contract.contributor_count(&ID), 0);
+        assert_eq!(contract.total_contributions(&ID), 0);
+        assert!(contract.refund(&ID, 2).is_empty());
+
+        let mut refunds = [first, rest].concat();
+        refunds.sort_unstable();
+        assert_eq!(
+            refunds,
+            vec![
+                ("alice".parse().unwrap(), 1),
+                ("bob".parse().unwrap(), 2),
+                ("carol".parse().unwrap(), 4),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_release_all_returns_pooled_total() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+        contract.lock(&ID, &IS_NOT_READY);
+        contract.deposit_to(&ID, 7);
+
+        assert_eq!(contract.release_all(&ID), 7);
+        assert_eq!(contract.contributor_count(&ID), 0);
+        assert_eq!(contract.total_contributions(&ID), 0);
+    }
+
+    #[test]
+    fn test_release_is_paginated() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+        contract.lock(&ID, &IS_NOT_READY);
+
+        for (account_id, amount) in [("alice", 1), ("bob", 2), ("carol", 4)] {
+            testing_env!(get_context(ONE_YOCTO, Some(account_id.parse().unwrap())));
+            contract.deposit_to(&ID, amount);
+        }
+
+        let first = contract.release(&ID, 2);
+
+        assert_eq!(contract.contributor_count(&ID), 1);
+        assert_eq!(contract.total_contributions(&ID), 7 - first);
+
+        let rest = contract.release(&ID, 2);
+
+        assert_eq!(first + rest, 7);
+        assert_eq!(contract.contributor_count(&ID), 0);
+        assert_eq!(contract.total_contributions(&ID), 0);
+        assert_eq!(contract.release(&ID, 2), 0);
+    }
+
+    #[test]
+    fn test_reset_removes_all_storage() {
+        testing_env!(get_context(ONE_YOCTO, None));
+
+        let mut sibling = crate::slot::Slot::<u32>::new(b"sibling".to_vec());
+        sibling.write(&7);
+
+        let usage_before = near_sdk::env::storage_usage();
+
+        let mut contract = Contract::new();
+        contract.lock(&ID, &IS_NOT_READY);
+        contract.lock(&(ID + 1), &IS_NOT_READY);
+        for account_id in ["alice", "bob", "carol"] {
+            let mut context = get_context(ONE_YOCTO, Some(account_id.parse().unwrap()));
+            // Keep counting storage usage across contexts.
+            context.storage_usage = near_sdk::env::storage_usage();
+            testing_env!(context);
+            contract.deposit_to(&ID, 1);
+        }
+
+        contract.reset_escrow(&ID);
+
+        assert!(!contract.is_locked(&ID));
+        assert_eq!(contract.contributor_count(&ID), 0);
+        assert_eq!(contract.total_contributions(&ID), 0);
+        assert!(contract.is_locked(&(ID + 1)));
+        assert_eq!(contract.escrow_count(), 1);
+
+        contract.reset_escrow(&(ID + 1));
+
+        assert_eq!(near_sdk::env::storage_usage(), usage_before);
+        assert_eq!(contract.escrow_count(), 0);
+        assert_eq!(sibling.read(), Some(7));
+
+        // The item can be locked again after a reset.
+        contract.lock(&ID, &IS_NOT_READY);
+        assert!(contract.is_locked(&ID));
+    }
+
+    #[test]
+    #[should_panic(expected = "Lock required")]
+    fn test_cannot_deposit_to_unlocked() {
+        testing_env!(get_context(ONE_YOCTO, None));
+        let mut contract = Contract::new();
+
+        contract.deposit_to(&ID, 1);
+    }
 }
@@ -0,0 +1,287 @@
+//! Composable access guards.
+//!
+//! A [`Guard`] decides whether an account may proceed. The guards in this
+//! module can be combined using [`Any`], [`All`], and [`NoneOf`], so that
+//! ownership checks ([`Owner`](crate::owner::Owner)) and role checks ([`Rbac`]) can be mixed,
+//! e.g. "the owner, or any account with the `Admin` role."
+//!
+//! The [`access!`](crate::access) macro is the most convenient way to use
+//! guards from within a contract's `impl` block:
+//!
+//! ```ignore
+//! access!(any(owner(), role(Role::Admin)));
+//! ```
+//!
+//! The macro evaluates the guard against the predecessor account and panics
+//! with `"Unauthorized"` if the guard is not satisfied.
+use std::marker::PhantomData;
+
+use near_sdk::{env, AccountId};
+
+use crate::{owner::OwnerInternal, rbac::Rbac};
+
+const UNAUTHORIZED_FAIL_MESSAGE: &str = "Unauthorized";
+
+/// A check that an account is permitted to perform some action.
+pub trait Guard {
+    /// Whether `account_id` satisfies this guard.
+    fn check(&self, account_id: &AccountId) -> bool;
+}
+
+impl<G: Guard + ?Sized> Guard for &G {
+    fn check(&self, account_id: &AccountId) -> bool {
+        (**self).check(account_id)
+    }
+}
+
+/// Satisfied when the account is the current owner of contract `C`.
+pub struct OwnerGuard<C>(PhantomData<C>);
+
+impl<C> OwnerGuard<C> {
+    /// Creates a new owner guard.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C> Default for OwnerGuard<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: OwnerInternal> Guard for OwnerGuard<C> {
+    fn check(&self, account_id: &AccountId) -> bool {
+        C::slot_owner().read().as_ref() == Some(account_id)
+    }
+}
+
+/// Satisfied when the account has the specified role in contract `C`.
+pub struct RoleGuard<C: Rbac>(pub C::Role);
+
+impl<C: Rbac> RoleGuard<C> {
+    /// Creates a new role guard.
+    #[must_use]
+    pub fn new(role: C::Role) -> Self {
+        Self(role)
+    }
+}
+
+impl<C: Rbac> Guard for RoleGuard<C> {
+    fn check(&self, account_id: &AccountId) -> bool {
+        C::has_role(account_id, &self.0)
+    }
+}
+
+/// Satisfied when at least one of the inner guards is satisfied.
+pub struct Any<'a>(pub &'a [&'a dyn Guard]);
+
+impl Guard for Any<'_> {
+    fn check(&self, account_id: &AccountId) -> bool {
+        self.0.iter().any(|g| g.check(account_id))
+    }
+}
+
+/// Satisfied when all of the inner guards are satisfied.
+pub struct All<'a>(pub &'a [&'a dyn Guard]);
+
+impl Guard for All<'_> {
+    fn check(&self, account_id: &AccountId) -> bool {
+        self.0.iter().all(|g| g.check(account_id))
+    }
+}
+
+/// Satisfied when none of the inner guards are satisfied.
+pub struct NoneOf<'a>(pub &'a [&'a dyn Guard]);
+
+impl Guard for NoneOf<'_> {
+    fn check(&self, account_id: &AccountId) -> bool {
+        !self.0.iter().any(|g| g.check(account_id))
+    }
+}
+
+/// Panics with `"Unauthorized"` if `account_id` does not satisfy `guard`.
+pub fn require(guard: &dyn Guard, account_id: &AccountId) {
+    if !guard.check(account_id) {
+        env::panic_str(UNAUTHORIZED_FAIL_MESSAGE);
+    }
+}
+
+/// Panics with `"Unauthorized"` if the predecessor does not satisfy `guard`.
+pub fn require_predecessor(guard: &dyn Guard) {
+    require(guard, &env::predecessor_account_id());
+}
+
+/// Builds a [`Guard`] for `Self` from the [`access!`](crate::access) syntax.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __access_guard {
+    (owner ()) => {
+        $crate::guard::OwnerGuard::<Self>::new()
+    };
+    (role ($role:expr)) => {
+        $crate::guard::RoleGuard::<Self>::new($role)
+    };
+    (any ($($g:ident $a:tt),* $(,)?)) => {
+        $crate::guard::Any(&[$(&$crate::__access_guard!($g $a)),*])
+    };
+    (all ($($g:ident $a:tt),* $(,)?)) => {
+        $crate::guard::All(&[$(&$crate::__access_guard!($g $a)),*])
+    };
+    (none ($($g:ident $a:tt),* $(,)?)) => {
+        $crate::guard::NoneOf(&[$(&$crate::__access_guard!($g $a)),*])
+    };
+}
+
+/// Requires that the predecessor satisfies an access guard, panicking with
+/// `"Unauthorized"` otherwise. Must be invoked from within an `impl` block
+/// of the contract, since guards are evaluated against `Self`.
+///
+/// Supported guards:
+///
+/// - `owner()`: the predecessor is the current owner ([`Owner`](crate::owner::Owner)).
+/// - `role(expr)`: the predecessor has the role ([`Rbac`](crate::rbac::Rbac)).
+/// - `any(...)`, `all(...)`, `none(...)`: compose other guards.
+///
+/// # Examples
+///
+/// ```ignore
+/// pub fn set_value(&mut self, value: u32) {
+///     access!(any(owner(), role(Role::Admin)));
+///     self.value = value;
+/// }
+/// ```
+#[macro_export]
+macro_rules! access {
+    ($g:ident $a:tt) => {
+        $crate::guard::require_predecessor(&$crate::__access_guard!($g $a))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::{
+        near, test_utils::VMContextBuilder, testing_env, AccountId, BorshStorageKey, PanicOnDefault,
+    };
+    use near_sdk_contract_tools_macros::{Owner, Rbac};
+
+    use crate::{owner::Owner, rbac::Rbac};
+
+    #[derive(BorshStorageKey)]
+    #[near]
+    enum Role {
+        Admin,
+        Banned,
+    }
+
+    #[derive(Owner, Rbac, PanicOnDefault)]
+    #[owner(crate = "crate")]
+    #[rbac(roles = "Role", crate = "crate")]
+    #[near(contract_state)]
+    struct Contract {}
+
+    impl Contract {
+        fn new(owner_id: &AccountId) -> Self {
+            let mut contract = Self {};
+            Owner::init(&mut contract, owner_id);
+            contract
+        }
+
+        fn owner_only(&self) {
+            access!(owner());
+        }
+
+        fn admin_only(&self) {
+            access!(role(Role::Admin));
+        }
+
+        fn owner_or_admin(&self) {
+            access!(any(owner(), role(Role::Admin)));
+        }
+
+        fn admin_not_banned(&self) {
+            access!(all(role(Role::Admin), none(role(Role::Banned))));
+        }
+    }
+
+    fn predecessor(account_id: &AccountId) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account_id.clone())
+            .build());
+    }
+
+    fn setup() -> (Contract, AccountId, AccountId, AccountId) {
+        let owner: AccountId = "owner".parse().unwrap();
+        let admin: AccountId = "admin".parse().unwrap();
+        let alice: AccountId = "alice".parse().unwrap();
+
+        let mut contract = Contract::new(&owner);
+        contract.add_role(&admin, &Role::Admin);
+
+        (contract, owner, admin, alice)
+    }
+
+    #[test]
+    fn owner_only_success() {
+        let (contract, owner, _, _) = setup();
+        predecessor(&owner);
+        contract.owner_only();
+    }
+
+    #[test]
+    #[should_panic = "Unauthorized"]
+    fn owner_only_fail() {
+        let (contract, _, admin, _) = setup();
+        predecessor(&admin);
+        contract.owner_only();
+    }
+
+    #[test]
+    fn role_only_success() {
+        let (contract, _, admin, _) = setup();
+        predecessor(&admin);
+        contract.admin_only();
+    }
+
+    #[test]
+    #[should_panic = "Unauthorized"]
+    fn role_only_fail() {
+        let (contract, owner, _, _) = setup();
+        predecessor(&owner);
+        contract.admin_only();
+    }
+
+    #[test]
+    fn any_success() {
+        let (contract, owner, admin, _) = setup();
+        predecessor(&owner);
+        contract.owner_or_admin();
+        predecessor(&admin);
+        contract.owner_or_admin();
+    }
+
+    #[test]
+    #[should_panic = "Unauthorized"]
+    fn any_fail() {
+        let (contract, _, _, alice) = setup();
+        predecessor(&alice);
+        contract.owner_or_admin();
+    }
+
+    #[test]
+    fn all_none_success() {
+        let (contract, _, admin, _) = setup();
+        predecessor(&admin);
+        contract.admin_not_banned();
+    }
+
+    #[test]
+    #[should_panic = "Unauthorized"]
+    fn all_none_fail() {
+        let (mut contract, _, admin, _) = setup();
+        contract.add_role(&admin, &Role::Banned);
+        predecessor(&admin);
+        contract.admin_not_banned();
+    }
+}
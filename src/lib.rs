@@ -5,6 +5,37 @@
 pub use near_sdk_contract_tools_macros::*;
 
 /// Default storage keys used by various traits' `root()` functions.
+///
+/// The following root keys are reserved by default:
+///
+/// | Component | Root key |
+/// |-|-|
+/// | `ApprovalManager` | `~am` |
+/// | `Escrow` | `~es` |
+/// | `Nep141` | `~$141` |
+/// | `Nep145` | `~$145` |
+/// | `Nep148` | `~$148` |
+/// | `Nep171` | `~$171` |
+/// | `Nep177` | `~$177` |
+/// | `Nep178` | `~$178` |
+/// | `Nep181` | `~$181` |
+/// | `Owner` | `~o` |
+/// | `Pause` | `~p` |
+/// | `Rbac` | `~r` |
+/// | User-defined ([`Custom`](DefaultStorageKey::Custom)) | `~~<tag>` |
+///
+/// All keys beginning with `~` are reserved for this crate, and future
+/// components will take their default roots from that space, with one
+/// exception: the `~~` prefix is never used by built-in components and is
+/// set aside for user-defined components. Use [`DefaultStorageKey::Custom`]
+/// (or [`slot::Slot::custom_root`]) to root a component in that namespace,
+/// so that it cannot alias the state of any built-in component, current or
+/// future.
+///
+/// Components that are configured with the same root key (e.g. with
+/// `#[owner(storage_key = "...")]`), or where one root key is a prefix of
+/// another, may overwrite each other's state. Use
+/// [`debug_assert_unique_storage_roots!`] to detect such collisions.
 #[derive(Clone, Debug)]
 pub enum DefaultStorageKey {
     /// Default storage key for [`approval::ApprovalManagerInternal::root`].
@@ -31,6 +62,10 @@ pub enum DefaultStorageKey {
     Rbac,
     /// Default storage key for [`escrow::EscrowInternal::root`]
     Escrow,
+    /// Root key for a user-defined component, `~~` followed by the tag.
+    /// Distinct tags produce distinct roots, but a tag must not be a prefix
+    /// of another tag in the same contract.
+    Custom(&'static [u8]),
 }
 
 impl near_sdk::IntoStorageKey for DefaultStorageKey {
@@ -48,6 +83,7 @@ impl near_sdk::IntoStorageKey for DefaultStorageKey {
             DefaultStorageKey::Pause => b"~p".to_vec(),
             DefaultStorageKey::Rbac => b"~r".to_vec(),
             DefaultStorageKey::Escrow => b"~es".to_vec(),
+            DefaultStorageKey::Custom(tag) => [&b"~~"[..], tag].concat(),
         }
     }
 }
@@ -55,14 +91,18 @@ impl near_sdk::IntoStorageKey for DefaultStorageKey {
 pub mod standard;
 
 pub mod approval;
+pub mod bitmap;
 pub mod escrow;
 pub mod fast_account_id;
+pub mod guard;
 pub mod hook;
 pub mod migrate;
 pub mod owner;
 pub mod pause;
 pub mod rbac;
 pub mod slot;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod upgrade;
 pub mod utils;
 
@@ -75,9 +115,9 @@ pub mod nft {
                 StorageBalance, StorageBalanceBounds,
             },
             nep171::{
-                self, action::*, ext_nep171, ext_nep171_receiver, ext_nep171_resolver, Nep171,
-                Nep171Controller, Nep171ControllerInternal, Nep171Receiver, Nep171Resolver, Token,
-                TokenId,
+                self, action::*, ext_nep171, ext_nep171_receiver, ext_nep171_resolver,
+                sequential::SequentialTokenIds, Nep171, Nep171Controller, Nep171ControllerInternal,
+                Nep171Receiver, Nep171Resolver, Token, TokenId,
             },
             nep177::{
                 self, ext_nep177, ContractMetadata, Nep177, Nep177Controller,
@@ -102,8 +142,8 @@ pub mod ft {
         standard::{
             nep141::{
                 self, ext_nep141, ext_nep141_receiver, ext_nep141_resolver, Nep141, Nep141Burn,
-                Nep141Controller, Nep141ControllerInternal, Nep141Mint, Nep141Receiver,
-                Nep141Resolver, Nep141Transfer,
+                Nep141Controller, Nep141ControllerInternal, Nep141Mint, Nep141Observer,
+                Nep141Receiver, Nep141RegisterAndMint, Nep141Resolver, Nep141Transfer,
             },
             nep145::{
                 self, ext_nep145, Nep145, Nep145Controller, Nep145ControllerInternal,
@@ -16,12 +16,35 @@
 //! # Safety
 //! The contract state must conform to the old schema otherwise deserializing it
 //! will fail and throw an error.
+//!
+//! # Dry runs
+//!
+//! [`MigrateHook::dry_run_migrate`] deserializes the old schema and runs the
+//! conversion without writing anything, so operators can check that a
+//! migration will succeed before performing it. The derive macro can expose
+//! this as a view (`#[migrate(dry_run)]`). A conversion that panics will still
+//! cause the dry run to fail, but not with a reported error.
 #![allow(missing_docs)] // #[ext_contract(...)] does not play nicely with clippy
 
 use near_sdk::{
     borsh::{BorshDeserialize, BorshSerialize},
     env, ext_contract,
 };
+use thiserror::Error;
+
+/// Storage key under which `near_sdk` stores the contract state.
+const STATE_KEY: &[u8] = b"STATE";
+
+/// Errors reported by [`MigrateHook::dry_run_migrate`].
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum MigrateDryRunError {
+    /// There is no contract state to migrate.
+    #[error("No contract state found")]
+    NoState,
+    /// The contract state does not conform to the old schema.
+    #[error("Failed to deserialize old state: {0}")]
+    Deserialize(String),
+}
 
 // TODO: Migration events?
 // *Possibly* unnecessary, since the salient occurence will probably be the instigating event (e.g. a code upgrade)
@@ -42,6 +65,19 @@ pub trait MigrateController {
         env::state_read::<Self::OldSchema>()
             .unwrap_or_else(|| env::panic_str("Failed to deserialize old state"))
     }
+
+    /// Deserializes the old schema from storage, returning an error instead
+    /// of panicking if it is missing or does not conform to the old schema.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no contract state.
+    /// - If the contract state cannot be deserialized as the old schema.
+    fn try_deserialize_old_schema() -> Result<Self::OldSchema, MigrateDryRunError> {
+        let bytes = env::storage_read(STATE_KEY).ok_or(MigrateDryRunError::NoState)?;
+        Self::OldSchema::try_from_slice(&bytes)
+            .map_err(|e| MigrateDryRunError::Deserialize(e.to_string()))
+    }
 }
 
 /// Called on migration. Must be implemented by the user. (The derive macro
@@ -52,6 +88,16 @@ pub trait MigrateHook: MigrateController {
     fn on_migrate(
         old_schema: <Self as MigrateController>::OldSchema,
     ) -> <Self as MigrateController>::NewSchema;
+
+    /// Performs the migration without writing the result to storage, and
+    /// returns the state that would be written.
+    ///
+    /// # Errors
+    ///
+    /// - If the old schema cannot be deserialized from storage.
+    fn dry_run_migrate() -> Result<<Self as MigrateController>::NewSchema, MigrateDryRunError> {
+        Self::try_deserialize_old_schema().map(Self::on_migrate)
+    }
 }
 
 /// Migrate-able contracts expose this trait publicly
@@ -21,9 +21,11 @@
 //! * (ERR) Only a "paused" contract can call `unpause`.
 //! * (ERR) [`Pause::require_paused`] may only be called when the contract is paused.
 //! * (ERR) [`Pause::require_unpaused`] may only be called when the contract is unpaused.
+//! * A contract paused with [`Pause::pause_until`] is considered unpaused once
+//!   the block timestamp reaches the scheduled time.
 
 use crate::{slot::Slot, standard::nep297::Event, DefaultStorageKey};
-use near_sdk::require;
+use near_sdk::{env, near, require, AccountId, BorshStorageKey};
 use near_sdk_contract_tools_macros::event;
 
 const UNPAUSED_FAIL_MESSAGE: &str = "Disallowed while contract is unpaused";
@@ -32,16 +34,52 @@ const PAUSED_FAIL_MESSAGE: &str = "Disallowed while contract is paused";
 /// Events emitted when contract pause state is changed
 #[event(
     standard = "x-paus",
-    version = "1.0.0",
+    version = "1.1.0",
     crate = "crate",
     macros = "near_sdk_contract_tools_macros"
 )]
 #[derive(Debug, Clone)]
 pub enum PauseEvent {
     /// Emitted when the contract is paused
-    Pause,
+    Pause {
+        /// The account that paused the contract
+        account_id: AccountId,
+        /// Block timestamp (milliseconds) at which the contract was paused
+        timestamp_ms: u64,
+        /// Scheduled unpause time (milliseconds), if paused using
+        /// [`Pause::pause_until`]
+        unpause_at_ms: Option<u64>,
+    },
     /// Emitted when the contract is unpaused
-    Unpause,
+    Unpause {
+        /// The account that unpaused the contract
+        account_id: AccountId,
+        /// Block timestamp (milliseconds) at which the contract was unpaused
+        timestamp_ms: u64,
+    },
+}
+
+impl PauseEvent {
+    fn pause(unpause_at_ms: Option<u64>) -> Self {
+        Self::Pause {
+            account_id: env::predecessor_account_id(),
+            timestamp_ms: env::block_timestamp_ms(),
+            unpause_at_ms,
+        }
+    }
+
+    fn unpause() -> Self {
+        Self::Unpause {
+            account_id: env::predecessor_account_id(),
+            timestamp_ms: env::block_timestamp_ms(),
+        }
+    }
+}
+
+#[derive(BorshStorageKey)]
+#[near]
+enum StorageKey {
+    UnpauseAt,
 }
 
 /// Internal functions for [`Pause`]. Using these methods may result in unexpected behavior.
@@ -57,6 +95,12 @@ pub trait PauseInternal {
     fn slot_paused() -> Slot<bool> {
         Self::root().transmute()
     }
+
+    /// Storage slot for the scheduled unpause time (milliseconds)
+    #[must_use]
+    fn slot_unpause_at() -> Slot<u64> {
+        Self::root().field(StorageKey::UnpauseAt)
+    }
 }
 
 /// Contract private-only interactions for a pausable contracts.
@@ -94,18 +138,32 @@ pub trait PauseInternal {
 /// ```
 pub trait Pause {
     /// Force the contract pause state in a particular direction.
-    /// Does not emit events or check the current pause state.
+    /// Does not emit events or check the current pause state. Clears any
+    /// scheduled unpause time.
     fn set_is_paused(&mut self, is_paused: bool);
 
-    /// Returns `true` if the contract is paused, `false` otherwise
+    /// Returns `true` if the contract is paused, `false` otherwise. A
+    /// contract whose scheduled unpause time has been reached is not paused.
     fn is_paused() -> bool;
 
+    /// The time (in milliseconds) at which the contract will automatically
+    /// unpause, if it was paused using [`Pause::pause_until`].
+    fn unpause_at() -> Option<u64>;
+
     /// Pauses the contract if it is currently unpaused, panics otherwise.
-    /// Emits a `PauseEvent::Pause` event.
+    /// Emits a [`PauseEvent::Pause`] event.
     fn pause(&mut self);
 
+    /// Pauses the contract until the block timestamp reaches
+    /// `unpause_at_ms`, after which it is automatically considered unpaused
+    /// without another transaction. A time in the past leaves the contract
+    /// effectively unpaused. Panics if the contract is currently paused.
+    /// Emits a [`PauseEvent::Pause`] event.
+    fn pause_until(&mut self, unpause_at_ms: u64);
+
     /// Unpauses the contract if it is currently paused, panics otherwise.
-    /// Emits a `PauseEvent::Unpause` event.
+    /// Clears any scheduled unpause time. Emits a [`PauseEvent::Unpause`]
+    /// event.
     fn unpause(&mut self);
 
     /// Rejects if the contract is unpaused.
@@ -118,22 +176,37 @@ pub trait Pause {
 impl<T: PauseInternal> Pause for T {
     fn set_is_paused(&mut self, is_paused: bool) {
         Self::slot_paused().write(&is_paused);
+        Self::slot_unpause_at().remove();
     }
 
     fn is_paused() -> bool {
         Self::slot_paused().read().unwrap_or(false)
+            && Self::slot_unpause_at()
+                .read()
+                .map_or(true, |unpause_at| env::block_timestamp_ms() < unpause_at)
+    }
+
+    fn unpause_at() -> Option<u64> {
+        Self::slot_unpause_at().read()
     }
 
     fn pause(&mut self) {
         Self::require_unpaused();
         self.set_is_paused(true);
-        PauseEvent::Pause.emit();
+        PauseEvent::pause(None).emit();
+    }
+
+    fn pause_until(&mut self, unpause_at_ms: u64) {
+        Self::require_unpaused();
+        self.set_is_paused(true);
+        Self::slot_unpause_at().write(&unpause_at_ms);
+        PauseEvent::pause(Some(unpause_at_ms)).emit();
     }
 
     fn unpause(&mut self) {
         Self::require_paused();
         self.set_is_paused(false);
-        PauseEvent::Unpause.emit();
+        PauseEvent::unpause().emit();
     }
 
     fn require_paused() {
@@ -25,22 +25,83 @@
 //!     account has the specified role.
 //! * (ERR) [`Rbac::prohibit_role`] may only be called when the predecessor
 //!     account does not have the specified role.
-use std::iter::FusedIterator;
+//!
+//! # Member ordering
+//!
+//! By default, [`Rbac::iter_members_of`] returns members in the iteration
+//! order of the backing `UnorderedSet`, which is an implementation detail.
+//! Enabling [`RbacInternal::ordered_members`] (`#[rbac(ordered)]`) maintains
+//! an additional list of the members of each role, at the cost of extra
+//! storage, so that members are always returned in the order in which they
+//! were added. Removing a member preserves the relative order of the
+//! remaining members, which costs one storage write for every member added
+//! after the removed one. Membership changes should go through
+//! [`Rbac::add_role`], [`Rbac::remove_role`], and [`Rbac::clear_role`]:
+//! modifications made using [`Rbac::with_members_of_mut`] are not reflected
+//! in the ordered list. Ordering should be enabled before any roles are
+//! assigned: if the ordered list of a role does not contain every member
+//! (e.g. because ordering was enabled on a deployed contract), members of
+//! that role are returned in `UnorderedSet` order instead, so that none are
+//! missing.
+//!
+//! # Snapshots
+//!
+//! If the role type implements [`NamedRole`], [`RbacSnapshot`] can export the
+//! full set of role assignments (optionally page-by-page) and import them
+//! again, e.g. from within a [`Migrate`](crate::migrate::Migrate) conversion
+//! function. [`RbacSnapshot::reset`] removes all of the component's storage.
+//!
+//! # Exclusive roles
+//!
+//! [`RbacExclusive::add_role_exclusive`] assigns a role from a group of
+//! mutually exclusive roles (e.g. membership tiers), removing the account
+//! from the other roles in the group. It emits [`RbacEvent`]s for every
+//! change it makes. The other membership methods do not emit events.
+use std::{collections::HashSet, iter::FusedIterator};
 
 use near_sdk::{
-    borsh::BorshSerialize, collections::UnorderedSet, env, require, AccountId, BorshStorageKey,
-    IntoStorageKey,
+    borsh::BorshSerialize,
+    collections::{UnorderedSet, Vector},
+    env, require, AccountId, BorshStorageKey, IntoStorageKey,
 };
+use near_sdk_contract_tools_macros::event;
+use thiserror::Error;
 
-use crate::{slot::Slot, DefaultStorageKey};
+use crate::{slot::Slot, standard::nep297::Event, DefaultStorageKey};
 
 const REQUIRE_ROLE_FAIL_MESSAGE: &str = "Unauthorized role";
 const PROHIBIT_ROLE_FAIL_MESSAGE: &str = "Prohibited role";
 
+/// Events emitted by [`RbacExclusive::add_role_exclusive`].
+#[event(
+    standard = "x-rbac",
+    version = "1.0.0",
+    crate = "crate",
+    macros = "near_sdk_contract_tools_macros"
+)]
+#[derive(Debug, Clone)]
+pub enum RbacEvent {
+    /// Emitted when an account is assigned a role.
+    RoleGranted {
+        /// The account that was assigned the role.
+        account_id: AccountId,
+        /// Name of the role ([`NamedRole::role_name`]).
+        role: String,
+    },
+    /// Emitted when a role is removed from an account.
+    RoleRevoked {
+        /// The account that lost the role.
+        account_id: AccountId,
+        /// Name of the role ([`NamedRole::role_name`]).
+        role: String,
+    },
+}
+
 #[derive(BorshSerialize, BorshStorageKey)]
 #[borsh(crate = "near_sdk::borsh")]
 enum StorageKey<R> {
     Role(R),
+    OrderedRole(R),
 }
 
 /// Internal functions for [`Rbac`]. Using these methods may result in unexpected behavior.
@@ -59,6 +120,32 @@ pub trait RbacInternal {
     fn slot_members_of(role: &Self::Role) -> Slot<UnorderedSet<AccountId>> {
         Self::root().field::<UnorderedSet<AccountId>>(StorageKey::Role(role))
     }
+
+    /// Whether to maintain an insertion-ordered list of the members of each
+    /// role in [`RbacInternal::slot_ordered_members_of`], so that
+    /// [`Rbac::iter_members_of`] returns members in a stable order.
+    #[must_use]
+    fn ordered_members() -> bool {
+        false
+    }
+
+    /// Storage slot for the backing `Vector` of all accounts assigned to a
+    /// role, in the order in which they were assigned. Only maintained if
+    /// [`RbacInternal::ordered_members`] is `true`.
+    fn slot_ordered_members_of(role: &Self::Role) -> Slot<Vector<AccountId>> {
+        Self::root().field::<Vector<AccountId>>(StorageKey::OrderedRole(role))
+    }
+}
+
+fn with_ordered_members_of_mut<I: RbacInternal, T>(
+    role: &I::Role,
+    f: impl FnOnce(&mut Vector<AccountId>) -> T,
+) -> T {
+    let mut slot = I::slot_ordered_members_of(role);
+    let mut vector = slot.read().unwrap_or_else(|| Vector::new(slot.key.clone()));
+    let value = f(&mut vector);
+    slot.write(&vector);
+    value
 }
 
 /// Role-based access control
@@ -78,9 +165,21 @@ pub trait Rbac {
     /// `f` on it. Returns the return value of `f`.
     fn with_members_of<T>(role: &Self::Role, f: impl FnOnce(&UnorderedSet<AccountId>) -> T) -> T;
 
-    /// Iterates over all accounts that have been assigned a role.
+    /// Iterates over all accounts that have been assigned a role. See the
+    /// [module-level documentation](self#member-ordering) for ordering.
     fn iter_members_of(role: &Self::Role) -> Iter;
 
+    /// Iterates over all accounts that have been assigned at least one of
+    /// `roles`, without duplicates. Accounts are returned in the order they
+    /// are first encountered when iterating the members of each role in
+    /// turn (see [`Rbac::iter_members_of`]).
+    fn iter_members_of_any(roles: &[Self::Role]) -> std::vec::IntoIter<AccountId>;
+
+    /// Iterates over all accounts that have been assigned every one of
+    /// `roles`, in the order of the members of the first role (see
+    /// [`Rbac::iter_members_of`]). Empty if `roles` is empty.
+    fn iter_members_of_all(roles: &[Self::Role]) -> std::vec::IntoIter<AccountId>;
+
     /// Returns whether a given account has been given a certain role.
     fn has_role(account_id: &AccountId, role: &Self::Role) -> bool;
 
@@ -90,6 +189,16 @@ pub trait Rbac {
     /// Removes a role from an account.
     fn remove_role(&mut self, account_id: &AccountId, role: &Self::Role);
 
+    /// Removes a role from every account.
+    fn clear_role(&mut self, role: &Self::Role);
+
+    /// Removes a role from every account, and removes the storage of the
+    /// role's (now empty) backing collections, leaving no keys behind.
+    /// Costs gas proportional to the number of members of the role: very
+    /// large roles should first be shrunk across multiple transactions
+    /// using [`Rbac::remove_role`].
+    fn reset_role(&mut self, role: &Self::Role);
+
     /// Requires transaction predecessor to have a given role.
     fn require_role(role: &Self::Role);
 
@@ -124,10 +233,42 @@ impl<I: RbacInternal> Rbac for I {
     fn iter_members_of(role: &Self::Role) -> Iter {
         let slot = Self::slot_members_of(role);
         let set = slot.read().unwrap_or_else(|| UnorderedSet::new(slot.key));
+
+        if Self::ordered_members() {
+            let slot = Self::slot_ordered_members_of(role);
+            let vector = slot.read().unwrap_or_else(|| Vector::new(slot.key));
+            // Members assigned before ordering was enabled are missing from
+            // the ordered list.
+            if vector.len() == set.len() {
+                return Iter::new_ordered(vector);
+            }
+        }
+
         // Cannot use with_members_of because Iter must be owned
         Iter::new(set)
     }
 
+    fn iter_members_of_any(roles: &[Self::Role]) -> std::vec::IntoIter<AccountId> {
+        let mut seen = HashSet::new();
+        roles
+            .iter()
+            .flat_map(Self::iter_members_of)
+            .filter(|account_id| seen.insert(account_id.clone()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn iter_members_of_all(roles: &[Self::Role]) -> std::vec::IntoIter<AccountId> {
+        let Some((first, rest)) = roles.split_first() else {
+            return Vec::new().into_iter();
+        };
+
+        Self::iter_members_of(first)
+            .filter(|account_id| rest.iter().all(|role| Self::has_role(account_id, role)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     fn has_role(account_id: &AccountId, role: &Self::Role) -> bool {
         Self::slot_members_of(role)
             .read()
@@ -135,11 +276,52 @@ impl<I: RbacInternal> Rbac for I {
     }
 
     fn add_role(&mut self, account_id: &AccountId, role: &Self::Role) {
-        Self::with_members_of_mut(role, |set| set.insert(account_id));
+        let inserted = Self::with_members_of_mut(role, |set| set.insert(account_id));
+
+        if inserted && Self::ordered_members() {
+            with_ordered_members_of_mut::<Self, _>(role, |vector| vector.push(account_id));
+        }
     }
 
     fn remove_role(&mut self, account_id: &AccountId, role: &Self::Role) {
-        Self::with_members_of_mut(role, |set| set.remove(account_id));
+        let removed = Self::with_members_of_mut(role, |set| set.remove(account_id));
+
+        if removed && Self::ordered_members() {
+            with_ordered_members_of_mut::<Self, _>(role, |vector| {
+                let Some(index) = vector.iter().position(|member| &member == account_id) else {
+                    return;
+                };
+
+                // Shift subsequent members down to preserve their order.
+                for i in index as u64..vector.len() - 1 {
+                    let next = vector.get(i + 1).unwrap();
+                    vector.replace(i, &next);
+                }
+                vector.pop();
+            });
+        }
+    }
+
+    fn clear_role(&mut self, role: &Self::Role) {
+        Self::with_members_of_mut(role, UnorderedSet::clear);
+
+        if Self::ordered_members() {
+            with_ordered_members_of_mut::<Self, _>(role, Vector::clear);
+        }
+    }
+
+    fn reset_role(&mut self, role: &Self::Role) {
+        let mut slot = Self::slot_members_of(role);
+        if let Some(mut set) = slot.read() {
+            set.clear();
+        }
+        slot.remove();
+
+        let mut slot = Self::slot_ordered_members_of(role);
+        if let Some(mut vector) = slot.read() {
+            vector.clear();
+        }
+        slot.remove();
     }
 
     fn require_role(role: &Self::Role) {
@@ -159,9 +341,196 @@ impl<I: RbacInternal> Rbac for I {
     }
 }
 
+/// A role type whose values can be listed and referred to by name.
+pub trait NamedRole: Sized {
+    /// All roles, in a stable order.
+    fn all_roles() -> Vec<Self>;
+
+    /// The name of this role.
+    fn role_name(&self) -> String;
+
+    /// Looks up a role by name.
+    fn from_role_name(name: &str) -> Option<Self> {
+        Self::all_roles()
+            .into_iter()
+            .find(|role| role.role_name() == name)
+    }
+}
+
+/// A role name that does not correspond to any role.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error("Unknown role: `{0}`")]
+pub struct UnknownRoleError(pub String);
+
+/// Export and import of the complete set of role assignments.
+pub trait RbacSnapshot: Rbac {
+    /// Exports the members of every role, in the order given by
+    /// [`NamedRole::all_roles`]. Roles without members are omitted.
+    fn export_roles() -> Vec<(String, Vec<AccountId>)>;
+
+    /// Exports at most `limit` role assignments, starting at index `from`
+    /// of the sequence of all assignments (every member of the first role,
+    /// then every member of the second, and so on).
+    fn export_roles_page(from: usize, limit: usize) -> Vec<(String, Vec<AccountId>)>;
+
+    /// Assigns all of the roles in `roles`. Existing assignments are kept.
+    ///
+    /// # Errors
+    ///
+    /// - If any role name is unknown. No roles are assigned in that case.
+    fn import_roles(&mut self, roles: &[(String, Vec<AccountId>)]) -> Result<(), UnknownRoleError>;
+
+    /// Removes every account from every role.
+    fn clear_roles(&mut self);
+
+    /// Removes all storage used by RBAC, e.g. to decommission the component
+    /// before repurposing state in an upgrade. Resets every role using
+    /// [`Rbac::reset_role`]; storage of other components is not affected.
+    /// Costs gas proportional to the total number of role assignments.
+    fn reset(&mut self);
+}
+
+impl<T: Rbac> RbacSnapshot for T
+where
+    T::Role: NamedRole,
+{
+    fn export_roles() -> Vec<(String, Vec<AccountId>)> {
+        Self::export_roles_page(0, usize::MAX)
+    }
+
+    fn export_roles_page(from: usize, limit: usize) -> Vec<(String, Vec<AccountId>)> {
+        let mut skip = from;
+        let mut remaining = limit;
+        let mut page = vec![];
+
+        for role in T::Role::all_roles() {
+            if remaining == 0 {
+                break;
+            }
+
+            let members = Self::iter_members_of(&role);
+            let len = members.len();
+            let start = skip.min(len);
+            skip -= start;
+            let end = len.min(start.saturating_add(remaining));
+            remaining -= end - start;
+
+            let members = members.skip(start).take(end - start).collect::<Vec<_>>();
+
+            if !members.is_empty() {
+                page.push((role.role_name(), members));
+            }
+        }
+
+        page
+    }
+
+    fn import_roles(&mut self, roles: &[(String, Vec<AccountId>)]) -> Result<(), UnknownRoleError> {
+        let roles = roles
+            .iter()
+            .map(|(name, members)| {
+                T::Role::from_role_name(name)
+                    .map(|role| (role, members))
+                    .ok_or_else(|| UnknownRoleError(name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (role, members) in roles {
+            for account_id in members {
+                self.add_role(account_id, &role);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear_roles(&mut self) {
+        for role in T::Role::all_roles() {
+            self.clear_role(&role);
+        }
+    }
+
+    fn reset(&mut self) {
+        for role in T::Role::all_roles() {
+            self.reset_role(&role);
+        }
+    }
+}
+
+/// Assignment of mutually exclusive roles.
+pub trait RbacExclusive: Rbac {
+    /// Removes `account_id` from every role in `exclusive_group` other than
+    /// `role`, then assigns `role` to it. Emits [`RbacEvent::RoleRevoked`]
+    /// for each role removed and [`RbacEvent::RoleGranted`] if the account
+    /// did not already have `role`. `role` need not be a member of
+    /// `exclusive_group`. Roles are compared by [`NamedRole::role_name`].
+    fn add_role_exclusive(
+        &mut self,
+        account_id: &AccountId,
+        role: &Self::Role,
+        exclusive_group: &[Self::Role],
+    );
+}
+
+impl<T: Rbac> RbacExclusive for T
+where
+    T::Role: NamedRole,
+{
+    fn add_role_exclusive(
+        &mut self,
+        account_id: &AccountId,
+        role: &Self::Role,
+        exclusive_group: &[Self::Role],
+    ) {
+        let role_name = role.role_name();
+
+        for other in exclusive_group {
+            let other_name = other.role_name();
+            if other_name != role_name && Self::has_role(account_id, other) {
+                self.remove_role(account_id, other);
+                RbacEvent::RoleRevoked {
+                    account_id: account_id.clone(),
+                    role: other_name,
+                }
+                .emit();
+            }
+        }
+
+        if !Self::has_role(account_id, role) {
+            self.add_role(account_id, role);
+            RbacEvent::RoleGranted {
+                account_id: account_id.clone(),
+                role: role_name,
+            }
+            .emit();
+        }
+    }
+}
+
+enum Members {
+    Unordered(UnorderedSet<AccountId>),
+    Ordered(Vector<AccountId>),
+}
+
+impl Members {
+    fn len(&self) -> u64 {
+        match self {
+            Self::Unordered(set) => set.len(),
+            Self::Ordered(vector) => vector.len(),
+        }
+    }
+
+    fn get(&self, index: u64) -> Option<AccountId> {
+        match self {
+            Self::Unordered(set) => set.as_vector().get(index),
+            Self::Ordered(vector) => vector.get(index),
+        }
+    }
+}
+
 /// An iterator for `AccountId`s.
 pub struct Iter {
-    inner_collection: UnorderedSet<AccountId>,
+    inner_collection: Members,
     index: usize,
 }
 
@@ -170,7 +539,16 @@ impl Iter {
     #[must_use]
     pub fn new(s: UnorderedSet<AccountId>) -> Self {
         Self {
-            inner_collection: s,
+            inner_collection: Members::Unordered(s),
+            index: 0,
+        }
+    }
+
+    /// Creates a new iterator from an insertion-ordered `Vector`.
+    #[must_use]
+    pub fn new_ordered(v: Vector<AccountId>) -> Self {
+        Self {
+            inner_collection: Members::Ordered(v),
             index: 0,
         }
     }
@@ -184,7 +562,7 @@ impl Iterator for Iter {
     type Item = AccountId;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let value = self.inner_collection.iter().nth(self.index);
+        let value = self.inner_collection.get(self.index as u64);
         if value.is_some() {
             self.index += 1;
         }
@@ -217,11 +595,14 @@ impl ExactSizeIterator for Iter {}
 #[cfg(test)]
 mod tests {
     use near_sdk::{
-        near, test_utils::VMContextBuilder, testing_env, AccountId, BorshStorageKey, PanicOnDefault,
+        near,
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env, AccountId, BorshStorageKey, PanicOnDefault,
     };
     use near_sdk_contract_tools_macros::Rbac;
 
-    use super::Rbac;
+    use super::{NamedRole, Rbac, RbacEvent, RbacExclusive, RbacSnapshot, UnknownRoleError};
+    use crate::standard::nep297::Event;
 
     #[derive(BorshStorageKey)]
     #[near]
@@ -230,6 +611,20 @@ mod tests {
         B,
     }
 
+    impl NamedRole for Role {
+        fn all_roles() -> Vec<Self> {
+            vec![Role::A, Role::B]
+        }
+
+        fn role_name(&self) -> String {
+            match self {
+                Role::A => "A",
+                Role::B => "B",
+            }
+            .to_string()
+        }
+    }
+
     #[derive(Rbac, PanicOnDefault)]
     #[rbac(roles = "Role", crate = "crate")]
     #[near(contract_state)]
@@ -364,4 +759,309 @@ mod tests {
 
         Contract::prohibit_role(&Role::B);
     }
+
+    #[test]
+    pub fn export_clear_import() {
+        let mut r = Contract {};
+        let accounts: Vec<AccountId> = ["alice", "bob", "charlie", "dave"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+
+        r.add_role(&accounts[0], &Role::A);
+        r.add_role(&accounts[1], &Role::A);
+        r.add_role(&accounts[1], &Role::B);
+        r.add_role(&accounts[2], &Role::B);
+
+        let snapshot = |a: &AccountId| {
+            (
+                Contract::has_role(a, &Role::A),
+                Contract::has_role(a, &Role::B),
+            )
+        };
+        let original = accounts.iter().map(snapshot).collect::<Vec<_>>();
+
+        let exported = Contract::export_roles();
+        assert_eq!(
+            exported,
+            vec![
+                (
+                    "A".to_string(),
+                    vec![accounts[0].clone(), accounts[1].clone()]
+                ),
+                (
+                    "B".to_string(),
+                    vec![accounts[1].clone(), accounts[2].clone()]
+                ),
+            ],
+        );
+
+        r.clear_roles();
+        assert!(accounts.iter().map(snapshot).all(|(a, b)| !a && !b));
+        assert!(Contract::export_roles().is_empty());
+
+        r.import_roles(&exported).unwrap();
+        assert_eq!(accounts.iter().map(snapshot).collect::<Vec<_>>(), original);
+    }
+
+    #[test]
+    pub fn export_page() {
+        let mut r = Contract {};
+        let a: AccountId = "account_a".parse().unwrap();
+        let b: AccountId = "account_b".parse().unwrap();
+        let c: AccountId = "account_c".parse().unwrap();
+
+        r.add_role(&a, &Role::A);
+        r.add_role(&b, &Role::A);
+        r.add_role(&c, &Role::B);
+
+        assert_eq!(
+            Contract::export_roles_page(1, 2),
+            vec![
+                ("A".to_string(), vec![b.clone()]),
+                ("B".to_string(), vec![c.clone()]),
+            ],
+        );
+        assert_eq!(
+            Contract::export_roles_page(0, 1),
+            vec![("A".to_string(), vec![a])],
+        );
+        assert_eq!(
+            Contract::export_roles_page(2, 10),
+            vec![("B".to_string(), vec![c])],
+        );
+        assert!(Contract::export_roles_page(3, 10).is_empty());
+    }
+
+    #[test]
+    pub fn import_unknown_role() {
+        let mut r = Contract {};
+        let a: AccountId = "account".parse().unwrap();
+
+        assert_eq!(
+            r.import_roles(&[
+                ("A".to_string(), vec![a.clone()]),
+                ("C".to_string(), vec![a.clone()]),
+            ]),
+            Err(UnknownRoleError("C".to_string())),
+        );
+        assert!(!Contract::has_role(&a, &Role::A));
+    }
+
+    #[derive(Rbac, PanicOnDefault)]
+    #[rbac(roles = "Role", ordered, crate = "crate")]
+    #[near(contract_state)]
+    struct OrderedContract {}
+
+    #[test]
+    pub fn members_of_any_and_all() {
+        let mut r = OrderedContract {};
+        let [a, b, c, d]: [AccountId; 4] =
+            ["a", "b", "c", "d"].map(|account_id| account_id.parse().unwrap());
+
+        r.add_role(&a, &Role::A);
+        r.add_role(&b, &Role::A);
+        r.add_role(&c, &Role::A);
+        r.add_role(&d, &Role::B);
+        r.add_role(&c, &Role::B);
+        r.add_role(&a, &Role::B);
+
+        assert_eq!(
+            OrderedContract::iter_members_of_any(&[Role::A, Role::B]).collect::<Vec<_>>(),
+            vec![a.clone(), b.clone(), c.clone(), d.clone()],
+        );
+        assert_eq!(
+            OrderedContract::iter_members_of_any(&[Role::B, Role::A]).collect::<Vec<_>>(),
+            vec![d.clone(), c.clone(), a.clone(), b.clone()],
+        );
+        assert_eq!(
+            OrderedContract::iter_members_of_all(&[Role::A, Role::B]).collect::<Vec<_>>(),
+            vec![a.clone(), c.clone()],
+        );
+        assert_eq!(
+            OrderedContract::iter_members_of_all(&[Role::B, Role::A]).collect::<Vec<_>>(),
+            vec![c.clone(), a.clone()],
+        );
+        assert_eq!(OrderedContract::iter_members_of_any(&[]).count(), 0);
+        assert_eq!(OrderedContract::iter_members_of_all(&[]).count(), 0);
+    }
+
+    #[test]
+    pub fn ordered_members_stable_across_removal() {
+        let mut r = OrderedContract {};
+        let accounts: Vec<AccountId> = ["e", "d", "c", "b", "a"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+
+        for account_id in &accounts {
+            r.add_role(account_id, &Role::A);
+        }
+        r.add_role(&accounts[0], &Role::A);
+
+        assert_eq!(
+            OrderedContract::iter_members_of(&Role::A).collect::<Vec<_>>(),
+            accounts,
+        );
+
+        r.remove_role(&accounts[1], &Role::A);
+
+        let expected = [&accounts[0], &accounts[2], &accounts[3], &accounts[4]]
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            OrderedContract::iter_members_of(&Role::A).collect::<Vec<_>>(),
+            expected,
+        );
+        assert_eq!(
+            OrderedContract::export_roles_page(1, 2),
+            vec![("A".to_string(), expected[1..3].to_vec())],
+        );
+
+        r.add_role(&accounts[1], &Role::A);
+
+        assert_eq!(
+            OrderedContract::iter_members_of(&Role::A).last(),
+            Some(accounts[1].clone()),
+        );
+
+        r.clear_role(&Role::A);
+
+        assert_eq!(OrderedContract::iter_members_of(&Role::A).count(), 0);
+        assert!(!OrderedContract::has_role(&accounts[0], &Role::A));
+    }
+
+    #[test]
+    pub fn ordered_members_fall_back_to_unordered() {
+        let mut r = OrderedContract {};
+        let a: AccountId = "a".parse().unwrap();
+        let b: AccountId = "b".parse().unwrap();
+
+        // Assigned before ordering was enabled, so not in the ordered list.
+        OrderedContract::with_members_of_mut(&Role::A, |set| set.insert(&a));
+        r.add_role(&b, &Role::A);
+
+        let mut members = OrderedContract::iter_members_of(&Role::A).collect::<Vec<_>>();
+        members.sort();
+        assert_eq!(members, vec![a.clone(), b.clone()]);
+        assert_eq!(OrderedContract::iter_members_of_all(&[Role::A]).count(), 2);
+
+        r.remove_role(&b, &Role::A);
+
+        assert_eq!(
+            OrderedContract::iter_members_of(&Role::A).collect::<Vec<_>>(),
+            vec![a],
+        );
+    }
+
+    #[derive(BorshStorageKey, Clone, Copy, Debug)]
+    #[near]
+    enum Tier {
+        Bronze,
+        Silver,
+        Gold,
+    }
+
+    impl Tier {
+        const ALL: [Tier; 3] = [Tier::Bronze, Tier::Silver, Tier::Gold];
+    }
+
+    impl NamedRole for Tier {
+        fn all_roles() -> Vec<Self> {
+            Self::ALL.to_vec()
+        }
+
+        fn role_name(&self) -> String {
+            format!("{self:?}")
+        }
+    }
+
+    #[derive(Rbac, PanicOnDefault)]
+    #[rbac(roles = "Tier", crate = "crate")]
+    #[near(contract_state)]
+    struct TierContract {}
+
+    #[test]
+    fn add_role_exclusive_replaces_tier() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut r = TierContract {};
+        let a: AccountId = "account".parse().unwrap();
+
+        r.add_role(&a, &Tier::Silver);
+        r.add_role_exclusive(&a, &Tier::Gold, &Tier::ALL);
+
+        assert!(TierContract::has_role(&a, &Tier::Gold));
+        assert!(!TierContract::has_role(&a, &Tier::Silver));
+        assert!(!TierContract::has_role(&a, &Tier::Bronze));
+        assert_eq!(
+            get_logs(),
+            vec![
+                RbacEvent::RoleRevoked {
+                    account_id: a.clone(),
+                    role: "Silver".to_string(),
+                }
+                .to_event_string(),
+                RbacEvent::RoleGranted {
+                    account_id: a.clone(),
+                    role: "Gold".to_string(),
+                }
+                .to_event_string(),
+            ],
+        );
+
+        // Granting a held role again is a no-op.
+        r.add_role_exclusive(&a, &Tier::Gold, &Tier::ALL);
+        assert_eq!(get_logs().len(), 2);
+    }
+
+    #[derive(Rbac, PanicOnDefault)]
+    #[rbac(roles = "Role", crate = "crate", storage_key = "b\"o\"", ordered)]
+    #[near(contract_state)]
+    struct SiblingOrderedContract {}
+
+    #[test]
+    fn reset_removes_all_storage() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let mut sibling = crate::slot::Slot::<u32>::new(b"sibling".to_vec());
+        sibling.write(&7);
+
+        let usage_before = near_sdk::env::storage_usage();
+
+        let mut r = Contract {};
+        let mut o = SiblingOrderedContract {};
+        let accounts = ["a", "b", "c", "d"]
+            .iter()
+            .map(|a| a.parse::<AccountId>().unwrap())
+            .collect::<Vec<_>>();
+
+        for account_id in &accounts {
+            r.add_role(account_id, &Role::A);
+            o.add_role(account_id, &Role::B);
+        }
+        r.add_role(&accounts[0], &Role::B);
+
+        assert!(near_sdk::env::storage_usage() > usage_before);
+
+        r.reset();
+
+        for account_id in &accounts {
+            assert!(!Contract::has_role(account_id, &Role::A));
+            assert!(!Contract::has_role(account_id, &Role::B));
+            assert!(SiblingOrderedContract::has_role(account_id, &Role::B));
+        }
+
+        o.reset();
+
+        assert_eq!(near_sdk::env::storage_usage(), usage_before);
+        assert_eq!(Contract::export_roles(), vec![]);
+        assert_eq!(SiblingOrderedContract::export_roles(), vec![]);
+        assert_eq!(sibling.read(), Some(7));
+
+        // The component is usable again after a reset.
+        r.add_role(&accounts[0], &Role::A);
+        assert!(Contract::has_role(&accounts[0], &Role::A));
+    }
 }
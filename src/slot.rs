@@ -11,6 +11,22 @@ use near_sdk::{
 
 use crate::utils::prefix_key;
 
+/// Length, in bytes, of the digest suffix used by [`Slot::hashed_field`].
+pub const HASHED_KEY_LEN: usize = 16;
+
+/// Controls how [`Slot::field_with`] derives a field's storage key from its
+/// key material.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyHashing {
+    /// Key material is appended to the parent key as-is.
+    #[default]
+    None,
+    /// Key material longer than the given number of bytes is hashed.
+    LongerThan(usize),
+    /// Key material is always hashed.
+    Always,
+}
+
 #[derive(Clone, Debug)]
 #[near]
 /// A storage slot, composed of a storage location (key) and a data type
@@ -29,6 +45,13 @@ impl Slot<()> {
             _marker: PhantomData,
         }
     }
+
+    /// A placeholder slot rooted in the namespace reserved for user-defined
+    /// components. See [`DefaultStorageKey::Custom`](crate::DefaultStorageKey::Custom).
+    #[must_use]
+    pub fn custom_root(tag: &'static [u8]) -> Self {
+        Self::root(crate::DefaultStorageKey::Custom(tag))
+    }
 }
 
 impl<T> Slot<T> {
@@ -58,6 +81,33 @@ impl<T> Slot<T> {
         }
     }
 
+    /// Creates a new [`Slot`] whose key is the parent key followed by the
+    /// first [`HASHED_KEY_LEN`] bytes of the SHA-256 digest of the parent key
+    /// concatenated with the key material. Because the digest input includes
+    /// the parent key, identical key material under different parents does
+    /// not collide, and the resulting key is always a fixed length.
+    pub fn hashed_field<U>(&self, key: impl IntoStorageKey) -> Slot<U> {
+        let digest = env::sha256(&prefix_key(&self.key, &key.into_storage_key()));
+        Slot {
+            key: prefix_key(&self.key, &digest[..HASHED_KEY_LEN]),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new [`Slot`] for the given key material, hashed according
+    /// to `hashing`. See [`Slot::field`] and [`Slot::hashed_field`].
+    ///
+    /// Changing the hashing mode of an existing field moves its storage
+    /// location, so previously-written data will no longer be found.
+    pub fn field_with<U>(&self, key: impl IntoStorageKey, hashing: KeyHashing) -> Slot<U> {
+        let key = key.into_storage_key();
+        match hashing {
+            KeyHashing::LongerThan(len) if key.len() > len => self.hashed_field(key),
+            KeyHashing::Always => self.hashed_field(key),
+            _ => self.field(key),
+        }
+    }
+
     /// Creates a [`Slot`] that tries to parse a different data type from the same
     /// storage slot.
     ///
@@ -190,7 +240,7 @@ impl<T, U> PartialEq<Slot<U>> for Slot<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::Slot;
+    use super::{borsh, KeyHashing, Slot, HASHED_KEY_LEN};
 
     #[test]
     fn partialeq() {
@@ -200,4 +250,72 @@ mod tests {
         let b = Slot::<u32>::new(b"b");
         assert_ne!(a1, b);
     }
+
+    #[test]
+    fn hashed_field_distinct_fixed_length() {
+        let root = Slot::root(b"r");
+        let long: near_sdk::AccountId = "a".repeat(64).parse().unwrap();
+        let alice: near_sdk::AccountId = "alice.near".parse().unwrap();
+
+        let a = root.hashed_field::<u128>(borsh::to_vec(&long).unwrap());
+        let b = root.hashed_field::<u128>(borsh::to_vec(&alice).unwrap());
+
+        assert_ne!(a.key, b.key);
+        assert_eq!(a.key.len(), 1 + HASHED_KEY_LEN);
+        assert_eq!(b.key.len(), 1 + HASHED_KEY_LEN);
+        assert!(a.key.starts_with(b"r"));
+
+        // Same material under a different root hashes differently.
+        let other = Slot::root(b"s").hashed_field::<u128>(borsh::to_vec(&alice).unwrap());
+        assert_ne!(b.key[1..], other.key[1..]);
+    }
+
+    #[test]
+    fn field_with_threshold() {
+        let root = Slot::root(b"r");
+
+        let short = root.field_with::<()>(b"short".to_vec(), KeyHashing::LongerThan(8));
+        assert_eq!(short, root.field(b"short".to_vec()));
+
+        let long = root.field_with::<()>(b"much longer key".to_vec(), KeyHashing::LongerThan(8));
+        assert_eq!(long, root.hashed_field(b"much longer key".to_vec()));
+
+        let none = root.field_with::<()>(b"much longer key".to_vec(), KeyHashing::None);
+        assert_eq!(none, root.field(b"much longer key".to_vec()));
+    }
+
+    #[test]
+    fn custom_root_does_not_alias_builtin_roots() {
+        use crate::DefaultStorageKey;
+
+        let builtin = [
+            DefaultStorageKey::ApprovalManager,
+            DefaultStorageKey::Nep141,
+            DefaultStorageKey::Nep145,
+            DefaultStorageKey::Nep148,
+            DefaultStorageKey::Nep171,
+            DefaultStorageKey::Nep177,
+            DefaultStorageKey::Nep178,
+            DefaultStorageKey::Nep181,
+            DefaultStorageKey::Owner,
+            DefaultStorageKey::Pause,
+            DefaultStorageKey::Rbac,
+            DefaultStorageKey::Escrow,
+        ];
+
+        for tag in [&b""[..], b"o", b"$141", b"~am", b"my_component"] {
+            let custom = Slot::custom_root(tag);
+            assert_eq!(custom, Slot::root(DefaultStorageKey::Custom(tag)));
+
+            for key in builtin.clone() {
+                let root = Slot::root(key);
+                assert!(
+                    !custom.key.starts_with(&root.key) && !root.key.starts_with(&custom.key),
+                    "{:?} aliases {:?}",
+                    String::from_utf8_lossy(&custom.key),
+                    String::from_utf8_lossy(&root.key),
+                );
+            }
+        }
+    }
 }
@@ -78,4 +78,25 @@ pub enum TransferError {
     /// The balance of the sender is insufficient.
     #[error("Balance of the sender is insufficient: {0}")]
     SenderBalanceUnderflow(#[from] BalanceUnderflowError),
+    /// The sender and the receiver are the same account.
+    #[error(transparent)]
+    SelfTransfer(#[from] SelfTransferError),
+}
+
+/// An account attempted to transfer tokens to itself.
+#[derive(Debug, Error)]
+#[error("The account {account_id} cannot transfer tokens to itself.")]
+pub struct SelfTransferError {
+    /// The account ID.
+    pub account_id: AccountId,
+}
+
+/// Too many accounts were requested in a single batch balance query.
+#[derive(Debug, Error)]
+#[error("Cannot query {len} balances at once (maximum: {max_len}).")]
+pub struct BalanceBatchTooLongError {
+    /// The number of accounts requested.
+    pub len: usize,
+    /// The maximum number of accounts per query.
+    pub max_len: usize,
 }
@@ -4,6 +4,7 @@ use std::borrow::Cow;
 
 use near_sdk::{
     json_types::U128,
+    near,
     serde::{Deserialize, Serialize},
     AccountIdRef,
 };
@@ -32,6 +33,25 @@ pub enum Nep141Event<'a> {
     FtBurn(Vec<FtBurnData<'a>>),
 }
 
+/// Structured category for a change in total supply, included in
+/// [`FtMintData`] and [`FtBurnData`] so that indexers can aggregate mints and
+/// burns without parsing the free-text memo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+#[serde(rename_all = "snake_case")]
+pub enum SupplyChangeReason {
+    /// Tokens issued as a reward, e.g. staking or liquidity incentives.
+    Reward,
+    /// Tokens issued in exchange for a payment.
+    Purchase,
+    /// Tokens removed as a penalty.
+    Slashing,
+    /// Tokens moved to or from another chain.
+    Bridge,
+    /// Any other, application-defined reason.
+    Other(String),
+}
+
 /// Individual mint metadata
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(crate = "near_sdk::serde")]
@@ -43,6 +63,9 @@ pub struct FtMintData<'a> {
     /// Optional note
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<Cow<'a, str>>,
+    /// Optional category describing why the tokens were minted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<SupplyChangeReason>,
 }
 
 /// Individual transfer metadata
@@ -71,6 +94,9 @@ pub struct FtBurnData<'a> {
     /// Optional note
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<Cow<'a, str>>,
+    /// Optional category describing why the tokens were burned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<SupplyChangeReason>,
 }
 
 #[cfg(test)]
@@ -85,12 +111,37 @@ mod tests {
                 owner_id: AccountIdRef::new_or_panic("foundation.near").into(),
                 amount: 500u128.into(),
                 memo: None,
+                reason: None,
             }])
             .to_event_string(),
             r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[{"owner_id":"foundation.near","amount":"500"}]}"#,
         );
     }
 
+    #[test]
+    fn mint_emit_matches_event_string() {
+        near_sdk::testing_env!(near_sdk::test_utils::VMContextBuilder::new().build());
+
+        let event = Nep141Event::FtMint(vec![FtMintData {
+            owner_id: AccountIdRef::new_or_panic("foundation.near").into(),
+            amount: 500u128.into(),
+            memo: Some("Initial supply".into()),
+            reason: None,
+        }]);
+
+        assert_eq!(
+            event.to_event_string(),
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[{"owner_id":"foundation.near","amount":"500","memo":"Initial supply"}]}"#,
+        );
+
+        event.emit();
+
+        assert_eq!(
+            near_sdk::test_utils::get_logs(),
+            vec![event.to_event_string()],
+        );
+    }
+
     #[test]
     fn transfer() {
         assert_eq!(
@@ -120,9 +171,34 @@ mod tests {
                 owner_id: AccountIdRef::new_or_panic("foundation.near").into(),
                 amount: 100u128.into(),
                 memo: None,
+                reason: None,
             }])
             .to_event_string(),
             r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_burn","data":[{"owner_id":"foundation.near","amount":"100"}]}"#,
         );
     }
+
+    #[test]
+    fn reason() {
+        assert_eq!(
+            Nep141Event::FtMint(vec![FtMintData {
+                owner_id: AccountIdRef::new_or_panic("foundation.near").into(),
+                amount: 500u128.into(),
+                memo: None,
+                reason: Some(SupplyChangeReason::Reward),
+            }])
+            .to_event_string(),
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[{"owner_id":"foundation.near","amount":"500","reason":"reward"}]}"#,
+        );
+        assert_eq!(
+            Nep141Event::FtBurn(vec![FtBurnData {
+                owner_id: AccountIdRef::new_or_panic("foundation.near").into(),
+                amount: 100u128.into(),
+                memo: Some("bridge out".into()),
+                reason: Some(SupplyChangeReason::Other("migration".into())),
+            }])
+            .to_event_string(),
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_burn","data":[{"owner_id":"foundation.near","amount":"100","memo":"bridge out","reason":{"other":"migration"}}]}"#,
+        );
+    }
 }
@@ -1,6 +1,11 @@
 //! Hooks to integrate NEP-141 with other standards.
 
-use crate::{hook::Hook, standard::nep145::Nep145ForceUnregister};
+use near_sdk::env;
+
+use crate::{
+    hook::Hook,
+    standard::nep145::{Nep145Controller, Nep145ForceUnregister, Nep145Unregister},
+};
 
 use super::{Nep141Burn, Nep141Controller, Nep141ControllerInternal};
 
@@ -24,7 +29,7 @@ impl<C: Nep141Controller + Nep141ControllerInternal> Hook<C, Nep145ForceUnregist
                     .memo("storage forced unregistration"),
             )
             .unwrap_or_else(|e| {
-                near_sdk::env::panic_str(&format!(
+                env::panic_str(&format!(
                     "Failed to burn tokens during forced unregistration: {e}",
                 ))
             });
@@ -34,3 +39,30 @@ impl<C: Nep141Controller + Nep141ControllerInternal> Hook<C, Nep145ForceUnregist
         r
     }
 }
+
+/// Hook that rejects NEP-145 (non-forced) unregistration while the account
+/// holds a nonzero token balance. If the balance is zero, the account's
+/// balance record is removed, and the storage it used is released.
+pub struct RequireZeroNep141BalanceOnUnregisterHook;
+
+impl<C: Nep141Controller + Nep141ControllerInternal + Nep145Controller>
+    Hook<C, Nep145Unregister<'_>> for RequireZeroNep141BalanceOnUnregisterHook
+{
+    fn hook<R>(contract: &mut C, args: &Nep145Unregister<'_>, f: impl FnOnce(&mut C) -> R) -> R {
+        let balance = contract.balance_of(&args.account_id);
+        if balance != 0 {
+            env::panic_str(&format!(
+                "Account {} cannot unregister with a nonzero token balance: {balance}",
+                args.account_id,
+            ));
+        }
+
+        let storage_usage_start = env::storage_usage();
+        <C as Nep141ControllerInternal>::slot_account(&args.account_id).remove();
+        contract
+            .storage_accounting(&args.account_id, storage_usage_start)
+            .unwrap_or_else(|e| env::panic_str(&format!("Storage accounting error: {e}")));
+
+        f(contract)
+    }
+}
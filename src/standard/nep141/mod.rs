@@ -3,9 +3,14 @@
 
 use std::borrow::Cow;
 
-use near_sdk::{borsh::BorshSerialize, near, AccountIdRef, BorshStorageKey, Gas};
+use near_sdk::{borsh::BorshSerialize, near, AccountId, AccountIdRef, BorshStorageKey, Gas};
 
-use crate::{hook::Hook, slot::Slot, standard::nep297::*, DefaultStorageKey};
+use crate::{
+    hook::Hook,
+    slot::{KeyHashing, Slot},
+    standard::nep297::*,
+    DefaultStorageKey,
+};
 
 mod error;
 pub use error::*;
@@ -14,6 +19,10 @@ pub use event::*;
 mod ext;
 pub use ext::*;
 pub mod hooks;
+mod observer;
+pub use observer::*;
+mod register;
+pub use register::*;
 
 /// Gas value required for [`Nep141Resolver::ft_resolve_transfer`] call,
 /// independent of the amount of gas required for the preceding
@@ -23,8 +32,25 @@ pub const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas::from_gas(5_000_000_000_000);
 /// the subsequent [`Nep141Resolver::ft_resolve_transfer`] call).
 pub const GAS_FOR_FT_TRANSFER_CALL: Gas =
     Gas::from_gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.as_gas());
+/// Memo attached to the [`Nep141Event::FtTransfer`] event emitted when
+/// [`Nep141Resolver::ft_resolve_transfer`] returns unused tokens to the
+/// sender of an [`Nep141::ft_transfer_call`].
+///
+/// Every transfer with [`Nep141Transfer::revert`] set is labeled this way:
+/// its event memo is `"refund"`, or starts with `"refund: "` if the transfer
+/// carries another memo.
+pub const REFUND_MEMO: &str = "refund";
+/// Memo attached to the event emitted when
+/// [`Nep141Resolver::ft_resolve_transfer`] cannot return unused tokens to the
+/// sender because the sender is no longer registered, and the tokens are
+/// burned or sent to [`Nep141ControllerInternal::refund_fallback_account`]
+/// instead.
+pub const UNREFUNDABLE_MEMO: &str = "refund: sender not registered";
 /// Error message for insufficient gas.
 pub const MORE_GAS_FAIL_MESSAGE: &str = "Insufficient gas attached.";
+/// Default maximum number of accounts accepted by
+/// [`Nep141Controller::balance_of_batch`].
+pub const DEFAULT_MAX_BALANCE_BATCH_LEN: usize = 100;
 
 #[derive(BorshSerialize, BorshStorageKey)]
 #[borsh(crate = "near_sdk::borsh")]
@@ -49,6 +75,7 @@ pub struct Nep141Transfer<'a> {
     /// Message passed to contract located at `receiver_id`.
     pub msg: Option<Cow<'a, str>>,
     /// Is this transfer a revert as a result of a [`Nep141::ft_transfer_call`] -> [`Nep141Receiver::ft_on_transfer`] call?
+    /// If so, the memo of the emitted event is labeled with [`REFUND_MEMO`].
     pub revert: bool,
 }
 
@@ -69,6 +96,28 @@ impl<'a> Nep141Transfer<'a> {
         }
     }
 
+    /// The memo of the emitted [`Nep141Event::FtTransfer`] event. Reverts
+    /// are labeled with [`REFUND_MEMO`] unless the memo already is.
+    #[must_use]
+    pub fn event_memo(&self) -> Option<Cow<'a, str>> {
+        if !self.revert {
+            return self.memo.clone();
+        }
+
+        match self.memo.as_deref() {
+            None => Some(REFUND_MEMO.into()),
+            Some(memo)
+                if memo == REFUND_MEMO
+                    || memo
+                        .strip_prefix(REFUND_MEMO)
+                        .is_some_and(|rest| rest.starts_with(": ")) =>
+            {
+                self.memo.clone()
+            }
+            Some(memo) => Some(format!("{REFUND_MEMO}: {memo}").into()),
+        }
+    }
+
     /// Add a memo string.
     #[must_use]
     pub fn memo(self, memo: impl Into<Cow<'a, str>>) -> Self {
@@ -105,6 +154,8 @@ pub struct Nep141Mint<'a> {
     pub receiver_id: Cow<'a, AccountIdRef>,
     /// Optional memo string.
     pub memo: Option<Cow<'a, str>>,
+    /// Optional structured reason the tokens are minted.
+    pub reason: Option<SupplyChangeReason>,
 }
 
 impl<'a> Nep141Mint<'a> {
@@ -114,6 +165,7 @@ impl<'a> Nep141Mint<'a> {
             amount,
             receiver_id: receiver_id.into(),
             memo: None,
+            reason: None,
         }
     }
 
@@ -125,6 +177,15 @@ impl<'a> Nep141Mint<'a> {
             ..self
         }
     }
+
+    /// Add a structured reason, emitted alongside the memo.
+    #[must_use]
+    pub fn reason(self, reason: SupplyChangeReason) -> Self {
+        Self {
+            reason: Some(reason),
+            ..self
+        }
+    }
 }
 
 /// Describes a burn operation.
@@ -137,6 +198,8 @@ pub struct Nep141Burn<'a> {
     pub owner_id: Cow<'a, AccountIdRef>,
     /// Optional memo string.
     pub memo: Option<Cow<'a, str>>,
+    /// Optional structured reason the tokens are burned.
+    pub reason: Option<SupplyChangeReason>,
 }
 
 impl<'a> Nep141Burn<'a> {
@@ -146,6 +209,7 @@ impl<'a> Nep141Burn<'a> {
             amount,
             owner_id: owner_id.into(),
             memo: None,
+            reason: None,
         }
     }
 
@@ -157,6 +221,15 @@ impl<'a> Nep141Burn<'a> {
             ..self
         }
     }
+
+    /// Add a structured reason, emitted alongside the memo.
+    #[must_use]
+    pub fn reason(self, reason: SupplyChangeReason) -> Self {
+        Self {
+            reason: Some(reason),
+            ..self
+        }
+    }
 }
 
 /// Internal functions for [`Nep141Controller`]. Using these methods may result in unexpected behavior.
@@ -171,6 +244,10 @@ pub trait Nep141ControllerInternal {
         Self: Sized;
     /// Hook for burn operations.
     type BurnHook: for<'a> Hook<Self, Nep141Burn<'a>>
+    where
+        Self: Sized;
+    /// Observer notified of every balance change.
+    type Observer: Nep141Observer<Self>
     where
         Self: Sized;
 
@@ -180,10 +257,18 @@ pub trait Nep141ControllerInternal {
         Slot::new(DefaultStorageKey::Nep141)
     }
 
+    /// How per-account keys are derived. Hashing keeps keys short and
+    /// fixed-size regardless of account ID length. Changing this on a
+    /// deployed contract orphans existing balances.
+    #[must_use]
+    fn account_key_hashing() -> KeyHashing {
+        KeyHashing::None
+    }
+
     /// Slot for account data.
     #[must_use]
     fn slot_account(account_id: &AccountIdRef) -> Slot<u128> {
-        Self::root().field(StorageKey::Account(account_id))
+        Self::root().field_with(StorageKey::Account(account_id), Self::account_key_hashing())
     }
 
     /// Slot for storing total supply.
@@ -191,6 +276,41 @@ pub trait Nep141ControllerInternal {
     fn slot_total_supply() -> Slot<u128> {
         Self::root().field(StorageKey::TotalSupply)
     }
+
+    /// Maximum number of accounts accepted by
+    /// [`Nep141Controller::balance_of_batch`].
+    #[must_use]
+    fn max_balance_batch_len() -> usize {
+        DEFAULT_MAX_BALANCE_BATCH_LEN
+    }
+
+    /// Whether [`Nep141Resolver::ft_resolve_transfer`] may return unused
+    /// tokens to `account_id`, the sender of the original transfer. Contracts
+    /// that require registration (e.g. NEP-145) should return `false` for
+    /// accounts that are no longer registered, so that the refund does not
+    /// re-create their storage.
+    #[must_use]
+    fn can_receive_refund(account_id: &AccountIdRef) -> bool {
+        let _ = account_id;
+        true
+    }
+
+    /// Account that receives unused tokens that cannot be returned to the
+    /// sender (see [`Nep141ControllerInternal::can_receive_refund`]). If
+    /// `None`, the tokens are burned instead.
+    #[must_use]
+    fn refund_fallback_account() -> Option<AccountId> {
+        None
+    }
+}
+
+/// Account balances resulting from a transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferBalances {
+    /// New balance of the sender.
+    pub sender_balance: u128,
+    /// New balance of the receiver.
+    pub receiver_balance: u128,
 }
 
 /// Non-public implementations of functions for managing a fungible token.
@@ -211,11 +331,25 @@ pub trait Nep141Controller {
     /// Get the balance of an account. Returns 0 if the account does not exist.
     fn balance_of(&self, account_id: &AccountIdRef) -> u128;
 
+    /// Get the balances of multiple accounts, in the same order as
+    /// `account_ids`. Returns 0 for accounts that do not exist.
+    ///
+    /// # Errors
+    ///
+    /// - If more than [`Nep141ControllerInternal::max_balance_batch_len`]
+    ///   accounts are requested.
+    fn balance_of_batch(
+        &self,
+        account_ids: &[AccountId],
+    ) -> Result<Vec<u128>, BalanceBatchTooLongError>;
+
     /// Get the total circulating supply of the token.
     fn total_supply(&self) -> u128;
 
     /// Removes tokens from an account and decreases total supply. No event
-    /// emission or hook invocation.
+    /// emission or hook invocation, but the
+    /// [`Observer`](Nep141ControllerInternal::Observer) is notified. Returns
+    /// the new balance of the account.
     ///
     /// # Errors
     ///
@@ -225,10 +359,12 @@ pub trait Nep141Controller {
         &mut self,
         account_id: &AccountIdRef,
         amount: u128,
-    ) -> Result<(), WithdrawError>;
+    ) -> Result<u128, WithdrawError>;
 
     /// Increases the token balance of an account. Updates total supply. No
-    /// event emission or hook invocation.
+    /// event emission or hook invocation, but the
+    /// [`Observer`](Nep141ControllerInternal::Observer) is notified. Returns
+    /// the new balance of the account.
     ///
     /// # Errors
     ///
@@ -238,14 +374,17 @@ pub trait Nep141Controller {
         &mut self,
         account_id: &AccountIdRef,
         amount: u128,
-    ) -> Result<(), DepositError>;
+    ) -> Result<u128, DepositError>;
 
     /// Decreases the balance of `sender_account_id` by `amount` and increases
     /// the balance of `receiver_account_id` by the same. No change to total
-    /// supply. No event emission or hook invocation.
+    /// supply. No event emission or hook invocation, but the
+    /// [`Observer`](Nep141ControllerInternal::Observer) is notified of both
+    /// changes. Returns the new balances of both accounts.
     ///
     /// # Errors
     ///
+    /// - Sender and receiver are the same account.
     /// - Receiver balance overflow.
     /// - Sender balance underflow.
     fn transfer_unchecked(
@@ -253,34 +392,65 @@ pub trait Nep141Controller {
         sender_account_id: &AccountIdRef,
         receiver_account_id: &AccountIdRef,
         amount: u128,
-    ) -> Result<(), TransferError>;
+    ) -> Result<TransferBalances, TransferError>;
+
+    /// Performs an NEP-141 token transfer with event emission, but without
+    /// invoking [`Nep141Controller::TransferHook`]. Returns the new balances
+    /// of both accounts.
+    ///
+    /// Transfers can be performed at three levels:
+    ///
+    /// - [`Nep141Controller::transfer`] invokes hooks and emits an event. Use
+    ///   it for user-initiated transfers.
+    /// - This function emits an event but skips hooks. Use it for internal
+    ///   protocol operations (e.g. settling fees, liquidations) that must be
+    ///   visible to indexers but must not re-trigger user-facing hooks,
+    ///   which could re-enter or double-charge.
+    /// - [`Nep141Controller::transfer_unchecked`] only moves balances. Use it
+    ///   as a building block when the caller emits its own events.
+    ///
+    /// # Errors
+    ///
+    /// - Sender and receiver are the same account.
+    /// - Receiver balance overflow.
+    /// - Sender balance underflow.
+    fn transfer_with_event_no_hooks(
+        &mut self,
+        transfer: &Nep141Transfer<'_>,
+    ) -> Result<TransferBalances, TransferError>;
 
     /// Performs an NEP-141 token transfer, with event emission. Invokes
-    /// [`Nep141Controller::TransferHook`].
+    /// [`Nep141Controller::TransferHook`]. Returns the new balances of both
+    /// accounts.
     ///
     /// # Errors
     ///
+    /// - Sender and receiver are the same account.
     /// - Receiver balance overflow.
     /// - Sender balance underflow.
-    fn transfer(&mut self, transfer: &Nep141Transfer<'_>) -> Result<(), TransferError>;
+    fn transfer(
+        &mut self,
+        transfer: &Nep141Transfer<'_>,
+    ) -> Result<TransferBalances, TransferError>;
 
     /// Performs an NEP-141 token mint, with event emission. Invokes
-    /// [`Nep141Controller::MintHook`].
+    /// [`Nep141Controller::MintHook`]. Returns the new balance of the
+    /// receiver.
     ///
     /// # Errors
     ///
     /// - Account balance overflow.
     /// - Total supply overflow.
-    fn mint(&mut self, mint: &Nep141Mint<'_>) -> Result<(), DepositError>;
+    fn mint(&mut self, mint: &Nep141Mint<'_>) -> Result<u128, DepositError>;
 
     /// Performs an NEP-141 token burn, with event emission. Invokes
-    /// [`Nep141Controller::BurnHook`].
+    /// [`Nep141Controller::BurnHook`]. Returns the new balance of the owner.
     ///
     /// # Errors
     ///
     /// - Account balance underflow.
     /// - Total supply underflow.
-    fn burn(&mut self, burn: &Nep141Burn<'_>) -> Result<(), WithdrawError>;
+    fn burn(&mut self, burn: &Nep141Burn<'_>) -> Result<u128, WithdrawError>;
 }
 
 impl<T: Nep141ControllerInternal> Nep141Controller for T {
@@ -292,6 +462,24 @@ impl<T: Nep141ControllerInternal> Nep141Controller for T {
         Self::slot_account(account_id).read().unwrap_or(0)
     }
 
+    fn balance_of_batch(
+        &self,
+        account_ids: &[AccountId],
+    ) -> Result<Vec<u128>, BalanceBatchTooLongError> {
+        let max_len = Self::max_balance_batch_len();
+        if account_ids.len() > max_len {
+            return Err(BalanceBatchTooLongError {
+                len: account_ids.len(),
+                max_len,
+            });
+        }
+
+        Ok(account_ids
+            .iter()
+            .map(|account_id| self.balance_of(account_id))
+            .collect())
+    }
+
     fn total_supply(&self) -> u128 {
         Self::slot_total_supply().read().unwrap_or(0)
     }
@@ -300,19 +488,19 @@ impl<T: Nep141ControllerInternal> Nep141Controller for T {
         &mut self,
         account_id: &AccountIdRef,
         amount: u128,
-    ) -> Result<(), WithdrawError> {
+    ) -> Result<u128, WithdrawError> {
+        let balance = self.balance_of(account_id);
+
         if amount != 0 {
-            let balance = self.balance_of(account_id);
-            if let Some(balance) = balance.checked_sub(amount) {
-                Self::slot_account(account_id).write(&balance);
-            } else {
+            let Some(new_balance) = balance.checked_sub(amount) else {
                 return Err(BalanceUnderflowError {
                     account_id: account_id.to_owned(),
                     balance,
                     amount,
                 }
                 .into());
-            }
+            };
+            Self::slot_account(account_id).write(&new_balance);
 
             let total_supply = self.total_supply();
             if let Some(total_supply) = total_supply.checked_sub(amount) {
@@ -324,28 +512,32 @@ impl<T: Nep141ControllerInternal> Nep141Controller for T {
                 }
                 .into());
             }
+
+            T::Observer::on_balance_change(self, account_id, balance, new_balance);
+
+            return Ok(new_balance);
         }
 
-        Ok(())
+        Ok(balance)
     }
 
     fn deposit_unchecked(
         &mut self,
         account_id: &AccountIdRef,
         amount: u128,
-    ) -> Result<(), DepositError> {
+    ) -> Result<u128, DepositError> {
+        let balance = self.balance_of(account_id);
+
         if amount != 0 {
-            let balance = self.balance_of(account_id);
-            if let Some(balance) = balance.checked_add(amount) {
-                Self::slot_account(account_id).write(&balance);
-            } else {
+            let Some(new_balance) = balance.checked_add(amount) else {
                 return Err(BalanceOverflowError {
                     account_id: account_id.to_owned(),
                     balance,
                     amount,
                 }
                 .into());
-            }
+            };
+            Self::slot_account(account_id).write(&new_balance);
 
             let total_supply = self.total_supply();
             if let Some(total_supply) = total_supply.checked_add(amount) {
@@ -357,9 +549,13 @@ impl<T: Nep141ControllerInternal> Nep141Controller for T {
                 }
                 .into());
             }
+
+            T::Observer::on_balance_change(self, account_id, balance, new_balance);
+
+            return Ok(new_balance);
         }
 
-        Ok(())
+        Ok(balance)
     }
 
     fn transfer_unchecked(
@@ -367,81 +563,113 @@ impl<T: Nep141ControllerInternal> Nep141Controller for T {
         sender_account_id: &AccountIdRef,
         receiver_account_id: &AccountIdRef,
         amount: u128,
-    ) -> Result<(), TransferError> {
-        let sender_balance = self.balance_of(sender_account_id);
+    ) -> Result<TransferBalances, TransferError> {
+        if sender_account_id == receiver_account_id {
+            return Err(SelfTransferError {
+                account_id: sender_account_id.to_owned(),
+            }
+            .into());
+        }
 
-        if let Some(sender_balance) = sender_balance.checked_sub(amount) {
-            let receiver_balance = self.balance_of(receiver_account_id);
-            if let Some(receiver_balance) = receiver_balance.checked_add(amount) {
+        let old_sender_balance = self.balance_of(sender_account_id);
+
+        if let Some(sender_balance) = old_sender_balance.checked_sub(amount) {
+            let old_receiver_balance = self.balance_of(receiver_account_id);
+            if let Some(receiver_balance) = old_receiver_balance.checked_add(amount) {
                 Self::slot_account(sender_account_id).write(&sender_balance);
                 Self::slot_account(receiver_account_id).write(&receiver_balance);
+
+                T::Observer::on_balance_change(
+                    self,
+                    sender_account_id,
+                    old_sender_balance,
+                    sender_balance,
+                );
+                T::Observer::on_balance_change(
+                    self,
+                    receiver_account_id,
+                    old_receiver_balance,
+                    receiver_balance,
+                );
+
+                Ok(TransferBalances {
+                    sender_balance,
+                    receiver_balance,
+                })
             } else {
-                return Err(BalanceOverflowError {
+                Err(BalanceOverflowError {
                     account_id: receiver_account_id.to_owned(),
-                    balance: receiver_balance,
+                    balance: old_receiver_balance,
                     amount,
                 }
-                .into());
+                .into())
             }
         } else {
-            return Err(BalanceUnderflowError {
+            Err(BalanceUnderflowError {
                 account_id: sender_account_id.to_owned(),
-                balance: sender_balance,
+                balance: old_sender_balance,
                 amount,
             }
-            .into());
+            .into())
         }
+    }
 
-        Ok(())
+    fn transfer_with_event_no_hooks(
+        &mut self,
+        transfer: &Nep141Transfer<'_>,
+    ) -> Result<TransferBalances, TransferError> {
+        let balances =
+            self.transfer_unchecked(&transfer.sender_id, &transfer.receiver_id, transfer.amount)?;
+
+        Nep141Event::FtTransfer(vec![FtTransferData {
+            old_owner_id: transfer.sender_id.clone(),
+            new_owner_id: transfer.receiver_id.clone(),
+            amount: transfer.amount.into(),
+            memo: transfer.event_memo(),
+        }])
+        .emit();
+
+        Ok(balances)
     }
 
-    fn transfer(&mut self, transfer: &Nep141Transfer<'_>) -> Result<(), TransferError> {
+    fn transfer(
+        &mut self,
+        transfer: &Nep141Transfer<'_>,
+    ) -> Result<TransferBalances, TransferError> {
         Self::TransferHook::hook(self, transfer, |contract| {
-            contract.transfer_unchecked(
-                &transfer.sender_id,
-                &transfer.receiver_id,
-                transfer.amount,
-            )?;
-
-            Nep141Event::FtTransfer(vec![FtTransferData {
-                old_owner_id: transfer.sender_id.clone(),
-                new_owner_id: transfer.receiver_id.clone(),
-                amount: transfer.amount.into(),
-                memo: transfer.memo.clone(),
-            }])
-            .emit();
-
-            Ok(())
+            contract.transfer_with_event_no_hooks(transfer)
         })
     }
 
-    fn mint(&mut self, mint: &Nep141Mint) -> Result<(), DepositError> {
+    fn mint(&mut self, mint: &Nep141Mint) -> Result<u128, DepositError> {
         Self::MintHook::hook(self, mint, |contract| {
-            contract.deposit_unchecked(&mint.receiver_id, mint.amount)?;
+            let balance = contract.deposit_unchecked(&mint.receiver_id, mint.amount)?;
 
             Nep141Event::FtMint(vec![FtMintData {
                 owner_id: mint.receiver_id.clone(),
                 amount: mint.amount.into(),
                 memo: mint.memo.clone(),
+                reason: mint.reason.clone(),
             }])
             .emit();
 
-            Ok(())
+            Ok(balance)
         })
     }
 
-    fn burn(&mut self, burn: &Nep141Burn) -> Result<(), WithdrawError> {
+    fn burn(&mut self, burn: &Nep141Burn) -> Result<u128, WithdrawError> {
         Self::BurnHook::hook(self, burn, |contract| {
-            contract.withdraw_unchecked(&burn.owner_id, burn.amount)?;
+            let balance = contract.withdraw_unchecked(&burn.owner_id, burn.amount)?;
 
             Nep141Event::FtBurn(vec![FtBurnData {
                 owner_id: burn.owner_id.clone(),
                 amount: burn.amount.into(),
                 memo: burn.memo.clone(),
+                reason: burn.reason.clone(),
             }])
             .emit();
 
-            Ok(())
+            Ok(balance)
         })
     }
 }
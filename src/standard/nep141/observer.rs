@@ -0,0 +1,39 @@
+//! Observers for NEP-141 balance changes.
+//!
+//! Unlike [`Hook`](crate::hook::Hook)s, which wrap whole operations,
+//! observers are notified of each individual balance change, including
+//! those made through the `*_unchecked` controller functions. This lets
+//! other components (e.g. governance or checkpointing layers) keep derived
+//! state in sync with token balances.
+
+use near_sdk::AccountIdRef;
+
+/// Notified whenever an account's NEP-141 balance changes.
+pub trait Nep141Observer<C> {
+    /// Called after the balance of `account_id` has been written.
+    fn on_balance_change(
+        _contract: &mut C,
+        _account_id: &AccountIdRef,
+        _old_balance: u128,
+        _new_balance: u128,
+    ) {
+    }
+}
+
+impl<C> Nep141Observer<C> for () {}
+
+impl<C, T, U> Nep141Observer<C> for (T, U)
+where
+    T: Nep141Observer<C>,
+    U: Nep141Observer<C>,
+{
+    fn on_balance_change(
+        contract: &mut C,
+        account_id: &AccountIdRef,
+        old_balance: u128,
+        new_balance: u128,
+    ) {
+        T::on_balance_change(contract, account_id, old_balance, new_balance);
+        U::on_balance_change(contract, account_id, old_balance, new_balance);
+    }
+}
@@ -0,0 +1,94 @@
+//! Registration and minting in a single step, for contracts that implement
+//! both NEP-141 and NEP-145 (e.g. using the `FungibleToken` derive macro).
+
+use near_sdk::{AccountIdRef, NearToken};
+use thiserror::Error;
+
+use crate::standard::nep145::{
+    error::{StorageDepositError, StorageWithdrawError},
+    Nep145Controller,
+};
+
+use super::{error::DepositError, Nep141Controller, Nep141Mint};
+
+/// Errors that may occur during [`Nep141RegisterAndMint::register_and_mint`].
+#[derive(Debug, Error)]
+pub enum RegisterAndMintError {
+    /// The deposit could not be added to the account's storage balance, e.g.
+    /// because it does not cover the minimum balance required to register.
+    #[error(transparent)]
+    StorageDeposit(#[from] StorageDepositError),
+    /// The tokens could not be minted.
+    #[error(transparent)]
+    Mint(#[from] DepositError),
+    /// The unused part of the deposit could not be withdrawn.
+    #[error(transparent)]
+    StorageWithdraw(#[from] StorageWithdrawError),
+}
+
+/// Registers an account for storage and mints tokens to it.
+pub trait Nep141RegisterAndMint {
+    /// Adds `deposit` (usually the attached deposit) to the storage balance
+    /// of `account_id`, registering it if necessary, then mints `amount`
+    /// tokens to it. `amount` is in the smallest unit of the token, as
+    /// determined by the NEP-148 `decimals`. The storage used by the mint is
+    /// charged to the account's storage balance as usual.
+    ///
+    /// Of `deposit`, only the minimum storage balance (for new accounts)
+    /// and the storage fee for the mint are kept. Returns the remainder,
+    /// which the caller should refund, e.g. to the predecessor. Deposits in
+    /// excess of the maximum storage balance are also returned.
+    ///
+    /// No state should be committed if this method returns an error, so
+    /// callers should panic.
+    ///
+    /// # Errors
+    ///
+    /// - If `deposit` does not cover the minimum storage balance of a new
+    ///   account.
+    /// - If minting fails.
+    fn register_and_mint(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+        deposit: NearToken,
+    ) -> Result<NearToken, RegisterAndMintError>;
+}
+
+impl<T: Nep141Controller + Nep145Controller> Nep141RegisterAndMint for T {
+    fn register_and_mint(
+        &mut self,
+        account_id: &AccountIdRef,
+        amount: u128,
+        deposit: NearToken,
+    ) -> Result<NearToken, RegisterAndMintError> {
+        let bounds = self.get_storage_balance_bounds();
+        let before = self.get_storage_balance(account_id).unwrap_or_default();
+
+        let deposited = bounds
+            .max
+            .map_or(deposit, |max| deposit.min(max.saturating_sub(before.total)));
+
+        self.deposit_to_storage_account(account_id, deposited)?;
+
+        Nep141Controller::mint(self, &Nep141Mint::new(amount, account_id))?;
+
+        let after = self
+            .get_storage_balance(account_id)
+            .map_err(StorageWithdrawError::from)?;
+
+        // Only the part of this deposit that is still available, and above
+        // the minimum balance, is returned.
+        let unused = after
+            .available
+            .saturating_sub(before.available)
+            .min(deposited)
+            .min(after.total.saturating_sub(bounds.min));
+
+        if !unused.is_zero() {
+            self.withdraw_from_storage_account(account_id, unused)?;
+        }
+
+        Ok(deposit.saturating_sub(deposited).saturating_add(unused))
+    }
+}
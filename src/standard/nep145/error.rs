@@ -24,6 +24,12 @@ pub struct InsufficientBalanceError {
 #[error("Account {0} is not registered")]
 pub struct AccountNotRegisteredError(pub AccountId);
 
+/// Occurs when an account attempts to withdraw its available storage balance,
+/// but none of its balance can be withdrawn.
+#[derive(Debug, Error)]
+#[error("Account {0} has no storage balance available to withdraw")]
+pub struct NothingToWithdrawError(pub AccountId);
+
 /// Occurs when an account attempts to unlock more tokens than it has deposited.
 #[derive(Debug, Error)]
 #[error("Account {0} cannot unlock more tokens than it has deposited")]
@@ -53,6 +59,18 @@ pub struct MaximumBalanceOverrunError {
     pub maximum_balance: NearToken,
 }
 
+/// Occurs when a deposit does not cover the storage balances it is meant to
+/// fund.
+#[derive(Debug, Error)]
+#[error("Attached deposit {attached} is less than required {required}")]
+pub struct InsufficientDepositError {
+    /// The amount deposited.
+    pub attached: NearToken,
+
+    /// The amount required.
+    pub required: NearToken,
+}
+
 /// Occurs when an account attempts to unregister with a locked balance.
 #[derive(Debug, Error)]
 #[error("Account {account_id} cannot unregister with locked balance {locked_balance} > 0")]
@@ -97,6 +115,18 @@ pub enum StorageDepositError {
     MaximumBalanceOverrunError(#[from] MaximumBalanceOverrunError),
 }
 
+/// Errors that can occur when depositing storage balance for multiple
+/// accounts at once.
+#[derive(Debug, Error)]
+pub enum StorageDepositBatchError {
+    /// The deposit does not cover all of the accounts.
+    #[error(transparent)]
+    InsufficientDeposit(#[from] InsufficientDepositError),
+    /// Depositing to one of the accounts failed.
+    #[error(transparent)]
+    StorageDeposit(#[from] StorageDepositError),
+}
+
 /// Errors that can occur when withdrawing storage balance.
 #[derive(Debug, Error)]
 pub enum StorageWithdrawError {
@@ -109,6 +139,9 @@ pub enum StorageWithdrawError {
     /// The withdrawal exceeds the available storage balance.
     #[error(transparent)]
     InsufficientBalance(#[from] InsufficientBalanceError),
+    /// None of the account's storage balance can be withdrawn.
+    #[error(transparent)]
+    NothingToWithdraw(#[from] NothingToWithdrawError),
 }
 
 /// Errors that can occur when unregistering storage balance.
@@ -1,9 +1,11 @@
 //! NEP-145 Storage Management
 //! <https://github.com/near/NEPs/blob/master/neps/nep-0145.md>
 
-use std::{borrow::Cow, cmp::Ordering};
+use std::{borrow::Cow, cmp::Ordering, collections::HashSet};
 
-use near_sdk::{borsh::BorshSerialize, env, near, AccountIdRef, BorshStorageKey, NearToken};
+use near_sdk::{
+    borsh::BorshSerialize, env, near, AccountId, AccountIdRef, BorshStorageKey, NearToken,
+};
 
 use crate::{hook::Hook, slot::Slot, DefaultStorageKey};
 
@@ -63,6 +65,32 @@ impl StorageBalanceBounds {
             balance
         }
     }
+
+    /// Determines how much of an attached deposit should be credited to an
+    /// account with storage balance `current_total` (`None` if the account
+    /// is not registered). The remainder of the deposit should be refunded.
+    ///
+    /// - A `registration_only` deposit for a registered account is refunded
+    ///     in full; for an unregistered account, only `min` is kept.
+    /// - Otherwise, the deposit is capped so that the account's total does
+    ///     not exceed `max`.
+    #[must_use]
+    pub fn bound_deposit(
+        &self,
+        current_total: Option<NearToken>,
+        attached: NearToken,
+        registration_only: bool,
+    ) -> NearToken {
+        match (current_total, registration_only) {
+            (Some(_), true) => NearToken::from_yoctonear(0),
+            (None, true) => self.min,
+            (current_total, false) => self.max.map_or(attached, |max| {
+                let room =
+                    max.saturating_sub(current_total.unwrap_or(NearToken::from_yoctonear(0)));
+                NearToken::from_yoctonear(u128::min(room.as_yoctonear(), attached.as_yoctonear()))
+            }),
+        }
+    }
 }
 
 impl Default for StorageBalanceBounds {
@@ -91,10 +119,23 @@ pub struct Nep145ForceUnregister<'a> {
     pub balance: StorageBalance,
 }
 
+/// Describes a (non-forced) unregister action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[near]
+pub struct Nep145Unregister<'a> {
+    /// The account to be unregistered.
+    pub account_id: Cow<'a, AccountIdRef>,
+}
+
 /// NEP-145 Storage Management internal controller interface.
 pub trait Nep145ControllerInternal {
     /// NEP-145 lifecycle hook.
     type ForceUnregisterHook: for<'a> Hook<Self, Nep145ForceUnregister<'a>>
+    where
+        Self: Sized;
+    /// Invoked when an account unregisters without `force`. May panic to
+    /// reject the unregistration.
+    type UnregisterHook: for<'a> Hook<Self, Nep145Unregister<'a>>
     where
         Self: Sized;
 
@@ -122,6 +163,11 @@ pub trait Nep145ControllerInternal {
 pub trait Nep145Controller {
     /// NEP-145 lifecycle hook.
     type ForceUnregisterHook: for<'a> Hook<Self, Nep145ForceUnregister<'a>>
+    where
+        Self: Sized;
+    /// Invoked when an account unregisters without `force`. May panic to
+    /// reject the unregistration.
+    type UnregisterHook: for<'a> Hook<Self, Nep145Unregister<'a>>
     where
         Self: Sized;
 
@@ -171,6 +217,23 @@ pub trait Nep145Controller {
         amount: NearToken,
     ) -> Result<StorageBalance, StorageDepositError>;
 
+    /// Registers multiple accounts from a single deposit. Each unregistered
+    /// account is charged the minimum balance; accounts that are already
+    /// registered (including accounts listed more than once) are skipped and
+    /// not charged. Returns the total amount charged and the resulting storage
+    /// balances, in order.
+    ///
+    /// # Errors
+    ///
+    /// - If `deposit` does not cover the total amount charged, in which case
+    ///     no account is modified.
+    /// - If an account's balance would fall outside the balance bounds.
+    fn deposit_to_storage_accounts_batch(
+        &mut self,
+        accounts: &[AccountId],
+        deposit: NearToken,
+    ) -> Result<(NearToken, Vec<StorageBalance>), StorageDepositBatchError>;
+
     /// Withdraws the given amount of storage balance for the given account.
     ///
     /// # Errors
@@ -184,6 +247,19 @@ pub trait Nep145Controller {
         amount: NearToken,
     ) -> Result<StorageBalance, StorageWithdrawError>;
 
+    /// Withdraws as much of the account's storage balance as possible: all of
+    /// the available (unlocked) balance, down to the minimum balance. Returns
+    /// the amount withdrawn and the new balance.
+    ///
+    /// # Errors
+    ///
+    /// - If the account is not registered.
+    /// - If there is nothing to withdraw.
+    fn withdraw_available_from_storage_account(
+        &mut self,
+        account_id: &AccountIdRef,
+    ) -> Result<(NearToken, StorageBalance), StorageWithdrawError>;
+
     /// Unregisters the given account, returning the amount of storage balance
     /// that should be refunded.
     ///
@@ -252,6 +328,7 @@ pub trait Nep145Controller {
 
 impl<T: Nep145ControllerInternal> Nep145Controller for T {
     type ForceUnregisterHook = <Self as Nep145ControllerInternal>::ForceUnregisterHook;
+    type UnregisterHook = <Self as Nep145ControllerInternal>::UnregisterHook;
 
     fn get_storage_balance(
         &self,
@@ -364,6 +441,54 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
         Ok(balance)
     }
 
+    fn deposit_to_storage_accounts_batch(
+        &mut self,
+        accounts: &[AccountId],
+        deposit: NearToken,
+    ) -> Result<(NearToken, Vec<StorageBalance>), StorageDepositBatchError> {
+        let min = self.get_storage_balance_bounds().min;
+        let mut seen = HashSet::<&AccountId>::new();
+        let mut required = NearToken::from_yoctonear(0);
+
+        let charged = accounts
+            .iter()
+            .map(|account_id| {
+                let charge =
+                    seen.insert(account_id) && Self::slot_account(account_id).read().is_none();
+
+                if charge {
+                    required = required
+                        .checked_add(min)
+                        .unwrap_or_else(|| env::panic_str(PANIC_MESSAGE_STORAGE_TOTAL_OVERFLOW));
+                }
+
+                charge
+            })
+            .collect::<Vec<_>>();
+
+        if required > deposit {
+            return Err(InsufficientDepositError {
+                attached: deposit,
+                required,
+            }
+            .into());
+        }
+
+        let balances = accounts
+            .iter()
+            .zip(charged)
+            .map(|(account_id, charge)| {
+                if charge {
+                    self.deposit_to_storage_account(account_id, min)
+                } else {
+                    Ok(Self::slot_account(account_id).read().unwrap_or_default())
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((required, balances))
+    }
+
     fn withdraw_from_storage_account(
         &mut self,
         account_id: &AccountIdRef,
@@ -403,31 +528,60 @@ impl<T: Nep145ControllerInternal> Nep145Controller for T {
         Ok(balance)
     }
 
+    fn withdraw_available_from_storage_account(
+        &mut self,
+        account_id: &AccountIdRef,
+    ) -> Result<(NearToken, StorageBalance), StorageWithdrawError> {
+        let balance = self.get_storage_balance(account_id)?;
+        let bounds = self.get_storage_balance_bounds();
+
+        let amount = NearToken::from_yoctonear(u128::min(
+            balance.available.as_yoctonear(),
+            balance.total.saturating_sub(bounds.min).as_yoctonear(),
+        ));
+
+        if amount.is_zero() {
+            return Err(NothingToWithdrawError(account_id.to_owned()).into());
+        }
+
+        let balance = self.withdraw_from_storage_account(account_id, amount)?;
+
+        Ok((amount, balance))
+    }
+
     fn unregister_storage_account(
         &mut self,
         account_id: &AccountIdRef,
     ) -> Result<NearToken, StorageUnregisterError> {
-        let mut account_slot = Self::slot_account(account_id);
+        self.get_storage_balance(account_id)?;
 
-        let balance = account_slot
-            .read()
-            .ok_or_else(|| AccountNotRegisteredError(account_id.to_owned()))?;
+        let action = Nep145Unregister {
+            account_id: account_id.into(),
+        };
 
-        match balance.total.checked_sub(balance.available) {
-            Some(locked_balance) if !locked_balance.is_zero() => {
-                return Err(UnregisterWithLockedBalanceError {
-                    account_id: account_id.to_owned(),
-                    locked_balance,
+        Self::UnregisterHook::hook(self, &action, |_| {
+            let mut account_slot = Self::slot_account(account_id);
+
+            let balance = account_slot
+                .read()
+                .ok_or_else(|| AccountNotRegisteredError(account_id.to_owned()))?;
+
+            match balance.total.checked_sub(balance.available) {
+                Some(locked_balance) if !locked_balance.is_zero() => {
+                    return Err(UnregisterWithLockedBalanceError {
+                        account_id: account_id.to_owned(),
+                        locked_balance,
+                    }
+                    .into())
                 }
-                .into())
+                None => env::panic_str(PANIC_MESSAGE_INCONSISTENT_STATE_AVAILABLE),
+                _ => {}
             }
-            None => env::panic_str(PANIC_MESSAGE_INCONSISTENT_STATE_AVAILABLE),
-            _ => {}
-        }
 
-        account_slot.remove();
+            account_slot.remove();
 
-        Ok(balance.total)
+            Ok(balance.total)
+        })
     }
 
     fn force_unregister_storage_account(
@@ -1,6 +1,6 @@
 //! Potential errors produced by various token manipulations.
 
-use near_sdk::AccountId;
+use near_sdk::{AccountId, Gas};
 use thiserror::Error;
 
 use crate::standard::nep178::ApprovalId;
@@ -16,6 +16,13 @@ pub enum Nep171BurnError {
     /// The token could not be burned because it is not owned by the expected owner.
     #[error(transparent)]
     TokenNotOwnedByExpectedOwner(#[from] TokenNotOwnedByExpectedOwnerError),
+    /// The tokens could not be burned because the same token ID was given
+    /// more than once.
+    #[error(transparent)]
+    DuplicateTokenId(#[from] DuplicateTokenIdError),
+    /// The token could not be burned because it is locked.
+    #[error(transparent)]
+    TokenLocked(#[from] TokenLockedError),
 }
 
 /// Potential errors encountered when attempting to mint a new token.
@@ -24,6 +31,23 @@ pub enum Nep171MintError {
     /// The token could not be minted because a token with the same ID already exists.
     #[error(transparent)]
     TokenAlreadyExists(#[from] TokenAlreadyExistsError),
+    /// The token could not be minted because its ID is too long.
+    #[error(transparent)]
+    TokenIdTooLong(#[from] TokenIdTooLongError),
+    /// The token could not be minted because the memo is too long.
+    #[error(transparent)]
+    MemoTooLong(#[from] MemoTooLongError),
+    /// The token could not be minted because minting has been permanently
+    /// closed.
+    #[error(transparent)]
+    MintClosed(#[from] MintClosedError),
+    /// The token could not be minted because minting is currently paused.
+    #[error(transparent)]
+    MintPaused(#[from] MintPausedError),
+    /// The tokens could not be minted because the same token ID was given
+    /// more than once.
+    #[error(transparent)]
+    DuplicateTokenId(#[from] DuplicateTokenIdError),
 }
 
 /// Potential errors encountered when performing a token transfer.
@@ -43,6 +67,29 @@ pub enum Nep171TransferError {
     /// The token could not be transferred because it is no longer owned by the expected owner.
     #[error(transparent)]
     TokenNotOwnedByExpectedOwner(#[from] TokenNotOwnedByExpectedOwnerError),
+    /// The token could not be transferred because transfers are currently frozen.
+    #[error(transparent)]
+    TransfersFrozen(#[from] TransfersFrozenError),
+    /// The token could not be transferred because its ID is too long.
+    #[error(transparent)]
+    TokenIdTooLong(#[from] TokenIdTooLongError),
+    /// The token could not be transferred because the memo is too long.
+    #[error(transparent)]
+    MemoTooLong(#[from] MemoTooLongError),
+    /// The token could not be transferred because it is locked.
+    #[error(transparent)]
+    TokenLocked(#[from] TokenLockedError),
+}
+
+/// Potential errors encountered when locking a token.
+#[derive(Error, Clone, Debug)]
+pub enum Nep171LockError {
+    /// The token could not be locked because it does not exist.
+    #[error(transparent)]
+    TokenDoesNotExist(#[from] TokenDoesNotExistError),
+    /// The token could not be locked because it is already locked.
+    #[error(transparent)]
+    TokenLocked(#[from] TokenLockedError),
 }
 
 /// Occurs when trying to create a token ID that already exists.
@@ -54,6 +101,14 @@ pub struct TokenAlreadyExistsError {
     pub token_id: TokenId,
 }
 
+/// Occurs when a batch operation lists the same token ID more than once.
+#[derive(Error, Clone, Debug)]
+#[error("Token `{token_id}` is listed more than once")]
+pub struct DuplicateTokenIdError {
+    /// The repeated token ID.
+    pub token_id: TokenId,
+}
+
 /// When attempting to interact with a non-existent token ID.
 #[derive(Error, Clone, Debug)]
 #[error("Token `{token_id}` does not exist")]
@@ -102,3 +157,68 @@ pub struct TokenReceiverIsCurrentOwnerError {
     /// The ID of the token in question.
     pub token_id: TokenId,
 }
+
+/// Occurs when attempting to transfer a token while contract-wide transfers
+/// are frozen.
+#[derive(Error, Clone, Debug)]
+#[error("Transfers are frozen: token `{token_id}` cannot be transferred")]
+pub struct TransfersFrozenError {
+    /// The ID of the token in question.
+    pub token_id: TokenId,
+}
+
+/// Occurs when attempting to transfer, burn, or lock a token that is locked.
+#[derive(Error, Clone, Debug)]
+#[error("Token `{token_id}` is locked by `{locker_id}`")]
+pub struct TokenLockedError {
+    /// The ID of the token in question.
+    pub token_id: TokenId,
+    /// The account that holds the lock.
+    pub locker_id: AccountId,
+}
+
+/// Occurs when attempting to mint after minting has been permanently closed.
+#[derive(Error, Clone, Debug)]
+#[error("Minting is closed")]
+pub struct MintClosedError;
+
+/// Occurs when attempting to mint while minting is paused.
+#[derive(Error, Clone, Debug)]
+#[error("Minting is paused")]
+pub struct MintPausedError;
+
+/// Occurs when a token ID exceeds the maximum allowed length.
+#[derive(Error, Clone, Debug)]
+#[error("Token ID length {len} exceeds maximum of {max_len} bytes")]
+pub struct TokenIdTooLongError {
+    /// The length of the rejected token ID, in bytes.
+    pub len: usize,
+    /// The maximum allowed length, in bytes.
+    pub max_len: usize,
+}
+
+/// Occurs when a memo exceeds the maximum allowed length.
+#[derive(Error, Clone, Debug)]
+#[error("Memo length {len} exceeds maximum of {max_len} bytes")]
+pub struct MemoTooLongError {
+    /// The length of the rejected memo, in bytes.
+    pub len: usize,
+    /// The maximum allowed length, in bytes.
+    pub max_len: usize,
+}
+
+/// Occurs when the gas attached to [`Nep171::nft_transfer_call`](super::Nep171::nft_transfer_call)
+/// cannot cover the minimum allocations for both the receiver and the
+/// resolver.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error(
+    "Insufficient gas for nft_transfer_call: {} required, {} attached",
+    .required.as_gas(),
+    .prepaid.as_gas(),
+)]
+pub struct TransferCallGasError {
+    /// The minimum amount of gas that must be attached.
+    pub required: Gas,
+    /// The amount of gas attached to the call.
+    pub prepaid: Gas,
+}
@@ -29,6 +29,28 @@ pub enum Nep171Event<'a> {
     ContractMetadataUpdate(Vec<NftContractMetadataUpdateLog<'a>>),
 }
 
+/// Non-standard events emitted when NFT transfers or mints are frozen or
+/// unfrozen.
+#[event(
+    crate = "crate",
+    macros = "near_sdk_contract_tools_macros",
+    standard = "x-nft-freeze",
+    version = "1.0.0"
+)]
+#[derive(Debug, Clone)]
+pub enum Nep171FreezeEvent {
+    /// Emitted when external transfers are frozen.
+    TransfersFrozen,
+    /// Emitted when external transfers are unfrozen.
+    TransfersUnfrozen,
+    /// Emitted when minting is permanently closed.
+    MintingClosed,
+    /// Emitted when minting is paused.
+    MintingPaused,
+    /// Emitted when minting is resumed.
+    MintingResumed,
+}
+
 /// Tokens minted to a single owner.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(crate = "near_sdk::serde")]
@@ -41,7 +41,7 @@ use std::error::Error;
 
 use near_sdk::{
     borsh::BorshSerialize,
-    near,
+    env, near,
     serde::{Deserialize, Serialize},
     AccountId, AccountIdRef, BorshStorageKey, Gas, NearSchema,
 };
@@ -59,22 +59,91 @@ use event::*;
 mod ext;
 pub use ext::*;
 pub mod hooks;
+pub mod sequential;
 
 /// Minimum required gas for [`Nep171Resolver::nft_resolve_transfer`] call in promise chain during [`Nep171::nft_transfer_call`].
 pub const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas::from_gas(5_000_000_000_000);
 /// Minimum gas required to execute the main body of [`Nep171::nft_transfer_call`] + gas for [`Nep171Resolver::nft_resolve_transfer`].
 pub const GAS_FOR_NFT_TRANSFER_CALL: Gas =
     Gas::from_gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.as_gas());
+/// Minimum gas forwarded to [`Nep171Receiver::nft_on_transfer`] during
+/// [`Nep171::nft_transfer_call`].
+pub const MIN_GAS_FOR_NFT_ON_TRANSFER: Gas = Gas::from_gas(5_000_000_000_000);
+/// Default percentage of the spare gas in [`Nep171::nft_transfer_call`]
+/// forwarded to the receiver. See [`TransferCallGas::allocate`].
+pub const DEFAULT_RECEIVER_GAS_SHARE: u8 = 100;
 /// Error message when insufficient gas is attached to function calls with a minimum attached gas requirement (i.e. those that produce a promise chain, perform cross-contract calls).
 pub const INSUFFICIENT_GAS_MESSAGE: &str = "More gas is required";
+/// Error message when the tracked total supply would overflow.
+pub const TOTAL_SUPPLY_OVERFLOW_MESSAGE: &str = "Total supply overflow";
+
+/// Default maximum length, in bytes, of a token ID accepted by
+/// [`Nep171Controller::mint`]. Not enforced on transfers by default, so that
+/// tokens minted with longer IDs remain transferable; see
+/// [`Nep171ControllerInternal::max_transfer_token_id_len`].
+pub const DEFAULT_MAX_TOKEN_ID_LEN: usize = 256;
+/// Default maximum length, in bytes, of a memo accepted by
+/// [`Nep171Controller::mint`] and [`Nep171Controller::external_transfer`].
+pub const DEFAULT_MAX_MEMO_LEN: usize = 256;
 
 /// NFT token IDs.
 pub type TokenId = String;
 
+/// Gas allocated to the cross-contract calls made by
+/// [`Nep171::nft_transfer_call`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferCallGas {
+    /// Gas for [`Nep171Receiver::nft_on_transfer`].
+    pub receiver: Gas,
+    /// Gas for [`Nep171Resolver::nft_resolve_transfer`].
+    pub resolver: Gas,
+}
+
+impl TransferCallGas {
+    /// Splits `prepaid_gas` between the receiver and the resolver.
+    ///
+    /// After reserving gas to execute [`Nep171::nft_transfer_call`] itself,
+    /// the receiver is guaranteed [`MIN_GAS_FOR_NFT_ON_TRANSFER`] and the
+    /// resolver is guaranteed [`GAS_FOR_RESOLVE_TRANSFER`]. Any gas left over
+    /// is divided between them: `receiver_share` percent (capped at 100) goes
+    /// to the receiver, and the remainder to the resolver.
+    ///
+    /// # Errors
+    ///
+    /// - If `prepaid_gas` cannot satisfy both minimums.
+    pub fn allocate(prepaid_gas: Gas, receiver_share: u8) -> Result<Self, TransferCallGasError> {
+        let required = GAS_FOR_NFT_TRANSFER_CALL.saturating_add(MIN_GAS_FOR_NFT_ON_TRANSFER);
+
+        let spare = prepaid_gas
+            .checked_sub(required)
+            .ok_or(TransferCallGasError {
+                required,
+                prepaid: prepaid_gas,
+            })?
+            .as_gas();
+
+        #[allow(clippy::cast_possible_truncation)] // share <= 100 so the result is <= spare
+        let receiver_spare = (u128::from(spare) * u128::from(receiver_share.min(100)) / 100) as u64;
+
+        Ok(Self {
+            receiver: MIN_GAS_FOR_NFT_ON_TRANSFER.saturating_add(Gas::from_gas(receiver_spare)),
+            resolver: GAS_FOR_RESOLVE_TRANSFER
+                .saturating_add(Gas::from_gas(spare - receiver_spare)),
+        })
+    }
+}
+
 #[derive(BorshSerialize, BorshStorageKey)]
 #[borsh(crate = "near_sdk::borsh")]
 enum StorageKey<'a> {
     TokenOwner(&'a str),
+    TransfersFrozen,
+    NextTokenId,
+    TokenIdPrefix,
+    TotalSupply,
+    MintingClosed,
+    MintingPaused,
+    TokenLock(&'a str),
 }
 
 /// Internal (storage location) methods for implementors of [`Nep171Controller`].
@@ -113,6 +182,106 @@ pub trait Nep171ControllerInternal {
     fn slot_token_owner(token_id: &TokenId) -> Slot<AccountId> {
         Self::root().field(StorageKey::TokenOwner(token_id))
     }
+
+    /// Storage slot for the account holding the lock on a token, if any.
+    #[must_use]
+    fn slot_token_lock(token_id: &TokenId) -> Slot<AccountId> {
+        Self::root().field(StorageKey::TokenLock(token_id))
+    }
+
+    /// Storage slot for the contract-wide transfer freeze flag.
+    #[must_use]
+    fn slot_transfers_frozen() -> Slot<bool> {
+        Self::root().field(StorageKey::TransfersFrozen)
+    }
+
+    /// Storage slot for the permanent minting closure flag.
+    #[must_use]
+    fn slot_minting_closed() -> Slot<bool> {
+        Self::root().field(StorageKey::MintingClosed)
+    }
+
+    /// Storage slot for the reversible minting pause flag.
+    #[must_use]
+    fn slot_minting_paused() -> Slot<bool> {
+        Self::root().field(StorageKey::MintingPaused)
+    }
+
+    /// Whether [`Nep171Controller::mint`] and [`Nep171Controller::burn`]
+    /// maintain a count of existing tokens in
+    /// [`Nep171ControllerInternal::slot_total_supply`].
+    #[must_use]
+    fn track_supply() -> bool {
+        false
+    }
+
+    /// Storage slot for the number of existing tokens. Only maintained if
+    /// [`Nep171ControllerInternal::track_supply`] is `true`.
+    #[must_use]
+    fn slot_total_supply() -> Slot<u64> {
+        Self::root().field(StorageKey::TotalSupply)
+    }
+
+    /// Maximum length, in bytes, of a minted token ID.
+    #[must_use]
+    fn max_token_id_len() -> usize {
+        DEFAULT_MAX_TOKEN_ID_LEN
+    }
+
+    /// Maximum length, in bytes, of a token ID accepted by
+    /// [`Nep171Controller::external_transfer`]. `None` (the default) means
+    /// no limit. Setting a limit makes existing tokens with longer IDs
+    /// untransferable.
+    #[must_use]
+    fn max_transfer_token_id_len() -> Option<usize> {
+        None
+    }
+
+    /// Maximum length, in bytes, of a memo.
+    #[must_use]
+    fn max_memo_len() -> usize {
+        DEFAULT_MAX_MEMO_LEN
+    }
+
+    /// Percentage of the spare gas in [`Nep171::nft_transfer_call`]
+    /// forwarded to the receiver rather than the resolver.
+    #[must_use]
+    fn receiver_gas_share() -> u8 {
+        DEFAULT_RECEIVER_GAS_SHARE
+    }
+}
+
+fn check_token_id_len(token_id: &str, max_len: usize) -> Result<(), TokenIdTooLongError> {
+    if token_id.len() > max_len {
+        return Err(TokenIdTooLongError {
+            len: token_id.len(),
+            max_len,
+        });
+    }
+    Ok(())
+}
+
+fn check_memo_len<T: Nep171ControllerInternal>(memo: Option<&str>) -> Result<(), MemoTooLongError> {
+    let max_len = T::max_memo_len();
+    match memo {
+        Some(memo) if memo.len() > max_len => Err(MemoTooLongError {
+            len: memo.len(),
+            max_len,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn check_unique_token_ids(token_ids: &[TokenId]) -> Result<(), DuplicateTokenIdError> {
+    let mut seen = std::collections::HashSet::with_capacity(token_ids.len());
+    for token_id in token_ids {
+        if !seen.insert(token_id) {
+            return Err(DuplicateTokenIdError {
+                token_id: token_id.clone(),
+            });
+        }
+    }
+    Ok(())
 }
 
 /// Non-public controller interface for NEP-171 implementations.
@@ -174,7 +343,9 @@ pub trait Nep171Controller {
     ///
     /// # Errors
     ///
+    /// - If minting is closed or paused.
     /// - If the token ID already exists.
+    /// - If the same token ID is given more than once.
     fn mint(&mut self, action: &Nep171Mint<'_>) -> Result<(), Nep171MintError>;
 
     /// Mints a new token `token_id` to `owner_id` without checking if the
@@ -188,10 +359,12 @@ pub trait Nep171Controller {
     ///
     /// - If the token does not exist.
     /// - If the token is not owned by the expected owner.
+    /// - If the token is locked.
+    /// - If the same token ID is given more than once.
     fn burn(&mut self, action: &Nep171Burn<'_>) -> Result<(), Nep171BurnError>;
 
-    /// Burns tokens `token_ids` without checking the owners. Does not emit
-    /// events or run hooks.
+    /// Burns tokens `token_ids` without checking the owners or locks, and
+    /// removes any locks on them. Does not emit events or run hooks.
     fn burn_unchecked(&mut self, token_ids: &[TokenId]) -> bool;
 
     /// Returns the owner of a token, if it exists.
@@ -199,6 +372,87 @@ pub trait Nep171Controller {
 
     /// Loads the metadata associated with a token.
     fn load_token(&self, token_id: &TokenId) -> Option<Token>;
+
+    /// Blocks all external transfers until [`Nep171Controller::unfreeze_transfers`]
+    /// is called. Mints, burns, and the reversal of in-flight
+    /// `nft_transfer_call` transfers are still allowed. Emits
+    /// [`Nep171FreezeEvent::TransfersFrozen`].
+    ///
+    /// This method performs no access control.
+    fn freeze_transfers(&mut self);
+
+    /// Allows external transfers again. Emits
+    /// [`Nep171FreezeEvent::TransfersUnfrozen`].
+    ///
+    /// This method performs no access control.
+    fn unfreeze_transfers(&mut self);
+
+    /// Whether external transfers are currently frozen.
+    fn transfers_frozen(&self) -> bool;
+
+    /// Locks a token on behalf of `locker_id` (e.g. a marketplace listing or
+    /// a staking position), blocking external transfers of the token until
+    /// [`Nep171Controller::unlock_token`] is called. The token stays in its
+    /// owner's custody. A locked token cannot be burned with
+    /// [`Nep171Controller::burn`] either.
+    ///
+    /// This method performs no access control. In particular, the
+    /// integrating contract should ensure that only the lock holder can
+    /// unlock the token, and not its owner.
+    ///
+    /// # Errors
+    ///
+    /// - If the token does not exist.
+    /// - If the token is already locked.
+    fn lock_token(
+        &mut self,
+        token_id: &TokenId,
+        locker_id: &AccountIdRef,
+    ) -> Result<(), Nep171LockError>;
+
+    /// Removes the lock on a token. Returns the account that held the lock,
+    /// if the token was locked.
+    ///
+    /// This method performs no access control.
+    fn unlock_token(&mut self, token_id: &TokenId) -> Option<AccountId>;
+
+    /// The account holding the lock on a token, if it is locked.
+    fn token_locker(&self, token_id: &TokenId) -> Option<AccountId>;
+
+    /// Whether a token is locked.
+    fn is_locked(&self, token_id: &TokenId) -> bool;
+
+    /// Permanently blocks [`Nep171Controller::mint`], e.g. to finalize the
+    /// supply of a drop. Transfers and burns are unaffected. This cannot be
+    /// undone. Emits [`Nep171FreezeEvent::MintingClosed`].
+    ///
+    /// This method performs no access control.
+    fn close_minting(&mut self);
+
+    /// Whether minting has been permanently closed.
+    fn minting_closed(&self) -> bool;
+
+    /// Blocks [`Nep171Controller::mint`] until
+    /// [`Nep171Controller::resume_minting`] is called. Transfers and burns
+    /// are unaffected. Emits [`Nep171FreezeEvent::MintingPaused`].
+    ///
+    /// This method performs no access control.
+    fn pause_minting(&mut self);
+
+    /// Allows minting again, unless it has been permanently closed. Emits
+    /// [`Nep171FreezeEvent::MintingResumed`].
+    ///
+    /// This method performs no access control.
+    fn resume_minting(&mut self);
+
+    /// Whether minting is currently paused.
+    fn minting_paused(&self) -> bool;
+
+    /// The number of tokens minted and not yet burned. Always `0` unless
+    /// [`Nep171ControllerInternal::track_supply`] is `true`. Tokens minted
+    /// before tracking was enabled, or via
+    /// [`Nep171Controller::mint_unchecked`], are not counted.
+    fn total_supply(&self) -> u64;
 }
 
 /// Authorization for a transfer.
@@ -284,6 +538,28 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
     type LoadTokenMetadata = <Self as Nep171ControllerInternal>::LoadTokenMetadata;
 
     fn external_transfer(&mut self, transfer: &Nep171Transfer) -> Result<(), Nep171TransferError> {
+        if !transfer.revert {
+            if let Some(max_len) = Self::max_transfer_token_id_len() {
+                check_token_id_len(&transfer.token_id, max_len)?;
+            }
+            check_memo_len::<Self>(transfer.memo.as_deref())?;
+
+            if self.transfers_frozen() {
+                return Err(TransfersFrozenError {
+                    token_id: transfer.token_id.clone(),
+                }
+                .into());
+            }
+
+            if let Some(locker_id) = self.token_locker(&transfer.token_id) {
+                return Err(TokenLockedError {
+                    token_id: transfer.token_id.clone(),
+                    locker_id,
+                }
+                .into());
+            }
+        }
+
         match Self::CheckExternalTransfer::check_external_transfer(self, transfer) {
             Ok(current_owner_id) => {
                 Self::TransferHook::hook(self, transfer, |contract| {
@@ -327,7 +603,20 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
             return Ok(());
         }
 
+        if self.minting_closed() {
+            return Err(MintClosedError.into());
+        }
+
+        if self.minting_paused() {
+            return Err(MintPausedError.into());
+        }
+
+        check_memo_len::<Self>(action.memo.as_deref())?;
+        check_unique_token_ids(&action.token_ids)?;
+
         for token_id in &action.token_ids {
+            check_token_id_len(token_id, Self::max_token_id_len())?;
+
             let slot = Self::slot_token_owner(token_id);
             if slot.exists() {
                 return Err(TokenAlreadyExistsError {
@@ -340,6 +629,16 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
         Self::MintHook::hook(self, action, |contract| {
             contract.mint_unchecked(&action.token_ids, &action.receiver_id);
 
+            if Self::track_supply() {
+                let mut slot = Self::slot_total_supply();
+                let total_supply = slot
+                    .read()
+                    .unwrap_or(0)
+                    .checked_add(action.token_ids.len() as u64)
+                    .unwrap_or_else(|| env::panic_str(TOTAL_SUPPLY_OVERFLOW_MESSAGE));
+                slot.write(&total_supply);
+            }
+
             Nep171Event::NftMint(vec![NftMintLog {
                 token_ids: action.token_ids.iter().map(Into::into).collect(),
                 owner_id: action.receiver_id.clone(),
@@ -356,6 +655,8 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
             return Ok(());
         }
 
+        check_unique_token_ids(&action.token_ids)?;
+
         for token_id in &action.token_ids {
             if let Some(actual_owner_id) = self.token_owner(token_id) {
                 if actual_owner_id != action.owner_id.as_ref() {
@@ -372,11 +673,28 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
                 }
                 .into());
             }
+
+            if let Some(locker_id) = self.token_locker(token_id) {
+                return Err(TokenLockedError {
+                    token_id: token_id.clone(),
+                    locker_id,
+                }
+                .into());
+            }
         }
 
         Self::BurnHook::hook(self, action, |contract| {
             contract.burn_unchecked(&action.token_ids);
 
+            if Self::track_supply() {
+                let mut slot = Self::slot_total_supply();
+                let total_supply = slot
+                    .read()
+                    .unwrap_or(0)
+                    .saturating_sub(action.token_ids.len() as u64);
+                slot.write(&total_supply);
+            }
+
             Nep171Event::NftBurn(vec![NftBurnLog {
                 token_ids: action.token_ids.iter().map(Into::into).collect(),
                 owner_id: action.owner_id.clone(),
@@ -394,6 +712,7 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
 
         for token_id in token_ids {
             removed_successfully &= Self::slot_token_owner(token_id).remove();
+            Self::slot_token_lock(token_id).remove();
         }
 
         removed_successfully
@@ -412,6 +731,86 @@ impl<T: Nep171ControllerInternal> Nep171Controller for T {
             extensions_metadata: metadata,
         })
     }
+
+    fn freeze_transfers(&mut self) {
+        Self::slot_transfers_frozen().write(&true);
+        Nep171FreezeEvent::TransfersFrozen.emit();
+    }
+
+    fn unfreeze_transfers(&mut self) {
+        Self::slot_transfers_frozen().remove();
+        Nep171FreezeEvent::TransfersUnfrozen.emit();
+    }
+
+    fn transfers_frozen(&self) -> bool {
+        Self::slot_transfers_frozen().read().unwrap_or(false)
+    }
+
+    fn lock_token(
+        &mut self,
+        token_id: &TokenId,
+        locker_id: &AccountIdRef,
+    ) -> Result<(), Nep171LockError> {
+        if !Self::slot_token_owner(token_id).exists() {
+            return Err(TokenDoesNotExistError {
+                token_id: token_id.clone(),
+            }
+            .into());
+        }
+
+        let mut slot = Self::slot_token_lock(token_id);
+
+        if let Some(locker_id) = slot.read() {
+            return Err(TokenLockedError {
+                token_id: token_id.clone(),
+                locker_id,
+            }
+            .into());
+        }
+
+        slot.write_deref(locker_id);
+
+        Ok(())
+    }
+
+    fn unlock_token(&mut self, token_id: &TokenId) -> Option<AccountId> {
+        Self::slot_token_lock(token_id).take()
+    }
+
+    fn token_locker(&self, token_id: &TokenId) -> Option<AccountId> {
+        Self::slot_token_lock(token_id).read()
+    }
+
+    fn is_locked(&self, token_id: &TokenId) -> bool {
+        Self::slot_token_lock(token_id).exists()
+    }
+
+    fn close_minting(&mut self) {
+        Self::slot_minting_closed().write(&true);
+        Nep171FreezeEvent::MintingClosed.emit();
+    }
+
+    fn minting_closed(&self) -> bool {
+        Self::slot_minting_closed().read().unwrap_or(false)
+    }
+
+    fn pause_minting(&mut self) {
+        Self::slot_minting_paused().write(&true);
+        Nep171FreezeEvent::MintingPaused.emit();
+    }
+
+    fn resume_minting(&mut self) {
+        Self::slot_minting_paused().remove();
+        Nep171FreezeEvent::MintingResumed.emit();
+    }
+
+    fn minting_paused(&self) -> bool {
+        Self::slot_minting_paused().read().unwrap_or(false)
+    }
+
+    fn total_supply(&self) -> u64 {
+        Self::slot_total_supply().read().unwrap_or(0)
+    }
 }
 
 /// Token information structure.
@@ -428,6 +827,19 @@ pub struct Token {
 }
 
 /// Trait for NFT extensions to load token metadata.
+///
+/// [`Nep171Controller::load_token`] (and therefore `nft_token`) loads the
+/// token ID and owner itself, and then lets the contract's loader add
+/// extension fields, each under its own key:
+///
+/// - [`TokenMetadata`](crate::standard::nep177::TokenMetadata) adds the
+///   NEP-177 metadata as `"metadata"`.
+/// - [`TokenApprovals`](crate::standard::nep178::TokenApprovals) adds the
+///   NEP-178 approvals as `"approved_account_ids"`.
+///
+/// Loaders compose as tuples, e.g. `(TokenMetadata, TokenApprovals)` loads
+/// both. The `Nep171` derive macro composes the built-in loaders listed in
+/// its `token_loader` option, and any custom loader given as `token_data`.
 pub trait LoadTokenMetadata<C> {
     /// Load token metadata into `metadata`.
     ///
@@ -464,3 +876,66 @@ impl<C, T: LoadTokenMetadata<C>, U: LoadTokenMetadata<C>> LoadTokenMetadata<C> f
 }
 
 // further variations are technically unnecessary: just use (T, (U, V)) or ((T, U), V)
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn tgas(n: u64) -> Gas {
+        Gas::from_gas(n * 1_000_000_000_000)
+    }
+
+    #[test]
+    fn transfer_call_gas_minimum() {
+        let gas = TransferCallGas::allocate(tgas(35), DEFAULT_RECEIVER_GAS_SHARE).unwrap();
+
+        assert_eq!(gas.receiver, MIN_GAS_FOR_NFT_ON_TRANSFER);
+        assert_eq!(gas.resolver, GAS_FOR_RESOLVE_TRANSFER);
+    }
+
+    #[test]
+    fn transfer_call_gas_insufficient() {
+        let error = TransferCallGas::allocate(tgas(34), DEFAULT_RECEIVER_GAS_SHARE).unwrap_err();
+
+        assert_eq!(
+            error,
+            TransferCallGasError {
+                required: tgas(35),
+                prepaid: tgas(34),
+            },
+        );
+        assert_eq!(
+            error.to_string(),
+            "Insufficient gas for nft_transfer_call: 35000000000000 required, 34000000000000 attached",
+        );
+    }
+
+    #[test]
+    fn transfer_call_gas_default_share() {
+        let gas = TransferCallGas::allocate(tgas(300), DEFAULT_RECEIVER_GAS_SHARE).unwrap();
+
+        assert_eq!(gas.receiver, tgas(270));
+        assert_eq!(gas.resolver, GAS_FOR_RESOLVE_TRANSFER);
+    }
+
+    #[test]
+    fn transfer_call_gas_split_share() {
+        let gas = TransferCallGas::allocate(tgas(135), 75).unwrap();
+
+        assert_eq!(gas.receiver, tgas(5 + 75));
+        assert_eq!(gas.resolver, tgas(5 + 25));
+
+        let gas = TransferCallGas::allocate(tgas(135), 0).unwrap();
+
+        assert_eq!(gas.receiver, MIN_GAS_FOR_NFT_ON_TRANSFER);
+        assert_eq!(gas.resolver, tgas(105));
+    }
+
+    #[test]
+    fn transfer_call_gas_share_capped() {
+        assert_eq!(
+            TransferCallGas::allocate(tgas(100), u8::MAX),
+            TransferCallGas::allocate(tgas(100), 100),
+        );
+    }
+}
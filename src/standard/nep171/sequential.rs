@@ -0,0 +1,118 @@
+//! Sequential token ID generation.
+//!
+//! [`SequentialTokenIds`] keeps a `u64` counter in a slot under the NEP-171
+//! root and yields IDs of the form `{prefix}{n}`. The counter is never reset,
+//! so IDs remain unique across contract upgrades as long as the NEP-171 root
+//! is preserved.
+
+use near_sdk::{env, AccountIdRef};
+
+use crate::{
+    slot::Slot,
+    standard::nep177::{Nep177Controller, TokenMetadata},
+};
+
+use super::{
+    action::Nep171Mint, error::Nep171MintError, Nep171Controller, Nep171ControllerInternal,
+    StorageKey, TokenId,
+};
+
+const TOKEN_ID_OVERFLOW_MESSAGE: &str = "Sequential token ID overflow";
+
+/// Generates sequential token IDs for NEP-171 contracts.
+pub trait SequentialTokenIds {
+    /// Storage slot for the counter of the next token ID.
+    fn slot_next_token_id() -> Slot<u64>;
+
+    /// Storage slot for the token ID prefix.
+    fn slot_token_id_prefix() -> Slot<String>;
+
+    /// Sets the prefix prepended to generated token IDs. Usually called
+    /// during contract initialization.
+    fn set_token_id_prefix(&mut self, prefix: &str);
+
+    /// The prefix prepended to generated token IDs. Defaults to the empty
+    /// string.
+    fn token_id_prefix(&self) -> String;
+
+    /// The token ID that will be yielded by the next call to
+    /// [`SequentialTokenIds::next_token_id`].
+    fn peek_next_token_id(&self) -> TokenId;
+
+    /// Yields a fresh token ID and advances the counter.
+    fn next_token_id(&mut self) -> TokenId;
+
+    /// Mints a token with the next sequential ID to `owner_id`.
+    ///
+    /// # Errors
+    ///
+    /// - If the generated token ID already exists (e.g. if it was minted
+    ///   manually).
+    fn mint_next(&mut self, owner_id: &AccountIdRef) -> Result<TokenId, Nep171MintError>;
+
+    /// Mints a token with the next sequential ID to `owner_id`, with NEP-177
+    /// metadata.
+    ///
+    /// # Errors
+    ///
+    /// - If the generated token ID already exists (e.g. if it was minted
+    ///   manually).
+    fn mint_next_with_metadata(
+        &mut self,
+        owner_id: &AccountIdRef,
+        metadata: &TokenMetadata,
+    ) -> Result<TokenId, Nep171MintError>
+    where
+        Self: Nep177Controller;
+}
+
+impl<T: Nep171ControllerInternal> SequentialTokenIds for T {
+    fn slot_next_token_id() -> Slot<u64> {
+        <Self as Nep171ControllerInternal>::root().field(StorageKey::NextTokenId)
+    }
+
+    fn slot_token_id_prefix() -> Slot<String> {
+        <Self as Nep171ControllerInternal>::root().field(StorageKey::TokenIdPrefix)
+    }
+
+    fn set_token_id_prefix(&mut self, prefix: &str) {
+        Self::slot_token_id_prefix().write_deref(prefix);
+    }
+
+    fn token_id_prefix(&self) -> String {
+        Self::slot_token_id_prefix().read().unwrap_or_default()
+    }
+
+    fn peek_next_token_id(&self) -> TokenId {
+        let n = Self::slot_next_token_id().read().unwrap_or(0);
+        format!("{}{n}", self.token_id_prefix())
+    }
+
+    fn next_token_id(&mut self) -> TokenId {
+        let mut slot = Self::slot_next_token_id();
+        let n = slot.read().unwrap_or(0);
+        let next = n
+            .checked_add(1)
+            .unwrap_or_else(|| env::panic_str(TOKEN_ID_OVERFLOW_MESSAGE));
+        slot.write(&next);
+        format!("{}{n}", self.token_id_prefix())
+    }
+
+    fn mint_next(&mut self, owner_id: &AccountIdRef) -> Result<TokenId, Nep171MintError> {
+        let token_id = self.next_token_id();
+        self.mint(&Nep171Mint::new(vec![token_id.clone()], owner_id))?;
+        Ok(token_id)
+    }
+
+    fn mint_next_with_metadata(
+        &mut self,
+        owner_id: &AccountIdRef,
+        metadata: &TokenMetadata,
+    ) -> Result<TokenId, Nep171MintError>
+    where
+        Self: Nep177Controller,
+    {
+        let token_id = self.next_token_id();
+        self.mint_with_metadata(&token_id, owner_id, metadata)
+    }
+}
@@ -68,6 +68,89 @@ impl ContractMetadata {
             reference_hash: None,
         }
     }
+
+    /// Checks that the metadata is well-formed.
+    ///
+    /// # Errors
+    ///
+    /// - If `name` or `symbol` is empty.
+    /// - If `reference` is present without `reference_hash`.
+    pub fn validate(&self) -> Result<(), InvalidContractMetadataError> {
+        if self.name.is_empty() {
+            return Err(InvalidContractMetadataError::EmptyName);
+        }
+        if self.symbol.is_empty() {
+            return Err(InvalidContractMetadataError::EmptySymbol);
+        }
+        if self.reference.is_some() && self.reference_hash.is_none() {
+            return Err(InvalidContractMetadataError::MissingReferenceHash);
+        }
+        Ok(())
+    }
+}
+
+/// Reasons contract metadata may be rejected.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum InvalidContractMetadataError {
+    /// The contract name is empty.
+    #[error("Contract metadata name must not be empty")]
+    EmptyName,
+    /// The contract symbol is empty.
+    #[error("Contract metadata symbol must not be empty")]
+    EmptySymbol,
+    /// A reference was provided without a reference hash.
+    #[error("Contract metadata reference requires a reference hash")]
+    MissingReferenceHash,
+}
+
+/// Partial update to [`ContractMetadata`]. Only the fields that are `Some`
+/// are applied. Optional fields cannot be cleared with a patch; use
+/// [`Nep177Controller::set_contract_metadata`] for that.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[near(serializers = [borsh, json])]
+pub struct ContractMetadataPatch {
+    /// New value for [`ContractMetadata::name`].
+    pub name: Option<String>,
+    /// New value for [`ContractMetadata::symbol`].
+    pub symbol: Option<String>,
+    /// New value for [`ContractMetadata::icon`].
+    pub icon: Option<String>,
+    /// New value for [`ContractMetadata::base_uri`].
+    pub base_uri: Option<String>,
+    /// New value for [`ContractMetadata::reference`].
+    pub reference: Option<String>,
+    /// New value for [`ContractMetadata::reference_hash`].
+    pub reference_hash: Option<String>,
+}
+
+impl ContractMetadataPatch {
+    /// Whether the patch would not change any fields.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Applies the `Some` fields of this patch to `metadata`.
+    pub fn apply(self, metadata: &mut ContractMetadata) {
+        if let Some(name) = self.name {
+            metadata.name = name;
+        }
+        if let Some(symbol) = self.symbol {
+            metadata.symbol = symbol;
+        }
+        if self.icon.is_some() {
+            metadata.icon = self.icon;
+        }
+        if self.base_uri.is_some() {
+            metadata.base_uri = self.base_uri;
+        }
+        if self.reference.is_some() {
+            metadata.reference = self.reference;
+        }
+        if self.reference_hash.is_some() {
+            metadata.reference_hash = self.reference_hash;
+        }
+    }
 }
 
 /// Non-fungible token metadata.
@@ -108,6 +191,54 @@ impl TokenMetadata {
         Self::default()
     }
 
+    /// Starts building a `TokenMetadata`. Same as [`TokenMetadata::new`];
+    /// fields that are not set remain `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use near_sdk_contract_tools::standard::nep177::TokenMetadata;
+    ///
+    /// let metadata = TokenMetadata::builder()
+    ///     .title("Token #1")
+    ///     .description("The first token")
+    ///     .build();
+    ///
+    /// assert_eq!(metadata.media, None);
+    /// ```
+    #[must_use]
+    pub fn builder() -> Self {
+        Self::new()
+    }
+
+    /// Finishes building a `TokenMetadata`.
+    #[must_use]
+    pub fn build(self) -> Self {
+        self
+    }
+
+    /// Fills every field that is `None` with the corresponding field of
+    /// `template`. Fields that are already set are kept.
+    #[must_use]
+    pub fn with_defaults(self, template: &TokenMetadata) -> Self {
+        Self {
+            title: self.title.or_else(|| template.title.clone()),
+            description: self.description.or_else(|| template.description.clone()),
+            media: self.media.or_else(|| template.media.clone()),
+            media_hash: self.media_hash.or_else(|| template.media_hash.clone()),
+            copies: self.copies.or(template.copies),
+            issued_at: self.issued_at.or(template.issued_at),
+            expires_at: self.expires_at.or(template.expires_at),
+            starts_at: self.starts_at.or(template.starts_at),
+            updated_at: self.updated_at.or(template.updated_at),
+            extra: self.extra.or_else(|| template.extra.clone()),
+            reference: self.reference.or_else(|| template.reference.clone()),
+            reference_hash: self
+                .reference_hash
+                .or_else(|| template.reference_hash.clone()),
+        }
+    }
+
     /// Set the title.
     #[must_use]
     pub fn title(mut self, title: impl Into<String>) -> Self {
@@ -221,6 +352,7 @@ impl<C: Nep177Controller> LoadTokenMetadata<C> for TokenMetadata {
 enum StorageKey<'a> {
     ContractMetadata,
     TokenMetadata(&'a TokenId),
+    DefaultTokenMetadata,
 }
 
 /// Internal functions for [`Nep177Controller`].
@@ -242,11 +374,22 @@ pub trait Nep177ControllerInternal {
     fn slot_token_metadata(token_id: &TokenId) -> Slot<TokenMetadata> {
         Self::root().field(StorageKey::TokenMetadata(token_id))
     }
+
+    /// Storage slot for the template merged into the metadata of newly
+    /// minted tokens.
+    #[must_use]
+    fn slot_default_token_metadata() -> Slot<TokenMetadata> {
+        Self::root().field(StorageKey::DefaultTokenMetadata)
+    }
 }
 
 /// Functions for managing non-fungible tokens with attached metadata, NEP-177.
 pub trait Nep177Controller {
-    /// Mint a new token with metadata.
+    /// Mint a new token with metadata. If a default template has been set
+    /// with [`Nep177Controller::set_default_token_metadata`], any fields of
+    /// `metadata` that are `None` are filled from the template.
+    ///
+    /// Returns the minted token ID.
     ///
     /// # Errors
     ///
@@ -256,7 +399,21 @@ pub trait Nep177Controller {
         token_id: &TokenId,
         owner_id: &AccountIdRef,
         metadata: &TokenMetadata,
-    ) -> Result<(), Nep171MintError>;
+    ) -> Result<TokenId, Nep171MintError>;
+
+    /// Mint multiple tokens with metadata to the same owner, emitting a
+    /// single mint event. Default metadata is applied as in
+    /// [`Nep177Controller::mint_with_metadata`]. Returns the minted token
+    /// IDs, in order.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the token IDs already exists.
+    fn mint_batch_with_metadata(
+        &mut self,
+        owner_id: &AccountIdRef,
+        tokens: &[(TokenId, TokenMetadata)],
+    ) -> Result<Vec<TokenId>, Nep171MintError>;
 
     /// Burn a token with metadata.
     ///
@@ -270,6 +427,19 @@ pub trait Nep177Controller {
         owner_id: &AccountId,
     ) -> Result<(), Nep171BurnError>;
 
+    /// Burn multiple tokens owned by the same account and remove their
+    /// metadata, emitting a single burn event.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the token IDs does not exist.
+    /// - If any of the tokens is not owned by the expected owner.
+    fn burn_batch_with_metadata(
+        &mut self,
+        token_ids: &[TokenId],
+        owner_id: &AccountIdRef,
+    ) -> Result<(), Nep171BurnError>;
+
     /// Sets the metadata for a token ID without checking whether the token
     /// exists, etc. and emits an [`Nep171Event::NftMetadataUpdate`] event.
     fn set_token_metadata_unchecked(
@@ -292,11 +462,35 @@ pub trait Nep177Controller {
     /// Sets the contract metadata and emits an [`Nep171Event::ContractMetadataUpdate`] event.
     fn set_contract_metadata(&mut self, metadata: &ContractMetadata);
 
+    /// Applies a partial update to the stored contract metadata, validates
+    /// the result, and emits an [`Nep171Event::ContractMetadataUpdate`]
+    /// event. An empty patch is a no-op: nothing is written and no event is
+    /// emitted.
+    ///
+    /// # Errors
+    ///
+    /// - If the updated metadata fails [`ContractMetadata::validate`]. The
+    ///   stored metadata is not modified.
+    fn update_contract_metadata(
+        &mut self,
+        patch: ContractMetadataPatch,
+    ) -> Result<(), InvalidContractMetadataError>;
+
     /// Returns the contract metadata.
     fn contract_metadata(&self) -> ContractMetadata;
 
     /// Returns the metadata for a token ID.
     fn token_metadata(&self, token_id: &TokenId) -> Option<TokenMetadata>;
+
+    /// Sets (or, with `None`, removes) the template that
+    /// [`Nep177Controller::mint_with_metadata`] merges with per-token
+    /// metadata, e.g. for collections that share media or a reference. Does
+    /// not affect tokens that have already been minted.
+    fn set_default_token_metadata(&mut self, template: Option<&TokenMetadata>);
+
+    /// Returns the template set by
+    /// [`Nep177Controller::set_default_token_metadata`], if any.
+    fn default_token_metadata(&self) -> Option<TokenMetadata>;
 }
 
 /// Error returned when a token update fails.
@@ -330,15 +524,60 @@ impl<T: Nep177ControllerInternal + Nep171Controller> Nep177Controller for T {
             .emit();
     }
 
+    fn update_contract_metadata(
+        &mut self,
+        patch: ContractMetadataPatch,
+    ) -> Result<(), InvalidContractMetadataError> {
+        if patch.is_empty() {
+            return Ok(());
+        }
+
+        let mut metadata = self.contract_metadata();
+        patch.apply(&mut metadata);
+        metadata.validate()?;
+        self.set_contract_metadata(&metadata);
+
+        Ok(())
+    }
+
     fn mint_with_metadata(
         &mut self,
         token_id: &TokenId,
         owner_id: &AccountIdRef,
         metadata: &TokenMetadata,
-    ) -> Result<(), Nep171MintError> {
+    ) -> Result<TokenId, Nep171MintError> {
         self.mint(&Nep171Mint::new(vec![token_id.clone()], owner_id))?;
-        self.set_token_metadata_unchecked(token_id, Some(metadata));
-        Ok(())
+        match self.default_token_metadata() {
+            Some(template) => {
+                let metadata = metadata.clone().with_defaults(&template);
+                self.set_token_metadata_unchecked(token_id, Some(&metadata));
+            }
+            None => self.set_token_metadata_unchecked(token_id, Some(metadata)),
+        }
+        Ok(token_id.clone())
+    }
+
+    fn mint_batch_with_metadata(
+        &mut self,
+        owner_id: &AccountIdRef,
+        tokens: &[(TokenId, TokenMetadata)],
+    ) -> Result<Vec<TokenId>, Nep171MintError> {
+        let token_ids = tokens
+            .iter()
+            .map(|(token_id, _)| token_id.clone())
+            .collect::<Vec<_>>();
+        self.mint(&Nep171Mint::new(token_ids.clone(), owner_id))?;
+        let template = self.default_token_metadata();
+        for (token_id, metadata) in tokens {
+            match &template {
+                Some(template) => {
+                    let metadata = metadata.clone().with_defaults(template);
+                    self.set_token_metadata_unchecked(token_id, Some(&metadata));
+                }
+                None => self.set_token_metadata_unchecked(token_id, Some(metadata)),
+            }
+        }
+        Ok(token_ids)
     }
 
     fn burn_with_metadata(
@@ -351,6 +590,18 @@ impl<T: Nep177ControllerInternal + Nep171Controller> Nep177Controller for T {
         Ok(())
     }
 
+    fn burn_batch_with_metadata(
+        &mut self,
+        token_ids: &[TokenId],
+        owner_id: &AccountIdRef,
+    ) -> Result<(), Nep171BurnError> {
+        self.burn(&Nep171Burn::new(token_ids.to_vec(), owner_id))?;
+        for token_id in token_ids {
+            self.set_token_metadata_unchecked(token_id, None);
+        }
+        Ok(())
+    }
+
     fn set_token_metadata_unchecked(
         &mut self,
         token_id: &TokenId,
@@ -373,6 +624,14 @@ impl<T: Nep177ControllerInternal + Nep171Controller> Nep177Controller for T {
             .read()
             .unwrap_or_else(|| env::panic_str(CONTRACT_METADATA_NOT_INITIALIZED_ERROR))
     }
+
+    fn set_default_token_metadata(&mut self, template: Option<&TokenMetadata>) {
+        Self::slot_default_token_metadata().set(template);
+    }
+
+    fn default_token_metadata(&self) -> Option<TokenMetadata> {
+        Self::slot_default_token_metadata().read()
+    }
 }
 
 // separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
@@ -386,3 +645,91 @@ mod ext {
         fn nft_metadata(&self) -> ContractMetadata;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::{near, PanicOnDefault};
+    use near_sdk_contract_tools_macros::Nep171;
+
+    use super::*;
+
+    #[test]
+    fn builder_leaves_unset_fields_none() {
+        let metadata = TokenMetadata::builder().title("title").copies(3).build();
+
+        assert_eq!(
+            metadata,
+            TokenMetadata {
+                title: Some("title".to_string()),
+                copies: Some(U64(3)),
+                ..TokenMetadata::default()
+            },
+        );
+        assert_eq!(TokenMetadata::builder().build(), TokenMetadata::default());
+    }
+
+    #[test]
+    fn with_defaults_keeps_set_fields() {
+        let template = TokenMetadata::builder()
+            .title("template")
+            .media("https://example.com/media.png")
+            .build();
+
+        let metadata = TokenMetadata::builder()
+            .title("token")
+            .build()
+            .with_defaults(&template);
+
+        assert_eq!(metadata.title.as_deref(), Some("token"));
+        assert_eq!(
+            metadata.media.as_deref(),
+            Some("https://example.com/media.png"),
+        );
+        assert_eq!(metadata.description, None);
+        assert_eq!(metadata.reference, None);
+    }
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[nep171(token_data = "TokenMetadata", crate = "crate")]
+    #[near(contract_state)]
+    struct Contract {}
+
+    impl Nep177ControllerInternal for Contract {}
+
+    #[test]
+    fn mint_merges_default_template() {
+        let mut contract = Contract {};
+        let alice: AccountId = "alice".parse().unwrap();
+
+        contract.set_default_token_metadata(Some(
+            &TokenMetadata::builder()
+                .description("shared")
+                .media("https://example.com/media.png")
+                .build(),
+        ));
+
+        let token_id = "1".to_string();
+        contract
+            .mint_with_metadata(
+                &token_id,
+                &alice,
+                &TokenMetadata::builder()
+                    .title("#1")
+                    .description("own")
+                    .build(),
+            )
+            .unwrap();
+
+        let metadata = contract.token_metadata(&token_id).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("#1"));
+        assert_eq!(metadata.description.as_deref(), Some("own"));
+        assert_eq!(
+            metadata.media.as_deref(),
+            Some("https://example.com/media.png"),
+        );
+        assert_eq!(metadata.extra, None);
+
+        contract.set_default_token_metadata(None);
+        assert_eq!(contract.default_token_metadata(), None);
+    }
+}
@@ -18,7 +18,9 @@ pub trait Nep178 {
 
     fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId);
 
-    fn nft_revoke_all(&mut self, token_id: TokenId);
+    /// Returns the accounts whose approvals were revoked. (The standard
+    /// does not specify a return value, so callers may ignore it.)
+    fn nft_revoke_all(&mut self, token_id: TokenId) -> Vec<AccountId>;
 
     fn nft_is_approved(
         &self,
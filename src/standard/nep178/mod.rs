@@ -72,7 +72,7 @@ impl<C: Nep178Controller> Hook<C, Nep171Burn<'_>> for TokenApprovals {
     fn hook<R>(contract: &mut C, args: &Nep171Burn<'_>, f: impl FnOnce(&mut C) -> R) -> R {
         let r = f(contract);
         for token_id in &args.token_ids {
-            contract.revoke_all_unchecked(token_id);
+            contract.clear_approvals_unchecked(token_id);
         }
         r
     }
@@ -177,6 +177,21 @@ pub trait Nep178Controller {
     /// if it exceeds the maximum number of approvals.
     fn approve_unchecked(&mut self, token_id: &TokenId, account_id: &AccountIdRef) -> ApprovalId;
 
+    /// Approve a batch of tokens, stopping at the first action that fails.
+    /// Hooks are invoked once per action.
+    ///
+    /// Approvals created before the failing action are not rolled back, so
+    /// callers should panic on error to revert the whole batch, as the
+    /// generated `nft_approve_batch` does.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Nep178Controller::approve`], for any action in the batch.
+    fn approve_batch(
+        &mut self,
+        actions: &[Nep178Approve<'_>],
+    ) -> Result<Vec<ApprovalId>, Nep178ApproveError>;
+
     /// Revoke approval for an account to transfer token.
     ///
     /// # Errors
@@ -189,16 +204,35 @@ pub trait Nep178Controller {
     /// the account is approved.
     fn revoke_unchecked(&mut self, token_id: &TokenId, account_id: &AccountIdRef);
 
-    /// Revoke all approvals for a token.
+    /// Revoke a batch of approvals, stopping at the first action that fails.
+    /// Like [`Nep178Controller::approve_batch`], earlier revocations are not
+    /// rolled back on error, and hooks are invoked once per action.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Nep178Controller::revoke`], for any action in the batch.
+    fn revoke_batch(&mut self, actions: &[Nep178Revoke<'_>]) -> Result<(), Nep178RevokeError>;
+
+    /// Revoke all approvals for a token. Returns the accounts whose
+    /// approvals were revoked, sorted by account ID.
     ///
     /// # Errors
     ///
     /// - If the acting account is not authorized to revoke approvals for the token.
-    fn revoke_all(&mut self, action: &Nep178RevokeAll<'_>) -> Result<(), Nep178RevokeAllError>;
+    fn revoke_all(
+        &mut self,
+        action: &Nep178RevokeAll<'_>,
+    ) -> Result<Vec<AccountId>, Nep178RevokeAllError>;
 
     /// Revoke all approvals for a token without checking current owner.
     fn revoke_all_unchecked(&mut self, token_id: &TokenId);
 
+    /// Removes all approval state for a token, including its approval ID
+    /// counter, without checking current owner. Does not emit events or run
+    /// hooks. Used when a token is burned so that a later re-mint of the same
+    /// token ID starts with a clean slate.
+    fn clear_approvals_unchecked(&mut self, token_id: &TokenId);
+
     /// Get the approval ID for an account, if it is approved for a token.
     fn get_approval_id_for(
         &self,
@@ -276,6 +310,13 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         })
     }
 
+    fn approve_batch(
+        &mut self,
+        actions: &[Nep178Approve<'_>],
+    ) -> Result<Vec<ApprovalId>, Nep178ApproveError> {
+        actions.iter().map(|action| self.approve(action)).collect()
+    }
+
     fn revoke_unchecked(&mut self, token_id: &TokenId, account_id: &AccountIdRef) {
         let mut slot = Self::slot_token_approvals(token_id);
         let Some(mut approvals) = slot.read() else {
@@ -327,7 +368,14 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         })
     }
 
-    fn revoke_all(&mut self, action: &Nep178RevokeAll) -> Result<(), Nep178RevokeAllError> {
+    fn revoke_batch(&mut self, actions: &[Nep178Revoke<'_>]) -> Result<(), Nep178RevokeError> {
+        actions.iter().try_for_each(|action| self.revoke(action))
+    }
+
+    fn revoke_all(
+        &mut self,
+        action: &Nep178RevokeAll,
+    ) -> Result<Vec<AccountId>, Nep178RevokeAllError> {
         // owner check
         if self.token_owner(&action.token_id).as_deref() != Some(action.current_owner_id.as_ref()) {
             return Err(UnauthorizedError {
@@ -337,10 +385,16 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
             .into());
         }
 
+        let mut revoked = self
+            .get_approvals_for(&action.token_id)
+            .into_keys()
+            .collect::<Vec<_>>();
+        revoked.sort_unstable();
+
         Self::RevokeAllHook::hook(self, action, |contract| {
             contract.revoke_all_unchecked(&action.token_id);
 
-            Ok(())
+            Ok(revoked)
         })
     }
 
@@ -356,6 +410,16 @@ impl<T: Nep178ControllerInternal + Nep171Controller> Nep178Controller for T {
         }
     }
 
+    fn clear_approvals_unchecked(&mut self, token_id: &TokenId) {
+        let mut slot = Self::slot_token_approvals(token_id);
+        let Some(mut approvals) = slot.read() else {
+            return;
+        };
+
+        approvals.accounts.clear();
+        slot.remove();
+    }
+
     fn get_approval_id_for(
         &self,
         token_id: &TokenId,
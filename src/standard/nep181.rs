@@ -129,6 +129,67 @@ pub trait Nep181Controller {
         owner_id: &AccountIdRef,
         f: impl FnOnce(&UnorderedSet<TokenId>) -> T,
     ) -> T;
+
+    /// Scan up to `scan_limit` enumerated tokens, starting at enumeration
+    /// index `from_index`, and return those that satisfy `predicate`, in
+    /// enumeration order. Tokens outside of the scanned range are never
+    /// loaded. Pass [`TokenPage::next_index`] as `from_index` to continue
+    /// the scan.
+    fn tokens_where(
+        &self,
+        from_index: usize,
+        scan_limit: usize,
+        predicate: impl FnMut(&Token) -> bool,
+    ) -> TokenPage;
+
+    /// Like [`Nep181Controller::tokens_where`], but only considers tokens
+    /// owned by `owner_id`. Indices refer to the enumeration of the owner's
+    /// tokens.
+    fn tokens_for_owner_where(
+        &self,
+        owner_id: &AccountIdRef,
+        from_index: usize,
+        scan_limit: usize,
+        predicate: impl FnMut(&Token) -> bool,
+    ) -> TokenPage;
+}
+
+/// Tokens returned by [`Nep181Controller::tokens_where`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenPage {
+    /// Scanned tokens that satisfy the predicate, in enumeration order.
+    pub tokens: Vec<Token>,
+    /// Enumeration index of the first token that was not scanned, or `None`
+    /// if the scan reached the end of the enumeration.
+    pub next_index: Option<usize>,
+}
+
+fn load_tokens_where<C: Nep171Controller>(
+    contract: &C,
+    tokens: &UnorderedSet<TokenId>,
+    from_index: usize,
+    scan_limit: usize,
+    mut predicate: impl FnMut(&Token) -> bool,
+) -> TokenPage {
+    let len = usize::try_from(tokens.len()).unwrap_or(usize::MAX);
+    let end = from_index.saturating_add(scan_limit).min(len);
+
+    let tokens = tokens
+        .iter()
+        .skip(from_index)
+        .take(scan_limit)
+        .map(|token_id| {
+            contract.load_token(&token_id).unwrap_or_else(|| {
+                env::panic_str(&format!("Inconsistent state: Token `{token_id}` is in the enumeration set but its metadata could not be loaded."))
+            })
+        })
+        .filter(|token| predicate(token))
+        .collect();
+
+    TokenPage {
+        tokens,
+        next_index: (end < len).then_some(end),
+    }
 }
 
 impl<T: Nep181ControllerInternal + Nep171Controller> Nep181Controller for T {
@@ -217,6 +278,29 @@ impl<T: Nep181ControllerInternal + Nep171Controller> Nep181Controller for T {
             .read()
             .unwrap_or_else(|| UnorderedSet::new(StorageKey::OwnerTokens(owner_id))))
     }
+
+    fn tokens_where(
+        &self,
+        from_index: usize,
+        scan_limit: usize,
+        predicate: impl FnMut(&Token) -> bool,
+    ) -> TokenPage {
+        self.with_tokens(|tokens| {
+            load_tokens_where(self, tokens, from_index, scan_limit, predicate)
+        })
+    }
+
+    fn tokens_for_owner_where(
+        &self,
+        owner_id: &AccountIdRef,
+        from_index: usize,
+        scan_limit: usize,
+        predicate: impl FnMut(&Token) -> bool,
+    ) -> TokenPage {
+        self.with_tokens_for_owner(owner_id, |tokens| {
+            load_tokens_where(self, tokens, from_index, scan_limit, predicate)
+        })
+    }
 }
 
 // separate module with re-export because ext_contract doesn't play well with #![warn(missing_docs)]
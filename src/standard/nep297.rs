@@ -30,7 +30,9 @@ use near_sdk::{
 /// e.emit();
 /// ```
 pub trait Event {
-    /// Converts the event into an NEP-297 event-formatted string.
+    /// Converts the event into an NEP-297 event-formatted string: exactly
+    /// what [`Event::emit`] logs. Does not require a VM context, so it can
+    /// be used in unit tests and off-chain tooling.
     fn to_event_string(&self) -> String;
 
     /// Emits the event string to the blockchain.
@@ -64,6 +66,20 @@ where
 }
 
 /// This type can be converted into an [`EventLog`] struct.
+///
+/// Usually implemented with `#[derive(Nep297)]`, which rejects event names
+/// that collide after renaming.
+///
+/// It also rejects event names containing characters other than ASCII
+/// alphanumerics, `_`, `-`, and spaces, unless they are explicitly allowed:
+///
+/// ```
+/// use near_sdk_contract_tools::Nep297;
+///
+/// #[derive(Nep297)]
+/// #[nep297(standard = "x-chars", version = "1.0.0", name = "nft.mint", allowed_chars = "_.")]
+/// struct NftMint;
+/// ```
 pub trait ToEventLog {
     /// Metadata associated with the event.
     type Data;
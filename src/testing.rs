@@ -0,0 +1,145 @@
+//! Helpers for inspecting NEP-297 events in tests.
+//!
+//! Enabled by the `testing` feature.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use near_sdk_contract_tools::testing::expect_event;
+//!
+//! let result = alice.call(contract.id(), "ft_transfer") /* ... */;
+//! let event = expect_event(result.logs(), "nep141", "ft_transfer");
+//! assert_eq!(event.data[0]["amount"], "100");
+//! ```
+use near_sdk::serde_json::{self, Value};
+
+use crate::standard::nep297::EventLog;
+
+const EVENT_PREFIX: &str = "EVENT_JSON:";
+
+/// An event log with untyped JSON data, as parsed from a contract's logs.
+pub type ParsedEventLog = EventLog<'static, Value>;
+
+/// Parses a single log line as an NEP-297 event. Returns `None` if the line
+/// is not an event log (e.g. a plain `log!` string).
+#[must_use]
+pub fn parse_event_log(log: &str) -> Option<ParsedEventLog> {
+    let json = log.strip_prefix(EVENT_PREFIX)?;
+    serde_json::from_str(json).ok()
+}
+
+/// Parses all of the event logs out of a list of log lines, skipping any
+/// lines that are not events.
+pub fn parse_event_logs<S: AsRef<str>>(logs: impl IntoIterator<Item = S>) -> Vec<ParsedEventLog> {
+    logs.into_iter()
+        .filter_map(|log| parse_event_log(log.as_ref()))
+        .collect()
+}
+
+/// Finds all events in `logs` matching `standard` and `event`.
+pub fn find_events<S: AsRef<str>>(
+    logs: impl IntoIterator<Item = S>,
+    standard: &str,
+    event: &str,
+) -> Vec<ParsedEventLog> {
+    parse_event_logs(logs)
+        .into_iter()
+        .filter(|e| e.standard == standard && e.event == event)
+        .collect()
+}
+
+/// Asserts that exactly one event matching `standard` and `event` was
+/// emitted, and returns it.
+///
+/// # Panics
+///
+/// If there is not exactly one matching event.
+pub fn expect_event<S: AsRef<str>>(
+    logs: impl IntoIterator<Item = S>,
+    standard: &str,
+    event: &str,
+) -> ParsedEventLog {
+    let events = parse_event_logs(logs);
+    let mut matching = events
+        .iter()
+        .filter(|e| e.standard == standard && e.event == event);
+
+    match (matching.next(), matching.next()) {
+        (Some(e), None) => e.clone(),
+        (None, _) => panic!(
+            "Expected event `{standard}::{event}`, found: {:?}",
+            events
+                .iter()
+                .map(|e| format!("{}::{}", e.standard, e.event))
+                .collect::<Vec<_>>(),
+        ),
+        (Some(_), Some(_)) => {
+            panic!("Expected exactly one event `{standard}::{event}`, found multiple")
+        }
+    }
+}
+
+/// Asserts that no event matching `standard` and `event` was emitted.
+///
+/// # Panics
+///
+/// If a matching event is found.
+pub fn expect_no_event<S: AsRef<str>>(
+    logs: impl IntoIterator<Item = S>,
+    standard: &str,
+    event: &str,
+) {
+    let found = find_events(logs, standard, event);
+    assert!(
+        found.is_empty(),
+        "Expected no event `{standard}::{event}`, found {}",
+        found.len(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::{json_types::U128, serde_json::json, AccountIdRef};
+
+    use super::*;
+    use crate::standard::{
+        nep141::{FtTransferData, Nep141Event},
+        nep297::Event,
+    };
+
+    #[test]
+    fn round_trip_ft_transfer() {
+        let emitted = Nep141Event::FtTransfer(vec![FtTransferData {
+            old_owner_id: AccountIdRef::new_or_panic("from.near").into(),
+            new_owner_id: AccountIdRef::new_or_panic("to.near").into(),
+            amount: U128(42),
+            memo: Some("memo".into()),
+        }])
+        .to_event_string();
+
+        let logs = ["not an event", emitted.as_str(), "EVENT_JSON:{broken"];
+
+        assert_eq!(parse_event_logs(logs).len(), 1);
+
+        let event = expect_event(logs, "nep141", "ft_transfer");
+
+        assert_eq!(event.version, "1.0.0");
+        assert_eq!(
+            event.data,
+            json!([{
+                "old_owner_id": "from.near",
+                "new_owner_id": "to.near",
+                "amount": "42",
+                "memo": "memo",
+            }]),
+        );
+
+        expect_no_event(logs, "nep141", "ft_mint");
+    }
+
+    #[test]
+    #[should_panic = "Expected event `nep141::ft_mint`"]
+    fn expect_event_missing() {
+        expect_event(["log"], "nep141", "ft_mint");
+    }
+}
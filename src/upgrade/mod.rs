@@ -29,7 +29,16 @@
 //! schema. If the new contract has a different storage schema from the old
 //! contract and does not migrate the state schema, the contract may become
 //! unusable.
-use near_sdk::Gas;
+//!
+//! # Errors
+//!
+//! Before scheduling the deployment, the upgrade functions check that the
+//! new code is not empty and that enough gas remains to run the post-upgrade
+//! function, panicking with a readable [`UpgradeError`] message otherwise.
+//! The deployment and the post-upgrade function call are batched together,
+//! so if the post-upgrade function fails, the deployment is reverted as well.
+use near_sdk::{env, Gas};
+use thiserror::Error;
 
 /// Default value for the name of the function that will be called after
 /// upgrade (usually a migrate function).
@@ -39,11 +48,51 @@ pub const DEFAULT_POST_UPGRADE_METHOD_ARGS: Vec<u8> = vec![];
 /// Guarantee the post-upgrade function receives at least this much gas by
 /// default.
 pub const DEFAULT_POST_UPGRADE_MINIMUM_GAS: Gas = Gas::from_gas(15_000_000_000_000);
+/// Gas reserved by default for finishing the upgrade function call itself,
+/// in addition to [`PostUpgrade::minimum_gas`].
+pub const DEFAULT_UPGRADE_GAS_LEFTOVER: Gas = Gas::from_gas(5_000_000_000_000);
+/// Gas charged for a `DEPLOY_CONTRACT` action regardless of the code size
+/// (send plus execution fee).
+pub const DEPLOY_CONTRACT_BASE_GAS: Gas = Gas::from_gas(184_765_750_000 * 2);
+/// Gas charged per byte of code for a `DEPLOY_CONTRACT` action (send plus
+/// execution fee).
+pub const DEPLOY_CONTRACT_GAS_PER_BYTE: Gas = Gas::from_gas(6_812_999 + 64_572_944);
+
+/// Gas charged to the current call for deploying `code_len` bytes of code.
+#[must_use]
+pub fn deploy_contract_gas(code_len: usize) -> Gas {
+    let per_byte = DEPLOY_CONTRACT_GAS_PER_BYTE
+        .as_gas()
+        .saturating_mul(u64::try_from(code_len).unwrap_or(u64::MAX));
+    DEPLOY_CONTRACT_BASE_GAS.saturating_add(Gas::from_gas(per_byte))
+}
 
 #[cfg(feature = "unstable")]
 pub mod raw;
 pub mod serialized;
 
+/// Expands to nothing. Used by the `Upgrade` derive macro to check that the
+/// `unstable` feature is enabled for `serializer = "raw"`.
+#[cfg(feature = "unstable")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_raw_upgrade {
+    () => {};
+}
+
+/// Fails compilation. Used by the `Upgrade` derive macro to check that the
+/// `unstable` feature is enabled for `serializer = "raw"`.
+#[cfg(not(feature = "unstable"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_raw_upgrade {
+    () => {
+        compile_error!(
+            "`serializer = \"raw\"` requires the `unstable` feature of `near-sdk-contract-tools`"
+        );
+    };
+}
+
 /// Function call after upgrade descriptor
 #[derive(Debug, Clone)]
 pub struct PostUpgrade {
@@ -64,3 +113,71 @@ impl Default for PostUpgrade {
         }
     }
 }
+
+impl PostUpgrade {
+    /// Checks that an upgrade to code of length `code_len` can be scheduled
+    /// with the gas remaining in the current call, keeping `gas_leftover`
+    /// in reserve. The fee for deploying the code, which grows with
+    /// `code_len` (see [`deploy_contract_gas`]), is included.
+    ///
+    /// # Errors
+    ///
+    /// - The code is empty.
+    /// - The post-upgrade method name is empty.
+    /// - Not enough gas remains for the deployment and the post-upgrade
+    ///   function call.
+    pub fn validate(&self, code_len: usize, gas_leftover: Gas) -> Result<(), UpgradeError> {
+        if code_len == 0 {
+            return Err(UpgradeError::EmptyCode);
+        }
+
+        if self.method.is_empty() {
+            return Err(UpgradeError::EmptyMethodName);
+        }
+
+        let required = self
+            .minimum_gas
+            .saturating_add(gas_leftover)
+            .saturating_add(deploy_contract_gas(code_len));
+        let available = env::prepaid_gas().saturating_sub(env::used_gas());
+        if available < required {
+            return Err(InsufficientGasError {
+                required,
+                available,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Errors that can occur when initiating an upgrade.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum UpgradeError {
+    /// The new contract code is empty.
+    #[error("Upgrade code must not be empty")]
+    EmptyCode,
+    /// The post-upgrade method name is empty.
+    #[error("Post-upgrade method name must not be empty")]
+    EmptyMethodName,
+    /// Not enough gas remains to run the post-upgrade function.
+    #[error(transparent)]
+    InsufficientGas(#[from] InsufficientGasError),
+}
+
+/// Not enough gas remains to deploy the code and run the post-upgrade
+/// function.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error(
+    "Insufficient gas for upgrade: {} required, {} available",
+    .required.as_gas(),
+    .available.as_gas(),
+)]
+pub struct InsufficientGasError {
+    /// Gas required for the deployment and the post-upgrade call, plus the
+    /// reserved leftover.
+    pub required: Gas,
+    /// Gas remaining in the current call.
+    pub available: Gas,
+}
@@ -10,9 +10,9 @@
 //! Functions in this module are generally _not callable_ from any call tree
 //! originating from a function annotated by `#[near]`.
 
-use near_sdk::{env, sys};
+use near_sdk::{env, sys, Gas};
 
-use super::PostUpgrade;
+use super::{PostUpgrade, DEFAULT_UPGRADE_GAS_LEFTOVER};
 
 /// This function performs low-level, `unsafe` interactions with the NEAR VM.
 /// This function automatically sets the return value of the function call to
@@ -21,22 +21,94 @@ use super::PostUpgrade;
 /// this function probably should not be called from a `#[near]`
 /// context, since the macro may automatically set a different return value.
 ///
+/// # Panics
+///
+/// If the upgrade is rejected by [`PostUpgrade::validate`], reserving
+/// [`DEFAULT_UPGRADE_GAS_LEFTOVER`].
+///
 /// # Safety
 ///
 /// Requires that `near_sdk::env::input()` contains the plain, raw bytes of a
 /// valid WebAssembly smart contract.
-#[allow(clippy::needless_pass_by_value)]
 pub unsafe fn upgrade(post_upgrade: PostUpgrade) {
+    upgrade_with_gas_leftover(post_upgrade, DEFAULT_UPGRADE_GAS_LEFTOVER);
+}
+
+/// Like [`upgrade`], but reserves `gas_leftover` instead of
+/// [`DEFAULT_UPGRADE_GAS_LEFTOVER`] for the remainder of the current call.
+///
+/// The code is deployed directly from the input register, so it is never
+/// copied into contract memory. Compared to accepting the code as a
+/// Borsh-serialized `Vec<u8>` argument, this saves the `read_register_byte`
+/// (98,562 gas) and `write_memory_byte` (2,723,772 gas) fees for copying the
+/// code into memory, and replaces the `read_memory_byte` fee (3,801,333 gas)
+/// for deploying it with `read_register_byte`: about 6.5 Mgas per byte, or
+/// 3.4 Tgas for 512 KiB of code, before the cost of deserializing it.
+///
+/// # Panics
+///
+/// If the upgrade is rejected by [`PostUpgrade::validate`].
+///
+/// # Safety
+///
+/// Requires that `near_sdk::env::input()` contains the plain, raw bytes of a
+/// valid WebAssembly smart contract.
+#[allow(clippy::needless_pass_by_value)]
+pub unsafe fn upgrade_with_gas_leftover(post_upgrade: PostUpgrade, gas_leftover: Gas) {
+    sys::input(0);
+
+    // `u64::MAX` indicates an unused register, i.e. no input.
+    let code_len = match sys::register_len(0) {
+        u64::MAX => 0,
+        len => usize::try_from(len).unwrap_or(usize::MAX),
+    };
+
+    post_upgrade
+        .validate(code_len, gas_leftover)
+        .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+
+    // Deploy the contract code directly from register 0
+    deploy_and_call(&post_upgrade, u64::MAX, 0);
+}
+
+/// Like [`upgrade`], but first reads the new code into memory and passes it
+/// to `validate_code`, which may panic to abort the upgrade (e.g. if the code
+/// is too large or does not match an expected hash).
+///
+/// This costs more gas than [`upgrade`], which deploys the code directly
+/// from the input register without copying it.
+///
+/// # Panics
+///
+/// If `validate_code` panics, or if the upgrade is rejected by
+/// [`PostUpgrade::validate`], reserving [`DEFAULT_UPGRADE_GAS_LEFTOVER`].
+///
+/// # Safety
+///
+/// Requires that `near_sdk::env::input()` contains the plain, raw bytes of a
+/// valid WebAssembly smart contract.
+#[allow(clippy::needless_pass_by_value)]
+pub unsafe fn upgrade_validated(post_upgrade: PostUpgrade, validate_code: impl FnOnce(&[u8])) {
+    let code = env::input().unwrap_or_default();
+
+    validate_code(&code);
+
+    post_upgrade
+        .validate(code.len(), DEFAULT_UPGRADE_GAS_LEFTOVER)
+        .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+
+    deploy_and_call(&post_upgrade, code.len() as u64, code.as_ptr() as u64);
+}
+
+unsafe fn deploy_and_call(post_upgrade: &PostUpgrade, code_len: u64, code_ptr: u64) {
     // Create a promise batch
     let promise_id = sys::promise_batch_create(
         env::current_account_id().as_bytes().len() as u64,
         env::current_account_id().as_bytes().as_ptr() as u64,
     );
 
-    sys::input(0);
-
     // Deploy the contract code
-    sys::promise_batch_action_deploy_contract(promise_id, u64::MAX, 0);
+    sys::promise_batch_action_deploy_contract(promise_id, code_len, code_ptr);
 
     let gas = post_upgrade.minimum_gas.as_gas();
 
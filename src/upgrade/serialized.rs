@@ -2,9 +2,9 @@
 //! Contract upgrade functions that work as expected in conjunction with
 //! `#[near]`.
 
-use near_sdk::{env, GasWeight, NearToken, Promise};
+use near_sdk::{env, Gas, GasWeight, NearToken, Promise};
 
-use super::PostUpgrade;
+use super::{PostUpgrade, UpgradeError, DEFAULT_UPGRADE_GAS_LEFTOVER};
 
 /// Upgrade lifecycle hooks
 pub trait UpgradeHook {
@@ -14,9 +14,40 @@ pub trait UpgradeHook {
     fn on_upgrade(&self);
 }
 
-/// Creates a promise that upgrades the current contract with given code
-pub fn upgrade(code: Vec<u8>, post_upgrade: PostUpgrade) -> Promise {
-    Promise::new(env::current_account_id())
+/// Upgrade lifecycle hook that receives the incoming code, e.g. to enforce a
+/// size limit or check a hash before the code is deployed.
+pub trait UpgradeCodeHook {
+    /// Called with the new contract code after [`UpgradeHook::on_upgrade`]
+    /// and before the code is deployed. Panic to abort the upgrade. If you
+    /// use the [`crate::Upgrade`] macro with the `validate_code` flag, it
+    /// will call the hook automatically for you.
+    fn on_upgrade_code(&self, code: &[u8]);
+}
+
+/// Body of the [`UpgradeHook`] generated by `#[upgrade(hook = "owner")]`.
+/// Panics unless the predecessor is the owner of the contract.
+///
+/// The [`Owner`](crate::owner::Owner) bound lives here so that a contract
+/// that uses the `"owner"` hook without implementing `Owner` fails to
+/// compile with an error that points at this requirement.
+pub fn owner_hook<C: crate::owner::Owner>() {
+    C::require_owner();
+}
+
+/// Creates a promise that upgrades the current contract with given code,
+/// reserving `gas_leftover` for the remainder of the current call.
+///
+/// # Errors
+///
+/// See [`PostUpgrade::validate`].
+pub fn try_upgrade(
+    code: Vec<u8>,
+    post_upgrade: PostUpgrade,
+    gas_leftover: Gas,
+) -> Result<Promise, UpgradeError> {
+    post_upgrade.validate(code.len(), gas_leftover)?;
+
+    Ok(Promise::new(env::current_account_id())
         .deploy_contract(code)
         .function_call_weight(
             post_upgrade.method,
@@ -24,7 +55,18 @@ pub fn upgrade(code: Vec<u8>, post_upgrade: PostUpgrade) -> Promise {
             NearToken::from_yoctonear(0u128),
             post_upgrade.minimum_gas,
             GasWeight(u64::MAX),
-        )
+        ))
+}
+
+/// Creates a promise that upgrades the current contract with given code.
+///
+/// # Panics
+///
+/// If the upgrade is rejected by [`PostUpgrade::validate`], reserving
+/// [`DEFAULT_UPGRADE_GAS_LEFTOVER`].
+pub fn upgrade(code: Vec<u8>, post_upgrade: PostUpgrade) -> Promise {
+    try_upgrade(code, post_upgrade, DEFAULT_UPGRADE_GAS_LEFTOVER)
+        .unwrap_or_else(|e| env::panic_str(&e.to_string()))
 }
 
 /// Creates a promise that upgrades the current contract with given code and
@@ -32,3 +74,100 @@ pub fn upgrade(code: Vec<u8>, post_upgrade: PostUpgrade) -> Promise {
 pub fn upgrade_default(code: Vec<u8>) -> Promise {
     upgrade(code, PostUpgrade::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env};
+
+    use super::*;
+    use crate::upgrade::{
+        deploy_contract_gas, InsufficientGasError, DEFAULT_POST_UPGRADE_MINIMUM_GAS,
+    };
+
+    #[test]
+    fn empty_code() {
+        testing_env!(VMContextBuilder::new().build());
+
+        assert_eq!(
+            try_upgrade(vec![], PostUpgrade::default(), DEFAULT_UPGRADE_GAS_LEFTOVER).unwrap_err(),
+            UpgradeError::EmptyCode,
+        );
+    }
+
+    #[test]
+    fn empty_method_name() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let post_upgrade = PostUpgrade {
+            method: String::new(),
+            ..PostUpgrade::default()
+        };
+
+        assert_eq!(
+            try_upgrade(vec![0], post_upgrade, DEFAULT_UPGRADE_GAS_LEFTOVER).unwrap_err(),
+            UpgradeError::EmptyMethodName,
+        );
+    }
+
+    #[test]
+    fn insufficient_gas() {
+        let prepaid_gas = Gas::from_gas(10_000_000_000_000);
+        testing_env!(VMContextBuilder::new().prepaid_gas(prepaid_gas).build());
+
+        let err = try_upgrade(
+            vec![0],
+            PostUpgrade::default(),
+            DEFAULT_UPGRADE_GAS_LEFTOVER,
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            UpgradeError::InsufficientGas(InsufficientGasError { required, available })
+                if required == DEFAULT_POST_UPGRADE_MINIMUM_GAS
+                    .saturating_add(DEFAULT_UPGRADE_GAS_LEFTOVER)
+                    .saturating_add(deploy_contract_gas(1))
+                    && available <= prepaid_gas,
+        ));
+    }
+
+    #[test]
+    fn insufficient_gas_for_large_code() {
+        // Enough for the post-upgrade call and the leftover, but not for
+        // deploying 512 KiB of code.
+        let prepaid_gas = Gas::from_gas(40_000_000_000_000);
+        testing_env!(VMContextBuilder::new().prepaid_gas(prepaid_gas).build());
+
+        let code = vec![0; 512 * 1024];
+        let err =
+            try_upgrade(code, PostUpgrade::default(), DEFAULT_UPGRADE_GAS_LEFTOVER).unwrap_err();
+
+        assert!(matches!(
+            err,
+            UpgradeError::InsufficientGas(InsufficientGasError { required, available })
+                if required > prepaid_gas && available <= prepaid_gas,
+        ));
+    }
+
+    #[test]
+    #[should_panic = "Upgrade code must not be empty"]
+    fn upgrade_empty_code_panics() {
+        testing_env!(VMContextBuilder::new().build());
+
+        upgrade_default(vec![]);
+    }
+
+    #[test]
+    fn sufficient_gas() {
+        testing_env!(VMContextBuilder::new()
+            .prepaid_gas(Gas::from_gas(300_000_000_000_000))
+            .build());
+
+        try_upgrade(
+            vec![0],
+            PostUpgrade::default(),
+            DEFAULT_UPGRADE_GAS_LEFTOVER,
+        )
+        .unwrap();
+    }
+}
@@ -93,9 +93,82 @@ pub fn assert_nonzero_deposit() {
     );
 }
 
+/// Panics if any two of the named storage roots are equal, or if one is a
+/// prefix of the other. Components whose roots overlap in this way may read
+/// and write each other's state, since every key a component uses starts
+/// with its root.
+///
+/// Usually invoked through
+/// [`debug_assert_unique_storage_roots!`](crate::debug_assert_unique_storage_roots).
+///
+/// # Panics
+///
+/// If two roots have the same key, or one root is a prefix of another.
+pub fn assert_unique_storage_roots<'a>(roots: impl IntoIterator<Item = (&'a str, Vec<u8>)>) {
+    let roots = roots.into_iter().collect::<Vec<_>>();
+
+    for (i, (name, key)) in roots.iter().enumerate() {
+        for (other, other_key) in &roots[..i] {
+            if key == other_key {
+                env::panic_str(&format!(
+                    "Storage key collision: `{other}` and `{name}` share the root key {:?}",
+                    String::from_utf8_lossy(key),
+                ));
+            }
+
+            let (short, long) = match (other_key.starts_with(key), key.starts_with(other_key)) {
+                (true, _) => ((name, key), (other, other_key)),
+                (_, true) => ((other, other_key), (name, key)),
+                _ => continue,
+            };
+
+            env::panic_str(&format!(
+                "Storage key collision: the root key {:?} of `{}` is a prefix of the root key {:?} of `{}`",
+                String::from_utf8_lossy(short.1),
+                short.0,
+                String::from_utf8_lossy(long.1),
+                long.0,
+            ));
+        }
+    }
+}
+
+/// In debug builds, panics if any of the listed components of `Self` have
+/// overlapping root storage keys, as checked by
+/// [`assert_unique_storage_roots`]. Must be invoked from within an `impl` block of the
+/// contract, e.g. in its initializer. Each component is the path of a trait
+/// with a `root()` function, such as [`OwnerInternal`](crate::owner::OwnerInternal).
+///
+/// The reserved default root keys are listed on
+/// [`DefaultStorageKey`](crate::DefaultStorageKey).
+///
+/// # Examples
+///
+/// ```ignore
+/// #[init]
+/// pub fn new() -> Self {
+///     debug_assert_unique_storage_roots!(OwnerInternal, PauseInternal, Nep141ControllerInternal);
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! debug_assert_unique_storage_roots {
+    ($($component:path),+ $(,)?) => {
+        if cfg!(debug_assertions) {
+            $crate::utils::assert_unique_storage_roots([
+                $((stringify!($component), <Self as $component>::root().key)),+
+            ]);
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
+    use near_sdk::{near, PanicOnDefault};
+    use near_sdk_contract_tools_macros::{Owner, Pause};
+
     use super::prefix_key;
+    use crate::{owner::OwnerInternal, pause::PauseInternal};
 
     #[test]
     fn test_prefix_key() {
@@ -106,4 +179,57 @@ mod tests {
         assert_eq!(prefix_key(&[], b""), [0u8; 0]);
         assert_eq!(prefix_key("abc".as_ref(), b""), b"abc");
     }
+
+    #[derive(Owner, Pause, PanicOnDefault)]
+    #[owner(crate = "crate")]
+    #[pause(crate = "crate")]
+    #[near(contract_state)]
+    struct Distinct {}
+
+    impl Distinct {
+        fn check() {
+            debug_assert_unique_storage_roots!(OwnerInternal, PauseInternal);
+        }
+    }
+
+    #[derive(Owner, Pause, PanicOnDefault)]
+    #[owner(crate = "crate", storage_key = "b\"k\"")]
+    #[pause(crate = "crate", storage_key = "b\"k\"")]
+    #[near(contract_state)]
+    struct Colliding {}
+
+    impl Colliding {
+        fn check() {
+            debug_assert_unique_storage_roots!(OwnerInternal, PauseInternal);
+        }
+    }
+
+    #[derive(Owner, Pause, PanicOnDefault)]
+    #[owner(crate = "crate", storage_key = "b\"kp\"")]
+    #[pause(crate = "crate", storage_key = "b\"k\"")]
+    #[near(contract_state)]
+    struct Overlapping {}
+
+    impl Overlapping {
+        fn check() {
+            debug_assert_unique_storage_roots!(OwnerInternal, PauseInternal);
+        }
+    }
+
+    #[test]
+    fn unique_storage_roots() {
+        Distinct::check();
+    }
+
+    #[test]
+    #[should_panic = "Storage key collision: the root key \"k\" of `PauseInternal` is a prefix of the root key \"kp\" of `OwnerInternal`"]
+    fn overlapping_storage_roots() {
+        Overlapping::check();
+    }
+
+    #[test]
+    #[should_panic = "Storage key collision: `OwnerInternal` and `PauseInternal` share the root key \"k\""]
+    fn colliding_storage_roots() {
+        Colliding::check();
+    }
 }
@@ -0,0 +1,86 @@
+//! Derives should work when `near_sdk_contract_tools` is only reachable
+//! through a non-default path, as is the case for crates that re-export it.
+
+use near_sdk::{env, near, test_utils::VMContextBuilder, testing_env, PanicOnDefault};
+
+mod reexport {
+    pub use near_sdk_contract_tools as tools;
+}
+
+use reexport::tools::{
+    owner::{Owner, OwnerExternal},
+    pause::Pause,
+    rbac::Rbac,
+    standard::{
+        nep141::{Nep141Controller, Nep141Mint},
+        nep145::{Nep145Controller, StorageBalanceBounds},
+        nep148::{ContractMetadata, Nep148Controller},
+    },
+    FungibleToken, Owner, Pause, Rbac, Upgrade,
+};
+
+#[derive(near_sdk::BorshStorageKey)]
+#[near]
+enum Role {
+    Admin,
+}
+
+#[derive(Owner, Pause, Rbac, Upgrade, FungibleToken, PanicOnDefault)]
+#[owner(crate = "crate::crate_path::reexport::tools")]
+#[pause(crate = "crate::crate_path::reexport::tools")]
+#[rbac(roles = "Role", crate = "crate::crate_path::reexport::tools")]
+#[upgrade(
+    serializer = "borsh",
+    hook = "owner",
+    crate = "crate::crate_path::reexport::tools"
+)]
+#[fungible_token(pausable, crate = "crate::crate_path::reexport::tools")]
+#[near(contract_state)]
+pub struct Contract {}
+
+#[near]
+impl Contract {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        Owner::init(&mut contract, &env::predecessor_account_id());
+        contract.set_metadata(&ContractMetadata::new("Aliased", "ALIAS", 24));
+        contract.set_storage_balance_bounds(&StorageBalanceBounds::default());
+
+        contract
+    }
+}
+
+#[test]
+fn derives_with_reexported_crate_path() {
+    let alice: near_sdk::AccountId = "alice".parse().unwrap();
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(alice.clone())
+        .build());
+
+    let mut contract = Contract::new();
+
+    assert_eq!(contract.own_get_owner(), Some(alice.clone()));
+    contract.add_role(&alice, &Role::Admin);
+    assert!(Contract::has_role(&alice, &Role::Admin));
+    assert!(!Contract::is_paused());
+
+    contract
+        .deposit_to_storage_account(&alice, near_sdk::NearToken::from_near(1))
+        .unwrap();
+    contract.mint(&Nep141Mint::new(100, alice.clone())).unwrap();
+
+    assert_eq!(contract.balance_of(&alice), 100);
+    assert_eq!(contract.get_metadata().symbol, "ALIAS");
+}
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/crate_path/core.rs");
+    t.pass("tests/ui/crate_path/simple_multisig.rs");
+    t.pass("tests/ui/crate_path/fungible_token.rs");
+    t.pass("tests/ui/crate_path/non_fungible_token.rs");
+    t.pass("tests/ui/crate_path/event.rs");
+}
@@ -51,7 +51,12 @@ mod test_events {
 
     #[derive(Nep297, Serialize)]
     #[serde(crate = "near_sdk::serde")]
-    #[nep297(standard = "enum-event", version = "1.0.0", rename_all = "snake_case")]
+    #[nep297(
+        standard = "enum-event",
+        version = "1.0.0",
+        rename_all = "snake_case",
+        allowed_chars = "_!"
+    )]
     #[allow(clippy::enum_variant_names)]
     pub enum EnumEventRenameAll {
         VariantOne,
@@ -154,3 +159,12 @@ mod event_attribute_macro {
         assert_eq!(e.to_event_string(), f.to_event_string());
     }
 }
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/nep297/no_collision.rs");
+    t.compile_fail("tests/ui/nep297/collision.rs");
+    t.pass("tests/ui/nep297/allowed_name.rs");
+    t.compile_fail("tests/ui/nep297/disallowed_name.rs");
+}
@@ -45,3 +45,58 @@ fn default_from() {
 
     assert_eq!(migrated.bar, 99);
 }
+
+mod dry_run {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env};
+
+    use super::*;
+
+    #[derive(Migrate, PanicOnDefault)]
+    #[migrate(from = "old::Old", dry_run)]
+    #[near(contract_state)]
+    struct DryRunContract {
+        pub bar: u64,
+    }
+
+    impl MigrateHook for DryRunContract {
+        fn on_migrate(old: old::Old) -> Self {
+            Self { bar: old.foo }
+        }
+    }
+
+    #[test]
+    fn dry_run_success() {
+        testing_env!(VMContextBuilder::new().build());
+        env::state_write(&old::Old::new(7));
+
+        assert_eq!(DryRunContract::migrate_dry_run(), Ok(()));
+        assert_eq!(DryRunContract::dry_run_migrate().unwrap().bar, 7);
+
+        // Nothing was written
+        assert_eq!(env::state_read::<old::Old>().unwrap().foo, 7);
+    }
+
+    #[test]
+    fn dry_run_incompatible_state() {
+        testing_env!(VMContextBuilder::new().build());
+        env::storage_write(b"STATE", &[1, 2, 3]);
+
+        let error = DryRunContract::migrate_dry_run().unwrap_err();
+
+        assert!(
+            error.starts_with("Failed to deserialize old state: "),
+            "{error}",
+        );
+        assert_eq!(env::storage_read(b"STATE").unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn dry_run_no_state() {
+        testing_env!(VMContextBuilder::new().build());
+
+        assert_eq!(
+            DryRunContract::migrate_dry_run(),
+            Err("No contract state found".to_string()),
+        );
+    }
+}
@@ -7,12 +7,14 @@ use near_sdk_contract_tools::{
     standard::nep297::Event, Escrow, Migrate, Owner, Pause, Rbac,
 };
 
+mod crate_path;
 mod escrow;
 mod event;
 mod migrate;
 mod owner;
 mod pause;
 mod standard;
+mod upgrade;
 
 mod my_event {
     use near_sdk::{serde::Serialize, AccountId};
@@ -100,3 +100,131 @@ fn derive_pause_methods_fail_paused() {
 
     contract.only_when_unpaused(5);
 }
+
+mod pause_until {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env};
+
+    use super::*;
+
+    fn set_block_timestamp_ms(timestamp_ms: u64) {
+        testing_env!(VMContextBuilder::new()
+            .block_timestamp(timestamp_ms * 1_000_000)
+            .build());
+    }
+
+    #[test]
+    fn unpauses_at_scheduled_time() {
+        set_block_timestamp_ms(1_000);
+        let mut contract = Contract { value: 0 };
+
+        contract.pause_until(2_000);
+
+        assert!(Contract::is_paused());
+        assert_eq!(Contract::unpause_at(), Some(2_000));
+        contract.only_when_paused(1);
+
+        set_block_timestamp_ms(1_999);
+        assert!(Contract::is_paused());
+
+        set_block_timestamp_ms(2_000);
+        assert!(!Contract::is_paused());
+        contract.only_when_unpaused(2);
+
+        set_block_timestamp_ms(5_000);
+        assert!(!contract.paus_is_paused());
+        assert_eq!(contract.get_value(), 2);
+    }
+
+    #[test]
+    fn past_time_is_unpaused() {
+        set_block_timestamp_ms(1_000);
+        let mut contract = Contract { value: 0 };
+
+        contract.pause_until(500);
+
+        assert!(!Contract::is_paused());
+        contract.only_when_unpaused(1);
+    }
+
+    #[test]
+    fn manual_unpause_clears_schedule() {
+        set_block_timestamp_ms(1_000);
+        let mut contract = Contract { value: 0 };
+
+        contract.pause_until(2_000);
+        contract.unpause();
+
+        assert!(!Contract::is_paused());
+        assert_eq!(Contract::unpause_at(), None);
+
+        // A later indefinite pause is not affected by the old schedule.
+        contract.pause();
+        set_block_timestamp_ms(3_000);
+        assert!(Contract::is_paused());
+    }
+
+    #[test]
+    fn pause_after_elapsed_schedule() {
+        set_block_timestamp_ms(1_000);
+        let mut contract = Contract { value: 0 };
+
+        contract.pause_until(2_000);
+        set_block_timestamp_ms(2_000);
+
+        contract.pause();
+        set_block_timestamp_ms(3_000);
+
+        assert!(Contract::is_paused());
+        assert_eq!(Contract::unpause_at(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Disallowed while contract is paused")]
+    fn pause_until_while_paused() {
+        set_block_timestamp_ms(1_000);
+        let mut contract = Contract { value: 0 };
+
+        contract.pause();
+        contract.pause_until(2_000);
+    }
+}
+
+#[test]
+fn pause_events() {
+    use near_sdk::{
+        test_utils::{get_logs, VMContextBuilder},
+        testing_env,
+    };
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("alice".parse().unwrap())
+        .block_timestamp(1_000_000_000)
+        .build());
+
+    let mut contract = Contract { value: 0 };
+
+    contract.pause();
+
+    assert_eq!(
+        get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"x-paus","version":"1.1.0","event":"pause","data":{"account_id":"alice","timestamp_ms":1000,"unpause_at_ms":null}}"#
+        ],
+    );
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("bob".parse().unwrap())
+        .block_timestamp(2_000_000_000)
+        .build());
+
+    contract.unpause();
+    contract.pause_until(5_000);
+
+    assert_eq!(
+        get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"x-paus","version":"1.1.0","event":"unpause","data":{"account_id":"bob","timestamp_ms":2000}}"#,
+            r#"EVENT_JSON:{"standard":"x-paus","version":"1.1.0","event":"pause","data":{"account_id":"bob","timestamp_ms":2000,"unpause_at_ms":5000}}"#,
+        ],
+    );
+}
@@ -3,3 +3,4 @@ pub mod nep141;
 pub mod nep145;
 pub mod nep148;
 pub mod nep171;
+pub mod nep177;
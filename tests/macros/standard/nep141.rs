@@ -2,7 +2,11 @@ use near_sdk::{
     borsh, collections::Vector, env, json_types::U128, log, near, test_utils::VMContextBuilder,
     testing_env, AccountId, NearToken, PanicOnDefault, PromiseOrValue,
 };
-use near_sdk_contract_tools::{hook::Hook, standard::nep141::*, Nep141};
+use near_sdk_contract_tools::{
+    hook::Hook,
+    standard::{nep141::*, nep297::Event},
+    Nep141,
+};
 
 #[derive(Nep141, PanicOnDefault)]
 #[nep141(transfer_hook = "TransferHook")]
@@ -105,3 +109,341 @@ fn nep141_transfer() {
     assert_eq!(ft.ft_balance_of(bob).0, 70);
     assert_eq!(ft.ft_total_supply().0, 120);
 }
+
+#[test]
+fn nep141_returns_new_balances() {
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+    let bob: AccountId = "bob".parse().unwrap();
+
+    assert_eq!(ft.deposit_unchecked(&alice, 100).unwrap(), 100);
+    assert_eq!(ft.deposit_unchecked(&alice, 0).unwrap(), 100);
+    assert_eq!(ft.mint(&Nep141Mint::new(20, bob.clone())).unwrap(), 20);
+
+    assert_eq!(
+        ft.transfer_unchecked(&alice, &bob, 30).unwrap(),
+        TransferBalances {
+            sender_balance: 70,
+            receiver_balance: 50,
+        },
+    );
+    assert_eq!(
+        ft.transfer(&Nep141Transfer::new(10, bob.clone(), alice.clone()))
+            .unwrap(),
+        TransferBalances {
+            sender_balance: 40,
+            receiver_balance: 80,
+        },
+    );
+
+    assert_eq!(ft.withdraw_unchecked(&alice, 5).unwrap(), 75);
+    assert_eq!(ft.burn(&Nep141Burn::new(40, bob.clone())).unwrap(), 0);
+
+    assert_eq!(ft.ft_balance_of(alice).0, 75);
+    assert_eq!(ft.ft_balance_of(bob).0, 0);
+    assert_eq!(ft.ft_total_supply().0, 75);
+}
+
+#[test]
+fn nep141_self_transfer_preserves_balance() {
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+
+    ft.mint(&Nep141Mint::new(100, alice.clone())).unwrap();
+
+    assert!(matches!(
+        ft.transfer_unchecked(&alice, &alice, 100),
+        Err(TransferError::SelfTransfer(SelfTransferError { account_id })) if account_id == alice,
+    ));
+    assert!(matches!(
+        ft.transfer(&Nep141Transfer::new(100, alice.clone(), alice.clone())),
+        Err(TransferError::SelfTransfer(_)),
+    ));
+
+    assert_eq!(ft.ft_balance_of(alice).0, 100);
+    assert_eq!(ft.ft_total_supply().0, 100);
+}
+
+#[test]
+fn nep141_transfer_with_event_no_hooks() {
+    testing_env!(VMContextBuilder::new().build());
+
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+    let bob: AccountId = "bob".parse().unwrap();
+
+    ft.deposit_unchecked(&alice, 100).unwrap();
+
+    assert_eq!(
+        ft.transfer_with_event_no_hooks(
+            &Nep141Transfer::new(30, alice.clone(), bob.clone()).memo("fee")
+        )
+        .unwrap(),
+        TransferBalances {
+            sender_balance: 70,
+            receiver_balance: 30,
+        },
+    );
+
+    assert_eq!(
+        near_sdk::test_utils::get_logs(),
+        vec![Nep141Event::FtTransfer(vec![FtTransferData {
+            old_owner_id: alice.clone().into(),
+            new_owner_id: bob.clone().into(),
+            amount: U128(30),
+            memo: Some("fee".into()),
+        }])
+        .to_event_string()],
+    );
+    assert_eq!(ft.hooks.len(), 0);
+    assert_eq!(ft.transfers.len(), 0);
+
+    ft.transfer(&Nep141Transfer::new(10, alice, bob)).unwrap();
+
+    assert_eq!(ft.hooks.len(), 2);
+    assert_eq!(ft.transfers.len(), 1);
+}
+
+#[test]
+fn nep141_revert_transfer_event_is_labeled() {
+    testing_env!(VMContextBuilder::new().build());
+
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+    let bob: AccountId = "bob".parse().unwrap();
+
+    ft.deposit_unchecked(&bob, 200).unwrap();
+
+    // Constructed the same way as the refund in `ft_resolve_transfer`.
+    let refund = Nep141Transfer {
+        sender_id: bob.clone().into(),
+        receiver_id: alice.clone().into(),
+        amount: 40,
+        memo: Some(REFUND_MEMO.into()),
+        msg: None,
+        revert: true,
+    };
+    let unlabeled = Nep141Transfer {
+        memo: None,
+        ..refund.clone()
+    };
+    let custom = Nep141Transfer {
+        memo: Some("fee".into()),
+        ..refund.clone()
+    };
+
+    ft.transfer(&refund).unwrap();
+    ft.transfer(&unlabeled).unwrap();
+    ft.transfer(&custom).unwrap();
+    ft.transfer(&Nep141Transfer::new(10, bob, alice)).unwrap();
+
+    assert_eq!(
+        near_sdk::test_utils::get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"alice","amount":"40","memo":"refund"}]}"#,
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"alice","amount":"40","memo":"refund"}]}"#,
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"alice","amount":"40","memo":"refund: fee"}]}"#,
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[{"old_owner_id":"bob","new_owner_id":"alice","amount":"10"}]}"#,
+        ],
+    );
+}
+
+#[test]
+fn nep141_balance_of_batch() {
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+    let bob: AccountId = "bob".parse().unwrap();
+    let charlie: AccountId = "charlie".parse().unwrap();
+
+    ft.mint(&Nep141Mint::new(100, alice.clone())).unwrap();
+    ft.mint(&Nep141Mint::new(20, charlie.clone())).unwrap();
+
+    assert_eq!(
+        ft.ft_balance_of_batch(vec![charlie, bob, alice.clone(), alice]),
+        vec![U128(20), U128(0), U128(100), U128(100)],
+    );
+    assert!(ft.ft_balance_of_batch(vec![]).is_empty());
+}
+
+#[test]
+fn nep141_mint_burn_reason_in_event() {
+    testing_env!(VMContextBuilder::new().build());
+
+    let mut ft = FungibleToken {
+        transfers: Vector::new(b"t"),
+        hooks: Vector::new(b"h"),
+    };
+
+    let alice: AccountId = "alice".parse().unwrap();
+
+    ft.mint(&Nep141Mint::new(100, alice.clone()).reason(SupplyChangeReason::Reward))
+        .unwrap();
+    ft.burn(
+        &Nep141Burn::new(40, alice.clone())
+            .memo("penalty")
+            .reason(SupplyChangeReason::Slashing),
+    )
+    .unwrap();
+    ft.mint(&Nep141Mint::new(5, alice)).unwrap();
+
+    assert_eq!(
+        near_sdk::test_utils::get_logs(),
+        vec![
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[{"owner_id":"alice","amount":"100","reason":"reward"}]}"#,
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_burn","data":[{"owner_id":"alice","amount":"40","memo":"penalty","reason":"slashing"}]}"#,
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[{"owner_id":"alice","amount":"5"}]}"#,
+        ],
+    );
+}
+
+mod balance_batch_limit {
+    use super::*;
+
+    #[derive(Nep141, PanicOnDefault)]
+    #[nep141(max_balance_batch_len = "2")]
+    #[near(contract_state)]
+    struct LimitedFungibleToken {}
+
+    fn accounts(n: usize) -> Vec<AccountId> {
+        (0..n)
+            .map(|i| format!("account{i}").parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn at_limit() {
+        let ft = LimitedFungibleToken {};
+
+        assert_eq!(ft.ft_balance_of_batch(accounts(2)), vec![U128(0); 2]);
+    }
+
+    #[test]
+    #[should_panic = "Cannot query 3 balances at once (maximum: 2)."]
+    fn over_limit() {
+        let ft = LimitedFungibleToken {};
+
+        ft.ft_balance_of_batch(accounts(3));
+    }
+}
+
+mod hashed_account_keys {
+    use near_sdk_contract_tools::slot::{KeyHashing, HASHED_KEY_LEN};
+
+    use super::*;
+
+    #[derive(Nep141, PanicOnDefault)]
+    #[nep141(account_key_hashing = "KeyHashing::Always")]
+    #[near(contract_state)]
+    struct HashedFungibleToken {}
+
+    #[test]
+    fn distinct_fixed_length_keys() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let mut ft = HashedFungibleToken {};
+
+        let long: AccountId = format!("{}.near", "a".repeat(59)).parse().unwrap();
+        let alice: AccountId = "alice".parse().unwrap();
+
+        ft.mint(&Nep141Mint::new(100, long.clone())).unwrap();
+        ft.mint(&Nep141Mint::new(20, alice.clone())).unwrap();
+
+        let long_slot = HashedFungibleToken::slot_account(&long);
+        let alice_slot = HashedFungibleToken::slot_account(&alice);
+
+        assert_ne!(long_slot, alice_slot);
+        assert_eq!(long_slot.key.len(), alice_slot.key.len());
+        assert_eq!(
+            long_slot.key.len(),
+            HashedFungibleToken::root().key.len() + HASHED_KEY_LEN,
+        );
+        assert_eq!(ft.balance_of(&long), 100);
+        assert_eq!(ft.balance_of(&alice), 20);
+    }
+}
+
+mod observer {
+    use near_sdk::AccountIdRef;
+
+    use super::*;
+
+    pub struct CountingObserver;
+
+    impl Nep141Observer<ObservedFungibleToken> for CountingObserver {
+        fn on_balance_change(
+            contract: &mut ObservedFungibleToken,
+            account_id: &AccountIdRef,
+            old_balance: u128,
+            new_balance: u128,
+        ) {
+            contract.mutations += 1;
+            contract
+                .last_change
+                .replace((account_id.to_owned(), old_balance, new_balance));
+        }
+    }
+
+    #[derive(Nep141, PanicOnDefault)]
+    #[nep141(observer = "CountingObserver")]
+    #[near(contract_state)]
+    struct ObservedFungibleToken {
+        mutations: u32,
+        last_change: Option<(AccountId, u128, u128)>,
+    }
+
+    #[test]
+    fn counts_balance_mutations() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let mut ft = ObservedFungibleToken {
+            mutations: 0,
+            last_change: None,
+        };
+
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob".parse().unwrap();
+
+        ft.mint(&Nep141Mint::new(100, alice.clone())).unwrap();
+        assert_eq!(ft.mutations, 1);
+        assert_eq!(ft.last_change, Some((alice.clone(), 0, 100)));
+
+        ft.transfer(&Nep141Transfer::new(30, alice.clone(), bob.clone()))
+            .unwrap();
+        assert_eq!(ft.mutations, 3);
+        assert_eq!(ft.last_change, Some((bob.clone(), 0, 30)));
+
+        ft.burn(&Nep141Burn::new(10, bob.clone())).unwrap();
+        assert_eq!(ft.mutations, 4);
+        assert_eq!(ft.last_change, Some((bob.clone(), 30, 20)));
+
+        ft.deposit_unchecked(&alice, 5).unwrap();
+        ft.withdraw_unchecked(&alice, 5).unwrap();
+        assert_eq!(ft.mutations, 6);
+        assert_eq!(ft.last_change, Some((alice, 75, 70)));
+
+        // Zero-amount operations and failures do not change any balance.
+        ft.deposit_unchecked(&bob, 0).unwrap();
+        ft.withdraw_unchecked(&bob, 1000).unwrap_err();
+        assert_eq!(ft.mutations, 6);
+    }
+}
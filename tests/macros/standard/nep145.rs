@@ -195,4 +195,181 @@ mod tests {
             ),
         );
     }
+
+    fn bounded_contract() -> Contract {
+        let mut contract = Contract::new();
+
+        Nep145Controller::set_storage_balance_bounds(
+            &mut contract,
+            &StorageBalanceBounds {
+                min: NearToken::from_millinear(100),
+                max: Some(NearToken::from_near(1)),
+            },
+        );
+
+        contract
+    }
+
+    #[test]
+    #[should_panic = "must cover the minimum balance"]
+    fn storage_deposit_below_min_fail() {
+        let mut contract = bounded_contract();
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_millinear(99))
+            .build());
+
+        Nep145::storage_deposit(&mut contract, None, None);
+    }
+
+    #[test]
+    fn storage_deposit_above_max_refund() {
+        let mut contract = bounded_contract();
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_millinear(600))
+            .build());
+
+        Nep145::storage_deposit(&mut contract, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_near(2))
+            .build());
+
+        // Only 0.4 NEAR fits under the maximum; the rest is refunded.
+        let balance = Nep145::storage_deposit(&mut contract, None, None);
+
+        assert_eq!(
+            balance,
+            StorageBalance {
+                total: NearToken::from_near(1),
+                available: NearToken::from_near(1),
+            },
+        );
+        assert_eq!(
+            Nep145::storage_balance_bounds(&contract),
+            StorageBalanceBounds {
+                min: NearToken::from_millinear(100),
+                max: Some(NearToken::from_near(1)),
+            },
+        );
+        assert_eq!(
+            StorageBalanceBounds {
+                min: NearToken::from_millinear(100),
+                max: Some(NearToken::from_near(1)),
+            }
+            .bound_deposit(
+                Some(NearToken::from_millinear(600)),
+                NearToken::from_near(2),
+                false,
+            ),
+            NearToken::from_millinear(400),
+        );
+    }
+
+    #[test]
+    fn storage_deposit_registration_only_when_registered() {
+        let mut contract = bounded_contract();
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_near(1))
+            .build());
+
+        let first = Nep145::storage_deposit(&mut contract, None, Some(true));
+        assert_eq!(first.total, NearToken::from_millinear(100));
+
+        let second = Nep145::storage_deposit(&mut contract, None, Some(true));
+        assert_eq!(second.total, NearToken::from_millinear(100));
+    }
+
+    #[test]
+    fn storage_withdraw_partial() {
+        let mut contract = Contract::new();
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_near(1))
+            .build());
+
+        Nep145::storage_deposit(&mut contract, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.use_storage(100);
+
+        let before = Nep145::storage_balance_of(&contract, alice()).unwrap();
+        let locked = before.total.saturating_sub(before.available);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+
+        let after = Nep145::storage_withdraw(&mut contract, Some(NearToken::from_millinear(250)));
+
+        assert_eq!(
+            after,
+            StorageBalance {
+                total: NearToken::from_millinear(750),
+                available: before
+                    .available
+                    .saturating_sub(NearToken::from_millinear(250)),
+            },
+        );
+        assert_eq!(after.total.saturating_sub(after.available), locked);
+    }
+
+    #[test]
+    #[should_panic = "insufficient balance"]
+    fn storage_withdraw_more_than_available_fail() {
+        let mut contract = Contract::new();
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_near(1))
+            .build());
+
+        Nep145::storage_deposit(&mut contract, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.use_storage(100);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+
+        // The total is deposited, but part of it is locked.
+        Nep145::storage_withdraw(&mut contract, Some(NearToken::from_near(1)));
+    }
+
+    #[test]
+    #[should_panic = "no storage balance available to withdraw"]
+    fn storage_withdraw_nothing_available_fail() {
+        let mut contract = bounded_contract();
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_near(1))
+            .build());
+
+        Nep145::storage_deposit(&mut contract, None, Some(true));
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+
+        // Only the minimum balance is deposited, so nothing can be withdrawn.
+        Nep145::storage_withdraw(&mut contract, None);
+    }
 }
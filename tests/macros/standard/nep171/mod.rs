@@ -103,6 +103,651 @@ impl NonFungibleToken {
     }
 }
 
+mod sequential {
+    use near_sdk::NearToken;
+
+    use super::*;
+
+    #[derive(NonFungibleToken, PanicOnDefault)]
+    #[near(contract_state)]
+    struct SequentialContract {}
+
+    #[test]
+    fn successive_mints_are_distinct_and_increasing() {
+        let mut contract = SequentialContract {};
+        let alice: AccountId = "alice".parse().unwrap();
+
+        Nep145Controller::deposit_to_storage_account(
+            &mut contract,
+            &alice,
+            NearToken::from_near(1),
+        )
+        .unwrap();
+
+        contract.set_token_id_prefix("token_");
+
+        let first = contract
+            .mint_next_with_metadata(&alice, &TokenMetadata::new().title("First"))
+            .unwrap();
+        let second = contract.mint_next(&alice).unwrap();
+
+        assert_eq!(first, "token_0");
+        assert_eq!(second, "token_1");
+        assert_eq!(contract.peek_next_token_id(), "token_2");
+        assert_eq!(contract.token_owner(&first), Some(alice.clone()));
+        assert_eq!(contract.token_owner(&second), Some(alice));
+        assert_eq!(
+            contract.token_metadata(&first),
+            Some(TokenMetadata::new().title("First")),
+        );
+    }
+}
+
+mod enumeration_filter {
+    use near_sdk::{serde_json, NearToken};
+
+    use super::*;
+
+    #[derive(NonFungibleToken, PanicOnDefault)]
+    #[near(contract_state)]
+    struct EnumeratedContract {}
+
+    fn issued_at(token: &Token) -> u64 {
+        let metadata: TokenMetadata =
+            serde_json::from_value(token.extensions_metadata["metadata"].clone()).unwrap();
+        metadata.issued_at.unwrap().0
+    }
+
+    #[test]
+    fn tokens_where_issued_after() {
+        let mut contract = EnumeratedContract {};
+        let alice: AccountId = "alice".parse().unwrap();
+
+        Nep145Controller::deposit_to_storage_account(
+            &mut contract,
+            &alice,
+            NearToken::from_near(1),
+        )
+        .unwrap();
+
+        for i in 0..6u64 {
+            contract
+                .mint_with_metadata(
+                    &format!("token_{i}"),
+                    &alice,
+                    &TokenMetadata::new().issued_at(i * 100),
+                )
+                .unwrap();
+        }
+
+        let ids = |page: nep181::TokenPage| {
+            page.tokens
+                .into_iter()
+                .map(|t| t.token_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ids(contract.tokens_where(0, 6, |t| issued_at(t) >= 300)),
+            vec!["token_3", "token_4", "token_5"],
+        );
+        assert_eq!(
+            ids(contract.tokens_where(4, 6, |t| issued_at(t) >= 300)),
+            vec!["token_4", "token_5"],
+        );
+        assert_eq!(
+            ids(contract.tokens_for_owner_where(&alice, 0, 2, |t| issued_at(t) < 300)),
+            vec!["token_0", "token_1"],
+        );
+
+        // the scan limit counts scanned tokens, not matches, and the page
+        // reports where to continue
+        let page = contract.tokens_where(0, 4, |t| issued_at(t) >= 300);
+        assert_eq!(page.next_index, Some(4));
+        assert_eq!(ids(page), vec!["token_3"]);
+
+        let page = contract.tokens_where(4, 4, |t| issued_at(t) >= 300);
+        assert_eq!(page.next_index, None);
+        assert_eq!(ids(page), vec!["token_4", "token_5"]);
+
+        assert_eq!(contract.tokens_where(6, 4, |_| true).next_index, None);
+
+        // tokens before `from_index` and after the scanned range are never
+        // loaded
+        let mut loaded = vec![];
+        contract.tokens_where(2, 1, |t| {
+            loaded.push(t.token_id.clone());
+            true
+        });
+        assert_eq!(loaded, vec!["token_2"]);
+    }
+}
+
+mod burn_approvals {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env, NearToken};
+
+    use super::*;
+
+    #[derive(NonFungibleToken, PanicOnDefault)]
+    #[near(contract_state)]
+    struct ApprovalContract {}
+
+    #[test]
+    fn burn_clears_approvals() {
+        let alice: AccountId = "alice".parse().unwrap();
+        let bob: AccountId = "bob".parse().unwrap();
+        let charlie: AccountId = "charlie".parse().unwrap();
+        let token_id = "token".to_string();
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice.clone())
+            .build());
+
+        let mut contract = ApprovalContract {};
+
+        Nep145Controller::deposit_to_storage_account(
+            &mut contract,
+            &alice,
+            NearToken::from_near(1),
+        )
+        .unwrap();
+
+        let approve = |contract: &mut ApprovalContract, account_id: &AccountId| {
+            contract
+                .approve(&Nep178Approve {
+                    token_id: token_id.clone(),
+                    current_owner_id: alice.clone().into(),
+                    account_id: account_id.clone().into(),
+                })
+                .unwrap()
+        };
+
+        contract
+            .mint_with_metadata(&token_id, &alice, &TokenMetadata::new().title("Original"))
+            .unwrap();
+        assert_eq!(approve(&mut contract, &bob), 0);
+        assert_eq!(approve(&mut contract, &charlie), 1);
+
+        contract.burn_with_metadata(&token_id, &alice).unwrap();
+
+        contract
+            .mint_with_metadata(&token_id, &alice, &TokenMetadata::new().title("Re-minted"))
+            .unwrap();
+
+        assert_eq!(contract.get_approval_id_for(&token_id, &bob), None);
+        assert!(contract.get_approvals_for(&token_id).is_empty());
+
+        assert_eq!(approve(&mut contract, &charlie), 0);
+        assert_eq!(contract.get_approval_id_for(&token_id, &charlie), Some(0));
+    }
+}
+
+mod input_limits {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env, NearToken};
+    use near_sdk_contract_tools::standard::nep171::{
+        error::{Nep171MintError, Nep171TransferError},
+        DEFAULT_MAX_TOKEN_ID_LEN,
+    };
+
+    use super::*;
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[nep171(max_token_id_len = "8", max_memo_len = "4")]
+    #[near(contract_state)]
+    struct LimitedContract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob".parse().unwrap()
+    }
+
+    fn transfer(token_id: &str, memo: &str) -> Nep171Transfer<'static> {
+        Nep171Transfer::new(
+            token_id.to_string(),
+            alice(),
+            bob(),
+            Nep171TransferAuthorization::Owner,
+        )
+        .memo(memo.to_string())
+    }
+
+    #[test]
+    fn mint_at_limit() {
+        let mut contract = LimitedContract {};
+
+        contract
+            .mint(&Nep171Mint::new(vec!["12345678".to_string()], alice()).memo("memo"))
+            .unwrap();
+
+        assert_eq!(contract.token_owner(&"12345678".to_string()), Some(alice()));
+    }
+
+    #[test]
+    fn mint_over_limit() {
+        let mut contract = LimitedContract {};
+
+        let err = contract
+            .mint(&Nep171Mint::new(vec!["123456789".to_string()], alice()))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Nep171MintError::TokenIdTooLong(e) if e.len == 9 && e.max_len == 8,
+        ));
+
+        let err = contract
+            .mint(&Nep171Mint::new(vec!["token".to_string()], alice()).memo("memos"))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Nep171MintError::MemoTooLong(e) if e.len == 5 && e.max_len == 4,
+        ));
+
+        assert_eq!(contract.token_owner(&"token".to_string()), None);
+    }
+
+    #[test]
+    fn transfer_at_limit() {
+        let mut contract = LimitedContract {};
+        contract.mint_unchecked(&["12345678".to_string()], &alice());
+
+        contract
+            .external_transfer(&transfer("12345678", "memo"))
+            .unwrap();
+
+        assert_eq!(contract.token_owner(&"12345678".to_string()), Some(bob()));
+    }
+
+    #[test]
+    fn transfer_over_limit() {
+        let mut contract = LimitedContract {};
+        contract.mint_unchecked(&["123456789".to_string()], &alice());
+
+        let err = contract
+            .external_transfer(&transfer("123456789", "memo"))
+            .unwrap_err();
+        assert!(matches!(err, Nep171TransferError::TokenIdTooLong(_)));
+
+        contract.mint_unchecked(&["token".to_string()], &alice());
+
+        let err = contract
+            .external_transfer(&transfer("token", "memos"))
+            .unwrap_err();
+        assert!(matches!(err, Nep171TransferError::MemoTooLong(_)));
+    }
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[near(contract_state)]
+    struct DefaultLimitsContract {}
+
+    #[test]
+    fn default_token_id_limit_applies_to_mint_only() {
+        let mut contract = DefaultLimitsContract {};
+        let long_id = "a".repeat(DEFAULT_MAX_TOKEN_ID_LEN + 1);
+
+        let err = contract
+            .mint(&Nep171Mint::new(vec![long_id.clone()], alice()))
+            .unwrap_err();
+        assert!(matches!(err, Nep171MintError::TokenIdTooLong(_)));
+
+        // e.g. minted before the limit was introduced
+        contract.mint_unchecked(&[long_id.clone()], &alice());
+
+        contract
+            .external_transfer(&transfer(&long_id, "memo"))
+            .unwrap();
+
+        assert_eq!(contract.token_owner(&long_id), Some(bob()));
+    }
+
+    #[test]
+    #[should_panic = "Memo length 5 exceeds maximum of 4 bytes"]
+    fn nft_transfer_memo_over_limit() {
+        let mut contract = LimitedContract {};
+        contract.mint_unchecked(&["token".to_string()], &alice());
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+
+        contract.nft_transfer(bob(), "token".to_string(), None, Some("memos".to_string()));
+    }
+}
+
+mod track_supply {
+    use near_sdk::json_types::U128;
+    use near_sdk_contract_tools::standard::nep171::error::{Nep171BurnError, Nep171MintError};
+
+    use super::*;
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[nep171(track_supply)]
+    #[near(contract_state)]
+    struct SupplyContract {}
+
+    fn token_ids(ids: &[&str]) -> Vec<TokenId> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn supply_after_mint_and_burn() {
+        let mut contract = SupplyContract {};
+        let alice: AccountId = "alice".parse().unwrap();
+
+        assert_eq!(contract.nft_total_supply(), U128(0));
+
+        contract
+            .mint(&Nep171Mint::new(token_ids(&["a", "b", "c"]), alice.clone()))
+            .unwrap();
+
+        assert_eq!(contract.total_supply(), 3);
+
+        contract
+            .burn(&Nep171Burn::new(token_ids(&["a", "c"]), alice.clone()))
+            .unwrap();
+
+        assert_eq!(contract.total_supply(), 1);
+        assert_eq!(contract.nft_total_supply(), U128(1));
+    }
+
+    #[test]
+    fn failed_batch_does_not_change_supply() {
+        let mut contract = SupplyContract {};
+        let alice: AccountId = "alice".parse().unwrap();
+
+        contract
+            .mint(&Nep171Mint::new(token_ids(&["a"]), alice.clone()))
+            .unwrap();
+        contract
+            .mint(&Nep171Mint::new(token_ids(&["b", "a"]), alice.clone()))
+            .unwrap_err();
+        contract
+            .burn(&Nep171Burn::new(token_ids(&["a", "b"]), alice))
+            .unwrap_err();
+
+        assert_eq!(contract.total_supply(), 1);
+    }
+
+    #[test]
+    fn duplicate_token_ids_rejected() {
+        let mut contract = SupplyContract {};
+        let alice: AccountId = "alice".parse().unwrap();
+
+        let err = contract
+            .mint(&Nep171Mint::new(token_ids(&["a", "a"]), alice.clone()))
+            .unwrap_err();
+        assert!(matches!(err, Nep171MintError::DuplicateTokenId(_)));
+        assert_eq!(contract.token_owner(&"a".to_string()), None);
+        assert_eq!(contract.total_supply(), 0);
+
+        contract
+            .mint(&Nep171Mint::new(token_ids(&["a", "b"]), alice.clone()))
+            .unwrap();
+
+        let err = contract
+            .burn(&Nep171Burn::new(token_ids(&["a", "a"]), alice))
+            .unwrap_err();
+        assert!(matches!(err, Nep171BurnError::DuplicateTokenId(_)));
+        assert_eq!(contract.total_supply(), 2);
+    }
+}
+
+mod minting_closed {
+    use near_sdk_contract_tools::standard::nep171::error::Nep171MintError;
+
+    use super::*;
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[near(contract_state)]
+    struct DropContract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob".parse().unwrap()
+    }
+
+    fn mint(contract: &mut DropContract, token_id: &str) -> Result<(), Nep171MintError> {
+        contract.mint(&Nep171Mint::new(vec![token_id.to_string()], alice()))
+    }
+
+    #[test]
+    fn close_minting_keeps_transfers_open() {
+        let mut contract = DropContract {};
+
+        mint(&mut contract, "minted").unwrap();
+        contract.close_minting();
+
+        assert!(contract.minting_closed());
+        assert!(matches!(
+            mint(&mut contract, "late").unwrap_err(),
+            Nep171MintError::MintClosed(_),
+        ));
+        assert_eq!(contract.token_owner(&"late".to_string()), None);
+
+        contract.resume_minting();
+        assert!(mint(&mut contract, "late").is_err());
+
+        contract
+            .external_transfer(&Nep171Transfer::new(
+                "minted".to_string(),
+                alice(),
+                bob(),
+                Nep171TransferAuthorization::Owner,
+            ))
+            .unwrap();
+
+        assert_eq!(contract.token_owner(&"minted".to_string()), Some(bob()));
+    }
+
+    #[test]
+    fn pause_and_resume_minting() {
+        let mut contract = DropContract {};
+
+        contract.pause_minting();
+
+        assert!(contract.minting_paused());
+        assert!(matches!(
+            mint(&mut contract, "token").unwrap_err(),
+            Nep171MintError::MintPaused(_),
+        ));
+
+        contract.resume_minting();
+
+        assert!(!contract.minting_paused());
+        mint(&mut contract, "token").unwrap();
+        assert_eq!(contract.token_owner(&"token".to_string()), Some(alice()));
+    }
+}
+
+mod burnable {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env, NearToken};
+
+    use super::*;
+
+    #[derive(Nep171, Nep177, PanicOnDefault)]
+    #[nep171(burnable, token_loader = "metadata")]
+    #[near(contract_state)]
+    struct MetadataContract {}
+
+    #[derive(Nep171, Nep177, PanicOnDefault)]
+    #[nep171(burnable = "metadata")]
+    #[near(contract_state)]
+    struct BurnMetadataContract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn as_alice() {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+    }
+
+    #[test]
+    fn nft_burn_removes_metadata() {
+        let mut contract = MetadataContract {};
+        let token_id = "token".to_string();
+
+        contract
+            .mint_with_metadata(&token_id, &alice(), &TokenMetadata::new().title("Token"))
+            .unwrap();
+
+        as_alice();
+        contract.nft_burn(vec![token_id.clone()]);
+
+        assert_eq!(contract.token_owner(&token_id), None);
+        assert_eq!(contract.token_metadata(&token_id), None);
+    }
+
+    #[test]
+    fn nft_burn_metadata_flag() {
+        let mut contract = BurnMetadataContract {};
+        let token_id = "token".to_string();
+
+        contract
+            .mint_with_metadata(&token_id, &alice(), &TokenMetadata::new().title("Token"))
+            .unwrap();
+
+        as_alice();
+        contract.nft_burn(vec![token_id.clone()]);
+
+        assert_eq!(contract.token_owner(&token_id), None);
+        assert_eq!(contract.token_metadata(&token_id), None);
+    }
+}
+
+mod token_lock {
+    use near_sdk::{test_utils::VMContextBuilder, testing_env, NearToken};
+    use near_sdk_contract_tools::standard::nep171::error::{
+        Nep171BurnError, Nep171LockError, Nep171TransferError,
+    };
+
+    use super::*;
+
+    #[derive(Nep171, PanicOnDefault)]
+    #[near(contract_state)]
+    struct StakingContract {}
+
+    fn alice() -> AccountId {
+        "alice".parse().unwrap()
+    }
+
+    fn bob() -> AccountId {
+        "bob".parse().unwrap()
+    }
+
+    fn market() -> AccountId {
+        "market".parse().unwrap()
+    }
+
+    fn setup() -> StakingContract {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+
+        let mut contract = StakingContract {};
+        contract
+            .mint(&Nep171Mint::new(vec!["token".to_string()], alice()))
+            .unwrap();
+        contract
+            .lock_token(&"token".to_string(), &market())
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn lock_blocks_transfer_until_unlocked() {
+        let mut contract = setup();
+        let token_id = "token".to_string();
+
+        assert!(contract.is_locked(&token_id));
+        assert_eq!(contract.token_locker(&token_id), Some(market()));
+        assert!(matches!(
+            contract.lock_token(&token_id, &bob()).unwrap_err(),
+            Nep171LockError::TokenLocked(_),
+        ));
+        assert!(matches!(
+            contract
+                .external_transfer(&Nep171Transfer::new(
+                    token_id.clone(),
+                    alice(),
+                    bob(),
+                    Nep171TransferAuthorization::Owner,
+                ))
+                .unwrap_err(),
+            Nep171TransferError::TokenLocked(_),
+        ));
+
+        assert_eq!(contract.unlock_token(&token_id), Some(market()));
+        assert!(!contract.is_locked(&token_id));
+
+        contract.nft_transfer(bob(), token_id.clone(), None, None);
+
+        assert_eq!(contract.token_owner(&token_id), Some(bob()));
+    }
+
+    #[test]
+    #[should_panic = "Token `token` is locked by `market`"]
+    fn nft_transfer_locked() {
+        let mut contract = setup();
+
+        contract.nft_transfer(bob(), "token".to_string(), None, None);
+    }
+
+    #[test]
+    fn lock_nonexistent_token() {
+        let mut contract = setup();
+
+        assert!(matches!(
+            contract
+                .lock_token(&"missing".to_string(), &market())
+                .unwrap_err(),
+            Nep171LockError::TokenDoesNotExist(_),
+        ));
+    }
+
+    #[test]
+    fn burn_locked_token_fails() {
+        let mut contract = setup();
+        let token_id = "token".to_string();
+
+        assert!(matches!(
+            contract
+                .burn(&Nep171Burn::new(vec![token_id.clone()], alice()))
+                .unwrap_err(),
+            Nep171BurnError::TokenLocked(_),
+        ));
+        assert_eq!(contract.token_owner(&token_id), Some(alice()));
+        assert!(contract.is_locked(&token_id));
+
+        contract.unlock_token(&token_id);
+        contract
+            .burn(&Nep171Burn::new(vec![token_id.clone()], alice()))
+            .unwrap();
+
+        assert_eq!(contract.token_owner(&token_id), None);
+    }
+
+    #[test]
+    fn burn_unchecked_removes_lock() {
+        let mut contract = setup();
+        let token_id = "token".to_string();
+
+        contract.burn_unchecked(&[token_id.clone()]);
+
+        assert!(!contract.is_locked(&token_id));
+    }
+}
+
 mod tests {
     use near_sdk::{
         test_utils::{get_logs, VMContextBuilder},
@@ -175,3 +820,9 @@ mod tests {
         );
     }
 }
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/nep171/track_supply_with_nep181.rs");
+}
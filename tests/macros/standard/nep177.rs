@@ -0,0 +1,75 @@
+use near_sdk::{near, test_utils::get_logs, PanicOnDefault};
+use near_sdk_contract_tools::{
+    nft::*,
+    standard::nep177::{ContractMetadataPatch, InvalidContractMetadataError},
+};
+
+#[derive(Nep171, Nep177, PanicOnDefault)]
+#[near(contract_state)]
+struct DerivesNftMetadata {}
+
+#[near]
+impl DerivesNftMetadata {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        contract.set_contract_metadata(
+            &ContractMetadata::new("My NFT", "MNFT", Some("https://example.com".into())),
+        );
+
+        contract
+    }
+}
+
+#[test]
+fn update_single_field() {
+    let mut contract = DerivesNftMetadata::new();
+    let before = contract.nft_metadata();
+
+    contract
+        .update_contract_metadata(ContractMetadataPatch {
+            icon: Some("data:image/png;base64,".into()),
+            ..Default::default()
+        })
+        .unwrap();
+
+    let after = contract.nft_metadata();
+
+    assert_eq!(
+        after,
+        ContractMetadata {
+            icon: Some("data:image/png;base64,".into()),
+            ..before
+        },
+    );
+}
+
+#[test]
+fn update_empty_patch_is_noop() {
+    let mut contract = DerivesNftMetadata::new();
+    let before = contract.nft_metadata();
+    let logs_before = get_logs().len();
+
+    contract
+        .update_contract_metadata(ContractMetadataPatch::default())
+        .unwrap();
+
+    assert_eq!(contract.nft_metadata(), before);
+    assert_eq!(get_logs().len(), logs_before);
+}
+
+#[test]
+fn update_invalid_leaves_metadata_intact() {
+    let mut contract = DerivesNftMetadata::new();
+    let before = contract.nft_metadata();
+
+    let result = contract.update_contract_metadata(ContractMetadataPatch {
+        name: Some(String::new()),
+        icon: Some("icon".into()),
+        ..Default::default()
+    });
+
+    assert_eq!(result, Err(InvalidContractMetadataError::EmptyName));
+    assert_eq!(contract.nft_metadata(), before);
+}
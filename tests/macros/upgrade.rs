@@ -0,0 +1,80 @@
+use near_sdk::{env, near, test_utils::VMContextBuilder, testing_env, PanicOnDefault};
+use near_sdk_contract_tools::{upgrade::serialized::UpgradeCodeHook, Upgrade};
+
+const MAX_CODE_LEN: usize = 8;
+
+#[derive(Upgrade, PanicOnDefault)]
+#[upgrade(serializer = "borsh", hook = "empty", validate_code)]
+#[near(contract_state)]
+pub struct Contract {}
+
+impl UpgradeCodeHook for Contract {
+    fn on_upgrade_code(&self, code: &[u8]) {
+        if code.len() > MAX_CODE_LEN {
+            env::panic_str("Code too large");
+        }
+    }
+}
+
+#[test]
+fn upgrade_code_within_limit() {
+    testing_env!(VMContextBuilder::new().build());
+    let mut contract = Contract {};
+
+    contract.upgrade(vec![0; MAX_CODE_LEN]);
+}
+
+#[test]
+#[should_panic = "Code too large"]
+fn upgrade_code_hook_rejects_oversized_code() {
+    testing_env!(VMContextBuilder::new().build());
+    let mut contract = Contract {};
+
+    contract.upgrade(vec![0; MAX_CODE_LEN + 1]);
+}
+
+mod auto_owner_hook {
+    use near_sdk::{near, test_utils::VMContextBuilder, testing_env, AccountId, PanicOnDefault};
+    use near_sdk_contract_tools::{owner::Owner, upgrade::serialized::UpgradeHook, Owner, Upgrade};
+
+    #[derive(Owner, Upgrade, PanicOnDefault)]
+    #[owner]
+    #[upgrade(serializer = "borsh", hook = "auto")]
+    #[near(contract_state)]
+    pub struct Contract {}
+
+    fn setup(predecessor: &AccountId) -> Contract {
+        let owner: AccountId = "owner".parse().unwrap();
+        let mut contract = Contract {};
+        Owner::init(&mut contract, &owner);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(predecessor.clone())
+            .build());
+
+        contract
+    }
+
+    #[test]
+    fn owner_may_upgrade() {
+        let contract = setup(&"owner".parse().unwrap());
+
+        contract.on_upgrade();
+    }
+
+    #[test]
+    #[should_panic = "Owner only"]
+    fn non_owner_may_not_upgrade() {
+        let contract = setup(&"alice".parse().unwrap());
+
+        contract.on_upgrade();
+    }
+}
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/upgrade/auto_owner_hook.rs");
+    t.pass("tests/ui/upgrade/default_custom_hook.rs");
+    t.compile_fail("tests/ui/upgrade/owner_hook_without_owner.rs");
+}
@@ -0,0 +1,57 @@
+//! Core component derives accept a re-exported `crate` path.
+
+use near_sdk::{near, BorshStorageKey, PanicOnDefault};
+
+mod reexport {
+    pub use ::near_sdk_contract_tools as tools;
+}
+
+use reexport::tools::{
+    escrow::Escrow, migrate::MigrateHook, owner::Owner, pause::Pause, rbac::Rbac,
+    upgrade::serialized::UpgradeHook, Escrow, Migrate, Owner, Pause, Rbac, Upgrade,
+};
+
+#[derive(BorshStorageKey)]
+#[near]
+enum Role {
+    Admin,
+}
+
+#[derive(Owner, Pause, Rbac, Escrow, Upgrade, PanicOnDefault)]
+#[owner(crate = "crate::reexport::tools")]
+#[pause(crate = "crate::reexport::tools")]
+#[rbac(roles = "Role", crate = "crate::reexport::tools")]
+#[escrow(id = "u64", state = "String", crate = "crate::reexport::tools")]
+#[upgrade(hook = "owner", crate = "crate::reexport::tools")]
+#[near(contract_state)]
+pub struct Contract {}
+
+mod old {
+    use super::*;
+
+    #[derive(PanicOnDefault)]
+    #[near(contract_state)]
+    pub struct Old {
+        pub foo: u64,
+    }
+}
+
+#[derive(Migrate, PanicOnDefault)]
+#[migrate(from = "old::Old", crate = "crate::reexport::tools")]
+#[near(contract_state)]
+pub struct Migrated {
+    pub bar: u64,
+}
+
+impl MigrateHook for Migrated {
+    fn on_migrate(old: old::Old) -> Self {
+        Self { bar: old.foo }
+    }
+}
+
+fn assert_core<T: Owner + Pause + Rbac + Escrow + UpgradeHook>() {}
+
+fn main() {
+    assert_core::<Contract>();
+    let _ = Migrated::migrate;
+}
@@ -0,0 +1,30 @@
+//! `Nep297` and `#[event]` accept a re-exported `crate` path.
+
+mod reexport {
+    pub use ::near_sdk_contract_tools as tools;
+}
+
+use near_sdk::serde::Serialize;
+use reexport::tools::{event, standard::nep297::ToEventLog, Nep297};
+
+#[derive(Nep297, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[nep297(
+    standard = "x-reexport",
+    version = "1.0.0",
+    crate = "crate::reexport::tools"
+)]
+pub struct Derived;
+
+#[event(
+    standard = "x-reexport",
+    version = "1.0.0",
+    crate = "crate::reexport::tools",
+    macros = "crate::reexport::tools"
+)]
+pub struct Attribute;
+
+fn main() {
+    assert_eq!(Derived.to_event_log().event, "Derived");
+    assert_eq!(Attribute.to_event_log().event, "attribute");
+}
@@ -0,0 +1,31 @@
+//! Fungible token derives accept a re-exported `crate` path.
+
+use near_sdk::{near, PanicOnDefault};
+
+mod reexport {
+    pub use ::near_sdk_contract_tools as tools;
+}
+
+use reexport::tools::{
+    standard::{nep141::Nep141Controller, nep145::Nep145Controller, nep148::Nep148Controller},
+    FungibleToken, Nep141, Nep145, Nep148,
+};
+
+#[derive(Nep141, Nep145, Nep148, PanicOnDefault)]
+#[nep141(crate = "crate::reexport::tools")]
+#[nep145(crate = "crate::reexport::tools")]
+#[nep148(crate = "crate::reexport::tools")]
+#[near(contract_state)]
+pub struct Separate {}
+
+#[derive(FungibleToken, PanicOnDefault)]
+#[fungible_token(crate = "crate::reexport::tools")]
+#[near(contract_state)]
+pub struct Combined {}
+
+fn assert_fungible_token<T: Nep141Controller + Nep145Controller + Nep148Controller>() {}
+
+fn main() {
+    assert_fungible_token::<Separate>();
+    assert_fungible_token::<Combined>();
+}
@@ -0,0 +1,43 @@
+//! Non-fungible token derives accept a re-exported `crate` path.
+
+use near_sdk::{near, PanicOnDefault};
+
+mod reexport {
+    pub use ::near_sdk_contract_tools as tools;
+}
+
+use reexport::tools::{
+    standard::{
+        nep171::Nep171Controller, nep177, nep177::Nep177Controller, nep178,
+        nep178::Nep178Controller, nep181, nep181::Nep181Controller,
+    },
+    Nep171, Nep177, Nep178, Nep181, NonFungibleToken,
+};
+
+#[derive(Nep171, Nep177, Nep178, Nep181, PanicOnDefault)]
+#[nep171(
+    all_hooks = "(nep178::TokenApprovals, nep181::TokenEnumeration)",
+    check_external_transfer = "nep178::TokenApprovals",
+    token_data = "(nep177::TokenMetadata, nep178::TokenApprovals)",
+    crate = "crate::reexport::tools"
+)]
+#[nep177(crate = "crate::reexport::tools")]
+#[nep178(crate = "crate::reexport::tools")]
+#[nep181(crate = "crate::reexport::tools")]
+#[near(contract_state)]
+pub struct Separate {}
+
+#[derive(NonFungibleToken, PanicOnDefault)]
+#[non_fungible_token(crate = "crate::reexport::tools")]
+#[near(contract_state)]
+pub struct Combined {}
+
+fn assert_non_fungible_token<
+    T: Nep171Controller + Nep177Controller + Nep178Controller + Nep181Controller,
+>() {
+}
+
+fn main() {
+    assert_non_fungible_token::<Separate>();
+    assert_non_fungible_token::<Combined>();
+}
@@ -0,0 +1,41 @@
+//! `SimpleMultisig` accepts a re-exported `crate` path.
+
+use near_sdk::{near, BorshStorageKey, PanicOnDefault};
+
+mod reexport {
+    pub use ::near_sdk_contract_tools as tools;
+}
+
+use reexport::tools::{
+    approval::{
+        native_transaction_action::NativeTransactionAction,
+        simple_multisig::{AccountAuthorizer, ApprovalState, Configuration},
+        ApprovalManager,
+    },
+    Rbac, SimpleMultisig,
+};
+
+#[derive(BorshStorageKey)]
+#[near]
+enum Role {
+    Multisig,
+}
+
+#[derive(Rbac, SimpleMultisig, PanicOnDefault)]
+#[rbac(roles = "Role", crate = "crate::reexport::tools")]
+#[simple_multisig(
+    action = "NativeTransactionAction",
+    role = "Role::Multisig",
+    crate = "crate::reexport::tools"
+)]
+#[near(contract_state)]
+pub struct Contract {}
+
+fn assert_multisig<
+    T: AccountAuthorizer + ApprovalManager<NativeTransactionAction, ApprovalState, Configuration<T>>,
+>() {
+}
+
+fn main() {
+    assert_multisig::<Contract>();
+}
@@ -0,0 +1,12 @@
+//! `#[nep171(track_supply)]` cannot be combined with `Nep181`, which also
+//! exports `nft_total_supply`.
+
+use near_sdk::{near, PanicOnDefault};
+use near_sdk_contract_tools::{Nep171, Nep181};
+
+#[derive(Nep171, Nep181, PanicOnDefault)]
+#[nep171(track_supply)]
+#[near(contract_state)]
+pub struct Contract {}
+
+fn main() {}
@@ -0,0 +1,16 @@
+//! `allowed_chars` extends the set of characters permitted in event names.
+
+use near_sdk_contract_tools::{standard::nep297::ToEventLog, Nep297};
+
+#[derive(Nep297)]
+#[nep297(
+    standard = "x-chars",
+    version = "1.0.0",
+    name = "nft.mint",
+    allowed_chars = "_."
+)]
+pub struct NftMint;
+
+fn main() {
+    assert_eq!(NftMint.to_event_log().event, "nft.mint");
+}
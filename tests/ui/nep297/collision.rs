@@ -0,0 +1,13 @@
+//! Variants whose event names collide after renaming are rejected.
+
+use near_sdk_contract_tools::Nep297;
+
+#[allow(non_camel_case_types)]
+#[derive(Nep297)]
+#[nep297(standard = "x-collision", version = "1.0.0", rename_all = "snake_case")]
+pub enum Collision {
+    HelloWorld,
+    Hello_World,
+}
+
+fn main() {}
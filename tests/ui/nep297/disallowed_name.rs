@@ -0,0 +1,10 @@
+//! Event names may only contain ASCII alphanumerics and the allowed
+//! characters (by default `_`, `-`, and spaces).
+
+use near_sdk_contract_tools::Nep297;
+
+#[derive(Nep297)]
+#[nep297(standard = "x-chars", version = "1.0.0", name = "nft.mint")]
+pub struct NftMint;
+
+fn main() {}
@@ -0,0 +1,17 @@
+//! Variants with distinct event names after renaming are accepted.
+
+use near_sdk_contract_tools::{standard::nep297::ToEventLog, Nep297};
+
+#[allow(non_camel_case_types)]
+#[derive(Nep297)]
+#[nep297(standard = "x-collision", version = "1.0.0", rename_all = "snake_case")]
+pub enum NoCollision {
+    HelloWorld,
+    #[nep297(name = "hello_world_2")]
+    Hello_World,
+}
+
+fn main() {
+    assert_eq!(NoCollision::HelloWorld.to_event_log().event, "hello_world");
+    assert_eq!(NoCollision::Hello_World.to_event_log().event, "hello_world_2");
+}
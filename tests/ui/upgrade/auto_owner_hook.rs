@@ -0,0 +1,17 @@
+//! With `hook = "auto"`, `Upgrade` uses the `"owner"` hook when the struct
+//! has an `#[owner]` attribute.
+
+use near_sdk::{near, PanicOnDefault};
+use near_sdk_contract_tools::{upgrade::serialized::UpgradeHook, Owner, Upgrade};
+
+#[derive(Owner, Upgrade, PanicOnDefault)]
+#[owner]
+#[upgrade(hook = "auto")]
+#[near(contract_state)]
+pub struct Contract {}
+
+fn assert_upgrade_hook<T: UpgradeHook>() {}
+
+fn main() {
+    assert_upgrade_hook::<Contract>();
+}
@@ -0,0 +1,17 @@
+//! Without a `hook` option, `Upgrade` defaults to the `"custom"` hook even if
+//! the struct derives `Owner` and has an `#[owner]` attribute, so the
+//! contract provides its own `UpgradeHook` implementation.
+
+use near_sdk::{near, PanicOnDefault};
+use near_sdk_contract_tools::{upgrade::serialized::UpgradeHook, Owner, Upgrade};
+
+#[derive(Owner, Upgrade, PanicOnDefault)]
+#[owner]
+#[near(contract_state)]
+pub struct Contract {}
+
+impl UpgradeHook for Contract {
+    fn on_upgrade(&self) {}
+}
+
+fn main() {}
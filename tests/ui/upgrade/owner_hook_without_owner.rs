@@ -0,0 +1,11 @@
+//! `hook = "owner"` requires the contract to implement `Owner`.
+
+use near_sdk::{near, PanicOnDefault};
+use near_sdk_contract_tools::Upgrade;
+
+#[derive(Upgrade, PanicOnDefault)]
+#[upgrade(hook = "owner")]
+#[near(contract_state)]
+pub struct Contract {}
+
+fn main() {}
@@ -5,11 +5,12 @@ use near_sdk::{
     json_types::{Base64VecU8, U128},
     near,
     store::Vector,
-    PanicOnDefault,
+    AccountId, NearToken, PanicOnDefault, Promise,
 };
-use near_sdk_contract_tools::ft::*;
+use near_sdk_contract_tools::{ft::*, owner::Owner, pause::Pause, Owner, Pause};
 
-#[derive(FungibleToken, PanicOnDefault)]
+#[derive(FungibleToken, Owner, Pause, PanicOnDefault)]
+#[fungible_token(pausable, burnable, burn_from = "owner", storage_deposit_batch)]
 #[near(contract_state)]
 pub struct Contract {
     blobs: Vector<Vec<u8>>,
@@ -24,6 +25,7 @@ impl Contract {
         };
 
         contract.set_metadata(&ContractMetadata::new("My Fungible Token", "MYFT", 24));
+        Owner::init(&mut contract, &env::predecessor_account_id());
 
         contract
     }
@@ -36,6 +38,41 @@ impl Contract {
         .unwrap();
     }
 
+    #[payable]
+    pub fn register_and_mint(&mut self, account_id: AccountId, amount: U128) {
+        Self::require_owner();
+
+        let refund = Nep141RegisterAndMint::register_and_mint(
+            self,
+            &account_id,
+            amount.0,
+            env::attached_deposit(),
+        )
+        .unwrap_or_else(|e| env::panic_str(&e.to_string()));
+
+        if !refund.is_zero() {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+    }
+
+    pub fn set_storage_balance_min(&mut self, min: NearToken) {
+        Self::require_owner();
+
+        let bounds = StorageBalanceBounds {
+            min,
+            ..self.get_storage_balance_bounds()
+        };
+        self.set_storage_balance_bounds(&bounds);
+    }
+
+    pub fn pause(&mut self) {
+        Pause::pause(self);
+    }
+
+    pub fn unpause(&mut self) {
+        Pause::unpause(self);
+    }
+
     pub fn use_storage(&mut self, blob: Base64VecU8) {
         let storage_start = env::storage_usage();
         let blob = blob.into();
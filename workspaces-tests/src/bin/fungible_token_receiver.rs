@@ -1,7 +1,8 @@
 workspaces_tests::predicate!();
 
 use near_sdk::{
-    env, json_types::U128, log, near, AccountId, NearToken, PanicOnDefault, PromiseOrValue,
+    env, json_types::U128, log, near, AccountId, Gas, NearToken, PanicOnDefault, Promise,
+    PromiseOrValue,
 };
 use near_sdk_contract_tools::ft::*;
 
@@ -33,6 +34,19 @@ impl Nep141Receiver for Contract {
                 .into();
         }
 
+        if msg == "unregister_sender" {
+            log!("Unregistering {} before returning all tokens", sender_id);
+
+            return Contract::ext(sender_id)
+                .storage_unregister_from(env::predecessor_account_id())
+                .then(Contract::ext(env::current_account_id()).return_value(amount))
+                .into();
+        }
+
+        if let Some(unused) = msg.strip_prefix("return:") {
+            return PromiseOrValue::Value(U128(unused.parse().unwrap()));
+        }
+
         PromiseOrValue::Value(if msg == "return" { amount } else { U128(0) })
     }
 }
@@ -48,4 +62,23 @@ impl Contract {
     pub fn return_value(&self, value: U128) -> U128 {
         value
     }
+
+    pub fn ft_transfer_call_from_self(
+        &mut self,
+        token_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> Promise {
+        ext_nep141::ext(token_id)
+            .with_attached_deposit(NearToken::from_yoctonear(1u128))
+            .with_static_gas(Gas::from_tgas(150))
+            .ft_transfer_call(receiver_id, amount, None, msg)
+    }
+
+    pub fn storage_unregister_from(&mut self, token_id: AccountId) -> Promise {
+        ext_nep145::ext(token_id)
+            .with_attached_deposit(NearToken::from_yoctonear(1u128))
+            .storage_unregister(None)
+    }
 }
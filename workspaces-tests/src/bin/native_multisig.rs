@@ -47,15 +47,22 @@ impl Contract {
         receiver_id: AccountId,
         actions: Vec<native_transaction_action::PromiseAction>,
     ) -> u32 {
-        let request_id = self
-            .create_request(
-                native_transaction_action::NativeTransactionAction {
-                    receiver_id,
-                    actions,
-                },
-                ApprovalState::new(),
-            )
-            .unwrap();
+        let action = native_transaction_action::NativeTransactionAction {
+            receiver_id,
+            actions,
+        };
+
+        if let Err(errors) = action.validate() {
+            env::panic_str(
+                &errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            );
+        }
+
+        let request_id = self.create_request(action, ApprovalState::new()).unwrap();
 
         near_sdk::log!(format!("Request ID: {request_id}"));
 
@@ -70,6 +77,10 @@ impl Contract {
         <Contract as ApprovalManager<_, _, _>>::is_approved_for_execution(request_id).is_ok()
     }
 
+    pub fn get_approval_state(&self, request_id: u32) -> Option<ApprovalState> {
+        <Contract as ApprovalManager<_, _, _>>::get_approval_state(request_id)
+    }
+
     pub fn execute(&mut self, request_id: u32) -> Promise {
         self.execute_request(request_id).unwrap()
     }
@@ -0,0 +1,45 @@
+workspaces_tests::predicate!();
+
+use near_sdk::{env, near, PanicOnDefault};
+use near_sdk_contract_tools::nft::*;
+
+/// Implements NEP-171, NEP-177, and NEP-178 using the individual derive
+/// macros instead of `NonFungibleToken`.
+#[derive(Nep171, Nep177, Nep178, PanicOnDefault)]
+#[nep171(
+    all_hooks = "TokenApprovals",
+    check_external_transfer = "TokenApprovals",
+    token_loader = "metadata, approvals"
+)]
+#[near(contract_state)]
+pub struct Contract {}
+
+#[near]
+impl Contract {
+    #[init]
+    pub fn new() -> Self {
+        let mut contract = Self {};
+
+        contract.set_contract_metadata(&ContractMetadata::new(
+            "Composed NFT".to_string(),
+            "CNFT".to_string(),
+            None,
+        ));
+
+        contract
+    }
+
+    pub fn mint(&mut self, token_ids: Vec<TokenId>) {
+        let receiver = env::predecessor_account_id();
+        for token_id in token_ids {
+            self.mint_with_metadata(
+                &token_id,
+                &receiver,
+                &TokenMetadata::new()
+                    .title(token_id.clone())
+                    .description("description"),
+            )
+            .unwrap_or_else(|e| env::panic_str(&format!("Failed to mint: {:#?}", e)));
+        }
+    }
+}
@@ -7,16 +7,20 @@ use near_sdk_contract_tools::{
         nep171::{CheckExternalTransfer, LoadTokenMetadata},
         *,
     },
+    owner::Owner,
+    Owner,
 };
 
-#[derive(NonFungibleToken, PanicOnDefault)]
+#[derive(NonFungibleToken, Owner, PanicOnDefault)]
 #[non_fungible_token(
     transfer_hook = "Self",
     approve_hook = "Self",
     revoke_hook = "Self",
     revoke_all_hook = "Self",
     token_data = "ExtraTokenData",
-    check_external_transfer = "ExtraCheckExternalTransfer"
+    check_external_transfer = "ExtraCheckExternalTransfer",
+    entrypoints(burn, batch_approvals),
+    update_metadata = "owner"
 )]
 #[near(contract_state)]
 pub struct Contract {}
@@ -108,6 +112,8 @@ impl Contract {
     pub fn new() -> Self {
         let mut contract = Self {};
 
+        Owner::init(&mut contract, &env::predecessor_account_id());
+
         contract.set_contract_metadata(&ContractMetadata::new(
             "My NFT Smart Contract".to_string(),
             "MNSC".to_string(),
@@ -117,17 +123,19 @@ impl Contract {
         contract
     }
 
-    pub fn mint(&mut self, token_ids: Vec<TokenId>) {
+    pub fn mint(&mut self, token_ids: Vec<TokenId>) -> Vec<TokenId> {
         let receiver = env::predecessor_account_id();
-        for token_id in token_ids {
-            self.mint_with_metadata(
-                &token_id,
-                &receiver,
-                &TokenMetadata::new()
+        let tokens = token_ids
+            .into_iter()
+            .map(|token_id| {
+                let metadata = TokenMetadata::new()
                     .title(token_id.clone())
-                    .description("description"),
-            )
-            .unwrap_or_else(|e| env::panic_str(&format!("Failed to mint: {:#?}", e)));
-        }
+                    .description("description");
+                (token_id, metadata)
+            })
+            .collect::<Vec<_>>();
+
+        self.mint_batch_with_metadata(&receiver, &tokens)
+            .unwrap_or_else(|e| env::panic_str(&format!("Failed to mint: {:#?}", e)))
     }
 }
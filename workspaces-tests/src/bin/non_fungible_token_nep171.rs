@@ -35,4 +35,12 @@ impl Contract {
         )
         .unwrap_or_else(|e| env::panic_str(&format!("Failed to mint: {:#?}", e)));
     }
+
+    pub fn freeze_transfers(&mut self) {
+        Nep171Controller::freeze_transfers(self);
+    }
+
+    pub fn unfreeze_transfers(&mut self) {
+        Nep171Controller::unfreeze_transfers(self);
+    }
 }
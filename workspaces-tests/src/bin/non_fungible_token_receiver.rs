@@ -1,6 +1,8 @@
 workspaces_tests::predicate!();
 
-use near_sdk::{env, log, near, AccountId, NearToken, PanicOnDefault, PromiseOrValue};
+use near_sdk::{
+    env, log, near, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue,
+};
 use near_sdk_contract_tools::standard::nep171::*;
 
 #[derive(PanicOnDefault)]
@@ -32,6 +34,17 @@ impl Nep171Receiver for Contract {
                 .nft_transfer(account_id.parse().unwrap(), token_id, None, None)
                 .then(Contract::ext(env::current_account_id()).return_true()) // ask to return the token even though we don't own it anymore
                 .into();
+        } else if msg == "freeze" {
+            log!("Freezing transfers and returning {}", token_id);
+            return Promise::new(env::predecessor_account_id())
+                .function_call(
+                    "freeze_transfers".to_string(),
+                    vec![],
+                    NearToken::from_yoctonear(0),
+                    Gas::from_tgas(5),
+                )
+                .then(Contract::ext(env::current_account_id()).return_true())
+                .into();
         }
 
         PromiseOrValue::Value(msg == "return")
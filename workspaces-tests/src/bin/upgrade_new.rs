@@ -1,6 +1,6 @@
 workspaces_tests::predicate!();
 
-use near_sdk::{near, PanicOnDefault};
+use near_sdk::{env, near, PanicOnDefault};
 use near_sdk_contract_tools::{migrate::*, Migrate};
 
 #[near]
@@ -30,6 +30,18 @@ impl ContractNew {
         Self { bar: 0 }
     }
 
+    /// Alternative post-upgrade function that takes arguments.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate_with_offset(offset: u64) -> Self {
+        let old_schema = env::state_read::<ContractOld>()
+            .unwrap_or_else(|| env::panic_str("Failed to read old state"));
+
+        Self {
+            bar: u64::from(old_schema.foo) + offset,
+        }
+    }
+
     pub fn get_bar(&self) -> u64 {
         self.bar
     }
@@ -6,10 +6,11 @@ use near_sdk::{
 use near_sdk_contract_tools::{
     nft::StorageBalance,
     standard::{
-        nep141::{FtTransferData, Nep141Event},
+        nep141::{FtTransferData, Nep141Event, REFUND_MEMO, UNREFUNDABLE_MEMO},
         nep145::error::InsufficientBalanceError,
         nep297::Event,
     },
+    testing::{expect_event, find_events},
 };
 use near_workspaces::{network::Sandbox, operations::Function, Account, Contract, Worker};
 use pretty_assertions::assert_eq;
@@ -126,6 +127,164 @@ async fn transfer_normal() {
     assert_eq!(ft_balance_of(&contract, charlie.id()).await, 10);
 }
 
+#[tokio::test]
+async fn transfer_paused() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_balances(2, |i| 10u128.pow(3 - i as u32).into()).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    contract.call("pause").transact().await.unwrap().unwrap();
+
+    let result = alice
+        .call(contract.id(), "ft_transfer")
+        .deposit(ONE_YOCTO)
+        .args_json(json!({
+            "receiver_id": bob.id(),
+            "amount": "10",
+        }))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        "Smart contract panicked: Disallowed while contract is paused",
+    );
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 1000);
+    assert_eq!(ft_balance_of(&contract, bob.id()).await, 100);
+
+    let result = contract
+        .call("ft_burn_from")
+        .deposit(ONE_YOCTO)
+        .args_json(json!({ "account_id": alice.id(), "amount": "100" }))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        "Smart contract panicked: Disallowed while contract is paused",
+    );
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 1000);
+    assert_eq!(ft_total_supply(&contract).await, 1100);
+
+    contract.call("unpause").transact().await.unwrap().unwrap();
+
+    alice
+        .call(contract.id(), "ft_transfer")
+        .deposit(ONE_YOCTO)
+        .args_json(json!({
+            "receiver_id": bob.id(),
+            "amount": "10",
+        }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 990);
+    assert_eq!(ft_balance_of(&contract, bob.id()).await, 110);
+}
+
+async fn ft_total_supply(contract: &Contract) -> u128 {
+    contract
+        .view("ft_total_supply")
+        .await
+        .unwrap()
+        .json::<U128>()
+        .unwrap()
+        .0
+}
+
+#[tokio::test]
+async fn burn() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_balances(2, |i| 10u128.pow(3 - i as u32).into()).await;
+    let alice = &accounts[0];
+
+    let result = alice
+        .call(contract.id(), "ft_burn")
+        .deposit(ONE_YOCTO)
+        .args_json(json!({ "amount": "400", "memo": "burn" }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let burn = expect_event(result.logs(), "nep141", "ft_burn");
+    assert_eq!(
+        burn.data,
+        json!([{
+            "owner_id": alice.id(),
+            "amount": "400",
+            "memo": "burn",
+        }]),
+    );
+
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 600);
+    assert_eq!(ft_total_supply(&contract).await, 700);
+}
+
+#[tokio::test]
+async fn burn_more_than_balance_fail() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_balances(1, |_| 1000.into()).await;
+    let alice = &accounts[0];
+
+    let result = alice
+        .call(contract.id(), "ft_burn")
+        .deposit(ONE_YOCTO)
+        .args_json(json!({ "amount": "1001" }))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        format!(
+            "Smart contract panicked: The account {} does not have enough balance to withdraw 1001 (current balance: 1000).",
+            alice.id(),
+        ),
+    );
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 1000);
+    assert_eq!(ft_total_supply(&contract).await, 1000);
+}
+
+#[tokio::test]
+async fn burn_from() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_balances(2, |i| 10u128.pow(3 - i as u32).into()).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    let result = bob
+        .call(contract.id(), "ft_burn_from")
+        .deposit(ONE_YOCTO)
+        .args_json(json!({ "account_id": alice.id(), "amount": "100" }))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(&result, "Smart contract panicked: Owner only");
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 1000);
+
+    contract
+        .call("ft_burn_from")
+        .deposit(ONE_YOCTO)
+        .args_json(json!({ "account_id": alice.id(), "amount": "100" }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 900);
+    assert_eq!(ft_total_supply(&contract).await, 1000);
+}
+
 #[tokio::test]
 async fn transfer_zero() {
     let Setup {
@@ -399,7 +558,7 @@ async fn transfer_call_return() {
                 old_owner_id: bob.id().into(),
                 new_owner_id: alice.id().into(),
                 amount: U128(10),
-                memo: None,
+                memo: Some(REFUND_MEMO.into()),
             }])
             .to_event_string(),
         ]
@@ -410,6 +569,62 @@ async fn transfer_call_return() {
     assert_eq!(ft_balance_of(&contract, charlie.id()).await, 10);
 }
 
+#[tokio::test]
+async fn transfer_call_partial_return() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_balances(3, |i| 10u128.pow(3 - i as u32).into()).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    bob.batch(bob.id())
+        .deploy(RECEIVER_WASM)
+        .call(Function::new("new").args_json(json!({})))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = alice
+        .call(contract.id(), "ft_transfer_call")
+        .deposit(ONE_YOCTO)
+        .max_gas()
+        .args_json(json!({
+            "receiver_id": bob.id(),
+            "amount": "10",
+            "msg": "return:4", // keep 6, return 4
+        }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result.json::<U128>().unwrap(), U128(6));
+
+    let transfers = find_events(result.logs(), "nep141", "ft_transfer");
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(
+        transfers[0].data,
+        json!([{
+            "old_owner_id": alice.id(),
+            "new_owner_id": bob.id(),
+            "amount": "10",
+        }]),
+    );
+    assert_eq!(
+        transfers[1].data,
+        json!([{
+            "old_owner_id": bob.id(),
+            "new_owner_id": alice.id(),
+            "amount": "4",
+            "memo": REFUND_MEMO,
+        }]),
+    );
+
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 994);
+    assert_eq!(ft_balance_of(&contract, bob.id()).await, 106);
+}
+
 #[tokio::test]
 async fn transfer_call_inner_transfer() {
     let Setup {
@@ -464,7 +679,7 @@ async fn transfer_call_inner_transfer() {
                 old_owner_id: bob.id().into(),
                 new_owner_id: alice.id().into(),
                 amount: U128(10),
-                memo: None,
+                memo: Some(REFUND_MEMO.into()),
             }])
             .to_event_string(),
         ]
@@ -525,7 +740,7 @@ async fn transfer_call_inner_panic() {
                 old_owner_id: bob.id().into(),
                 new_owner_id: alice.id().into(),
                 amount: U128(10),
-                memo: None,
+                memo: Some(REFUND_MEMO.into()),
             }])
             .to_event_string(),
         ]
@@ -535,3 +750,335 @@ async fn transfer_call_inner_panic() {
     assert_eq!(ft_balance_of(&contract, bob.id()).await, 100);
     assert_eq!(ft_balance_of(&contract, charlie.id()).await, 10);
 }
+
+async fn storage_balance_of(contract: &Contract, account: &Account) -> Option<StorageBalance> {
+    contract
+        .view("storage_balance_of")
+        .args_json(json!({ "account_id": account.id() }))
+        .await
+        .unwrap()
+        .json::<Option<StorageBalance>>()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn unregister_force_burns_balance() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_balances(2, |i| 10u128.pow(3 - i as u32).into()).await;
+    let alice = &accounts[0];
+
+    let result = alice
+        .call(contract.id(), "storage_unregister")
+        .args_json(json!({ "force": true }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(result.json::<bool>().unwrap());
+
+    let burn = expect_event(result.logs(), "nep141", "ft_burn");
+    assert_eq!(
+        burn.data,
+        json!([{
+            "owner_id": alice.id(),
+            "amount": "1000",
+            "memo": "storage forced unregistration",
+        }]),
+    );
+
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 0);
+    assert_eq!(storage_balance_of(&contract, alice).await, None);
+
+    let total_supply = contract
+        .view("ft_total_supply")
+        .await
+        .unwrap()
+        .json::<U128>()
+        .unwrap();
+    assert_eq!(total_supply, U128(100));
+}
+
+#[tokio::test]
+async fn transfer_call_refund_to_unregistered_sender_burns() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_balances(3, |i| 10u128.pow(3 - i as u32).into()).await;
+    let bob = &accounts[1];
+    let charlie = &accounts[2];
+
+    for account in [bob, charlie] {
+        account
+            .batch(account.id())
+            .deploy(RECEIVER_WASM)
+            .call(Function::new("new").args_json(json!({})))
+            .transact()
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    // Charlie sends its entire balance to Bob, who asks Charlie to
+    // unregister before returning all of the tokens.
+    let result = charlie
+        .call(charlie.id(), "ft_transfer_call_from_self")
+        .max_gas()
+        .args_json(json!({
+            "token_id": contract.id(),
+            "receiver_id": bob.id(),
+            "amount": "10",
+            "msg": "unregister_sender",
+        }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result.json::<U128>().unwrap(), U128(10));
+
+    let transfers = find_events(result.logs(), "nep141", "ft_transfer");
+    assert_eq!(transfers.len(), 1);
+
+    let burn = expect_event(result.logs(), "nep141", "ft_burn");
+    assert_eq!(
+        burn.data,
+        json!([{
+            "owner_id": bob.id(),
+            "amount": "10",
+            "memo": UNREFUNDABLE_MEMO,
+        }]),
+    );
+
+    assert_eq!(storage_balance_of(&contract, charlie).await, None);
+    assert_eq!(ft_balance_of(&contract, charlie.id()).await, 0);
+    assert_eq!(ft_balance_of(&contract, bob.id()).await, 100);
+    assert_eq!(ft_total_supply(&contract).await, 1100);
+}
+
+#[tokio::test]
+async fn unregister_nonzero_balance_fail() {
+    let Setup {
+        contract,
+        accounts,
+        worker,
+    } = setup_balances(1, |_| 1000.into()).await;
+    let alice = &accounts[0];
+    let bob = worker.dev_create_account().await.unwrap();
+
+    bob.call(contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(ONE_NEAR.saturating_div(100))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    alice
+        .call(contract.id(), "ft_transfer")
+        .args_json(json!({ "receiver_id": bob.id(), "amount": "10" }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = bob
+        .call(contract.id(), "storage_unregister")
+        .args_json(json!({}))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        format!(
+            "Smart contract panicked: Account {} cannot unregister with a nonzero token balance: 10",
+            bob.id(),
+        ),
+    );
+    assert_eq!(ft_balance_of(&contract, bob.id()).await, 10);
+    assert!(storage_balance_of(&contract, &bob).await.is_some());
+
+    // Once the balance is gone, unregistering succeeds.
+    bob.call(contract.id(), "ft_transfer")
+        .args_json(json!({ "receiver_id": alice.id(), "amount": "10" }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = bob
+        .call(contract.id(), "storage_unregister")
+        .args_json(json!({}))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(result.json::<bool>().unwrap());
+    assert_eq!(storage_balance_of(&contract, &bob).await, None);
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 1000);
+}
+
+#[tokio::test]
+async fn register_and_mint() {
+    let Setup {
+        contract, accounts, ..
+    } = setup(1).await;
+    let alice = &accounts[0];
+
+    assert_eq!(storage_balance_of(&contract, alice).await, None);
+
+    let deposit = ONE_NEAR.saturating_div(100);
+
+    let result = contract
+        .as_account()
+        .call(contract.id(), "register_and_mint")
+        .deposit(deposit)
+        .args_json(json!({
+            "account_id": alice.id(),
+            "amount": "100",
+        }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    expect_event(result.logs(), "nep141", "ft_mint");
+
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 100);
+
+    // Only the storage fee for the new balance is kept; the rest is refunded.
+    let storage_balance = storage_balance_of(&contract, alice).await.unwrap();
+    assert_eq!(storage_balance.available, NearToken::from_yoctonear(0));
+    assert!(!storage_balance.total.is_zero());
+    assert!(storage_balance.total < deposit);
+}
+
+#[tokio::test]
+async fn register_and_mint_insufficient_deposit() {
+    let Setup {
+        contract, accounts, ..
+    } = setup(1).await;
+    let alice = &accounts[0];
+
+    let result = contract
+        .as_account()
+        .call(contract.id(), "register_and_mint")
+        .args_json(json!({
+            "account_id": alice.id(),
+            "amount": "100",
+        }))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(&result, "Smart contract panicked: Storage accounting error");
+    assert_eq!(ft_balance_of(&contract, alice.id()).await, 0);
+}
+
+#[tokio::test]
+async fn register_and_mint_not_owner() {
+    let Setup {
+        contract, accounts, ..
+    } = setup(1).await;
+    let alice = &accounts[0];
+
+    let result = alice
+        .call(contract.id(), "register_and_mint")
+        .deposit(ONE_NEAR.saturating_div(100))
+        .args_json(json!({
+            "account_id": alice.id(),
+            "amount": "100",
+        }))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(&result, "Smart contract panicked: Owner only");
+    assert_eq!(storage_balance_of(&contract, alice).await, None);
+}
+
+#[tokio::test]
+async fn storage_deposit_batch() {
+    let Setup {
+        contract, accounts, ..
+    } = setup(4).await;
+    let alice = &accounts[0];
+    let min = ONE_NEAR.saturating_div(100);
+
+    contract
+        .call("set_storage_balance_min")
+        .args_json(json!({ "min": min }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    alice
+        .call(contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(min)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Alice is already registered, so only the other three are charged.
+    let batch = json!({
+        "accounts": [
+            [accounts[1].id(), null],
+            [accounts[2].id(), null],
+            [alice.id(), null],
+            [accounts[3].id(), true],
+        ],
+    });
+
+    let result = alice
+        .call(contract.id(), "storage_deposit_batch")
+        .args_json(&batch)
+        .deposit(min.saturating_mul(3).saturating_sub(ONE_YOCTO))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        format!(
+            "Smart contract panicked: Storage deposit error: Attached deposit {} is less than required {}",
+            min.saturating_mul(3).saturating_sub(ONE_YOCTO),
+            min.saturating_mul(3),
+        ),
+    );
+    for account in &accounts[1..] {
+        assert_eq!(storage_balance_of(&contract, account).await, None);
+    }
+
+    let storage_balances = alice
+        .call(contract.id(), "storage_deposit_batch")
+        .args_json(&batch)
+        .deposit(min.saturating_mul(3))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap()
+        .json::<Vec<StorageBalance>>()
+        .unwrap();
+
+    let expected = StorageBalance {
+        total: min,
+        available: min,
+    };
+    assert_eq!(storage_balances, vec![expected.clone(); 4]);
+    for account in &accounts {
+        assert_eq!(
+            storage_balance_of(&contract, account).await,
+            Some(expected.clone()),
+        );
+    }
+}
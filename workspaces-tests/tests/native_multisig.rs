@@ -11,7 +11,7 @@ use near_workspaces::{
 };
 use pretty_assertions::assert_eq;
 use tokio::{join, time::sleep};
-use workspaces_tests_utils::ONE_NEAR;
+use workspaces_tests_utils::{expect_execution_error, ONE_NEAR};
 
 const WASM: &[u8] =
     include_bytes!("../../target/wasm32-unknown-unknown/release/native_multisig.wasm");
@@ -722,3 +722,41 @@ async fn external_xcc() {
 
     assert_eq!(calls_after, 1);
 }
+
+#[tokio::test]
+async fn request_rejects_invalid_actions() {
+    let Setup {
+        contract, accounts, ..
+    } = setup_roles(sandbox().await.unwrap(), 1).await;
+
+    let alice = &accounts[0];
+
+    let result = alice
+        .call(contract.id(), "request")
+        .args_json(json!({
+            "receiver_id": alice.id(),
+            "actions": [
+                PromiseAction::Transfer {
+                    amount: NearToken::from_near(1),
+                },
+                PromiseAction::AddFullAccessKey {
+                    public_key: "ed25519:garbage".to_string(),
+                    nonce: None,
+                },
+                PromiseAction::FunctionCall {
+                    function_name: String::new(),
+                    arguments: vec![].into(),
+                    amount: NearToken::from_yoctonear(0),
+                    gas: Gas::from_tgas(5),
+                },
+            ],
+        }))
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        "Smart contract panicked: Invalid field `actions[1].public_key`: invalid public key; Invalid field `actions[2].function_name`: must not be empty",
+    );
+}
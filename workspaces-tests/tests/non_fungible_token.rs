@@ -4,7 +4,7 @@ use near_sdk::{json_types::U128, serde_json::json};
 use near_sdk_contract_tools::standard::{
     nep171::{
         self,
-        event::{Nep171Event, NftTransferLog},
+        event::{Nep171Event, Nep171FreezeEvent, NftBurnLog, NftTransferLog},
         Token,
     },
     nep177::{self, TokenMetadata},
@@ -13,6 +13,7 @@ use near_sdk_contract_tools::standard::{
     },
     nep297::Event,
 };
+use near_sdk_contract_tools::testing::expect_event;
 use near_workspaces::{operations::Function, types::Gas};
 use pretty_assertions::assert_eq;
 use tokio::task::JoinSet;
@@ -26,6 +27,9 @@ const WASM_171_ONLY: &[u8] =
 const WASM_FULL: &[u8] =
     include_bytes!("../../target/wasm32-unknown-unknown/release/non_fungible_token_full.wasm");
 
+const WASM_COMPOSED: &[u8] =
+    include_bytes!("../../target/wasm32-unknown-unknown/release/non_fungible_token_composed.wasm");
+
 const RECEIVER_WASM: &[u8] =
     include_bytes!("../../target/wasm32-unknown-unknown/release/non_fungible_token_receiver.wasm");
 
@@ -109,6 +113,32 @@ async fn create_and_mint() {
     assert_eq!(token_3, None::<Token>);
 }
 
+#[tokio::test]
+async fn mint_returns_token_ids() {
+    let Setup { contract, accounts } = setup_balances(WASM_FULL, 1, |_| vec![], true).await;
+    let alice = &accounts[0];
+
+    let token_ids = alice
+        .call(contract.id(), "mint")
+        .args_json(json!({ "token_ids": ["token_a", "token_b"] }))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap()
+        .json::<Vec<String>>()
+        .unwrap();
+
+    assert_eq!(token_ids, vec!["token_a", "token_b"]);
+
+    for token_id in token_ids {
+        let token = nft_token::<Token>(&contract, &token_id).await.unwrap();
+
+        assert_eq!(token.token_id, token_id);
+        assert_eq!(&token.owner_id, alice.id());
+        assert_eq!(token.extensions_metadata["metadata"], token_meta(token_id));
+    }
+}
+
 #[tokio::test]
 async fn create_and_mint_with_metadata_and_enumeration() {
     let Setup { contract, accounts } =
@@ -517,7 +547,7 @@ async fn transfer_call_success() {
             "receiver_id": bob.id(),
             "msg": "",
         }))
-        .gas(THIRTY_TERAGAS)
+        .gas(THIRTY_TERAGAS.saturating_mul(2))
         .deposit(ONE_YOCTO)
         .transact()
         .await
@@ -576,7 +606,7 @@ async fn transfer_call_return_success() {
             "receiver_id": bob.id(),
             "msg": "return",
         }))
-        .gas(THIRTY_TERAGAS)
+        .gas(THIRTY_TERAGAS.saturating_mul(2))
         .deposit(ONE_YOCTO)
         .transact()
         .await
@@ -645,7 +675,7 @@ async fn transfer_call_receiver_panic() {
             "receiver_id": bob.id(),
             "msg": "panic",
         }))
-        .gas(THIRTY_TERAGAS)
+        .gas(THIRTY_TERAGAS.saturating_mul(2))
         .deposit(ONE_YOCTO)
         .transact()
         .await
@@ -1111,3 +1141,486 @@ async fn transfer_fail_not_registered_nep145() {
         .unwrap()
         .unwrap();
 }
+
+#[tokio::test]
+async fn transfer_fail_frozen() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_171_ONLY, 2, |i| vec![format!("token_{i}")], false).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    let result = alice
+        .call(contract.id(), "freeze_transfers")
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        result.logs(),
+        vec![Nep171FreezeEvent::TransfersFrozen.to_event_string()],
+    );
+
+    let result = alice
+        .call(contract.id(), "nft_transfer")
+        .args_json(json!({
+            "token_id": "token_0",
+            "receiver_id": bob.id(),
+        }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        "Smart contract panicked: Transfers are frozen: token `token_0` cannot be transferred",
+    );
+
+    let result = alice
+        .call(contract.id(), "unfreeze_transfers")
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        result.logs(),
+        vec![Nep171FreezeEvent::TransfersUnfrozen.to_event_string()],
+    );
+
+    alice
+        .call(contract.id(), "nft_transfer")
+        .args_json(json!({
+            "token_id": "token_0",
+            "receiver_id": bob.id(),
+        }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        nft_token(&contract, "token_0").await,
+        Some(Token {
+            token_id: "token_0".to_string(),
+            owner_id: bob.id().clone(),
+            extensions_metadata: Default::default(),
+        }),
+    );
+}
+
+#[tokio::test]
+async fn transfer_call_revert_while_frozen() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_171_ONLY, 2, |i| vec![format!("token_{i}")], false).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    bob.batch(bob.id())
+        .deploy(RECEIVER_WASM)
+        .call(Function::new("new"))
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    // The receiver freezes transfers before asking for the token back.
+    let result = alice
+        .call(contract.id(), "nft_transfer_call")
+        .args_json(json!({
+            "token_id": "token_0",
+            "receiver_id": bob.id(),
+            "msg": "freeze",
+        }))
+        .gas(THIRTY_TERAGAS.saturating_mul(2))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    expect_event(result.logs(), "x-nft-freeze", "transfers_frozen");
+
+    // returned, despite transfers being frozen
+    assert_eq!(
+        nft_token(&contract, "token_0").await,
+        Some(Token {
+            token_id: "token_0".to_string(),
+            owner_id: alice.id().clone(),
+            extensions_metadata: Default::default(),
+        }),
+    );
+}
+
+#[tokio::test]
+async fn burn_success() {
+    let Setup { contract, accounts } = setup_balances(
+        WASM_FULL,
+        1,
+        |i| vec![format!("token_{i}_a"), format!("token_{i}_b")],
+        true,
+    )
+    .await;
+    let alice = &accounts[0];
+
+    let result = alice
+        .call(contract.id(), "nft_burn")
+        .args_json(json!({ "token_ids": ["token_0_a", "token_0_b"] }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        result.logs(),
+        vec![Nep171Event::NftBurn(vec![NftBurnLog {
+            owner_id: alice.id().into(),
+            token_ids: vec!["token_0_a".into(), "token_0_b".into()],
+            authorized_id: None,
+            memo: None,
+        }])
+        .to_event_string()],
+    );
+
+    let (token_a, token_b) = tokio::join!(
+        nft_token(&contract, "token_0_a"),
+        nft_token(&contract, "token_0_b"),
+    );
+
+    assert_eq!(token_a, None::<Token>);
+    assert_eq!(token_b, None::<Token>);
+}
+
+#[tokio::test]
+async fn burn_fail_not_owner() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_FULL, 2, |i| vec![format!("token_{i}")], true).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    let result = alice
+        .call(contract.id(), "nft_burn")
+        .args_json(json!({ "token_ids": ["token_1"] }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        format!(
+            "Smart contract panicked: Token `token_1` is owned by `{}` instead of expected `{}`",
+            bob.id(),
+            alice.id(),
+        ),
+    );
+
+    assert!(nft_token::<Token>(&contract, "token_1").await.is_some());
+}
+
+#[tokio::test]
+async fn approve_batch_success() {
+    let Setup { contract, accounts } = setup_balances(
+        WASM_FULL,
+        2,
+        |i| {
+            vec![
+                format!("token_{i}_a"),
+                format!("token_{i}_b"),
+                format!("token_{i}_c"),
+            ]
+        },
+        true,
+    )
+    .await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    let result = alice
+        .call(contract.id(), "nft_approve_batch")
+        .args_json(json!({
+            "token_ids": ["token_0_a", "token_0_b", "token_0_c"],
+            "account_id": bob.id(),
+        }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        result.logs(),
+        vec![
+            "before_nft_approve(token_0_a)",
+            "after_nft_approve(token_0_a)",
+            "before_nft_approve(token_0_b)",
+            "after_nft_approve(token_0_b)",
+            "before_nft_approve(token_0_c)",
+            "after_nft_approve(token_0_c)",
+        ],
+    );
+    assert_eq!(result.json::<Vec<u32>>().unwrap(), vec![0, 0, 0]);
+
+    for token_id in ["token_0_a", "token_0_b", "token_0_c"] {
+        let is_approved = contract
+            .view("nft_is_approved")
+            .args_json(json!({
+                "token_id": token_id,
+                "approved_account_id": bob.id().to_string(),
+            }))
+            .await
+            .unwrap()
+            .json::<bool>()
+            .unwrap();
+
+        assert!(is_approved);
+    }
+
+    alice
+        .call(contract.id(), "nft_revoke_batch")
+        .args_json(json!({
+            "token_ids": ["token_0_a", "token_0_c"],
+            "account_id": bob.id(),
+        }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    for (token_id, expected) in [
+        ("token_0_a", false),
+        ("token_0_b", true),
+        ("token_0_c", false),
+    ] {
+        let is_approved = contract
+            .view("nft_is_approved")
+            .args_json(json!({
+                "token_id": token_id,
+                "approved_account_id": bob.id().to_string(),
+            }))
+            .await
+            .unwrap()
+            .json::<bool>()
+            .unwrap();
+
+        assert_eq!(is_approved, expected);
+    }
+}
+
+#[tokio::test]
+async fn approve_batch_fail_not_owner() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_FULL, 2, |i| vec![format!("token_{i}")], true).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    let result = alice
+        .call(contract.id(), "nft_approve_batch")
+        .args_json(json!({
+            "token_ids": ["token_0", "token_1"],
+            "account_id": bob.id(),
+        }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        format!(
+            "Smart contract panicked: Account `{}` is not authorized to manage approvals for token `token_1`.",
+            bob.id(),
+        ),
+    );
+
+    let is_approved = contract
+        .view("nft_is_approved")
+        .args_json(json!({
+            "token_id": "token_0",
+            "approved_account_id": bob.id().to_string(),
+        }))
+        .await
+        .unwrap()
+        .json::<bool>()
+        .unwrap();
+
+    assert!(!is_approved);
+}
+
+#[tokio::test]
+async fn revoke_all_success() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_FULL, 3, |i| vec![format!("token_{i}")], true).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+    let charlie = &accounts[2];
+
+    alice
+        .batch(contract.id())
+        .call(
+            Function::new("nft_approve")
+                .args_json(json!({ "token_id": "token_0", "account_id": bob.id() }))
+                .deposit(ONE_YOCTO),
+        )
+        .call(
+            Function::new("nft_approve")
+                .args_json(json!({ "token_id": "token_0", "account_id": charlie.id() }))
+                .deposit(ONE_YOCTO),
+        )
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = alice
+        .call(contract.id(), "nft_revoke_all")
+        .args_json(json!({ "token_id": "token_0" }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        result.logs(),
+        vec![
+            "before_nft_revoke_all(token_0)",
+            "after_nft_revoke_all(token_0)",
+        ],
+    );
+
+    let mut expected = vec![bob.id().clone(), charlie.id().clone()];
+    expected.sort();
+    assert_eq!(result.json::<Vec<near_sdk::AccountId>>().unwrap(), expected,);
+
+    let token = nft_token::<Token>(&contract, "token_0").await.unwrap();
+    assert_eq!(token.extensions_metadata["approved_account_ids"], json!({}),);
+}
+
+#[tokio::test]
+async fn revoke_all_fail_not_owner() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_FULL, 2, |i| vec![format!("token_{i}")], true).await;
+    let bob = &accounts[1];
+
+    let result = bob
+        .call(contract.id(), "nft_revoke_all")
+        .args_json(json!({ "token_id": "token_0" }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(
+        &result,
+        format!(
+            "Smart contract panicked: Account `{}` is not authorized to manage approvals for token `token_0`.",
+            bob.id(),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn update_metadata_owner_only() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_FULL, 1, |i| vec![format!("token_{i}")], true).await;
+    let alice = &accounts[0];
+
+    let result = alice
+        .call(contract.id(), "update_contract_metadata")
+        .args_json(json!({ "patch": { "name": "Hijacked" } }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(&result, "Smart contract panicked: Owner only");
+
+    let result = alice
+        .call(contract.id(), "update_token_metadata")
+        .args_json(json!({ "token_id": "token_0", "metadata": token_meta("hijacked") }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap();
+
+    expect_execution_error(&result, "Smart contract panicked: Owner only");
+
+    contract
+        .call("update_contract_metadata")
+        .args_json(json!({ "patch": { "name": "Renamed" } }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let metadata = contract
+        .view("nft_metadata")
+        .await
+        .unwrap()
+        .json::<nep177::ContractMetadata>()
+        .unwrap();
+
+    assert_eq!(metadata.name, "Renamed");
+    assert_eq!(metadata.symbol, "MNSC");
+}
+
+#[tokio::test]
+async fn composed_derives_load_metadata_and_approvals() {
+    let Setup { contract, accounts } =
+        setup_balances(WASM_COMPOSED, 2, |i| vec![format!("token_{i}")], false).await;
+    let alice = &accounts[0];
+    let bob = &accounts[1];
+
+    alice
+        .call(contract.id(), "nft_approve")
+        .args_json(json!({
+            "token_id": "token_0",
+            "account_id": bob.id(),
+        }))
+        .deposit(ONE_YOCTO)
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let (token_0, token_1) = tokio::join!(
+        nft_token::<Token>(&contract, "token_0"),
+        nft_token::<Token>(&contract, "token_1"),
+    );
+
+    assert_eq!(
+        token_0,
+        Some(Token {
+            token_id: "token_0".into(),
+            owner_id: alice.id().clone(),
+            extensions_metadata: [
+                ("metadata".to_string(), token_meta("token_0")),
+                (
+                    "approved_account_ids".to_string(),
+                    json!({
+                        bob.id().to_string(): 0,
+                    }),
+                ),
+            ]
+            .into(),
+        }),
+    );
+    assert_eq!(
+        token_1,
+        Some(Token {
+            token_id: "token_1".into(),
+            owner_id: bob.id().clone(),
+            extensions_metadata: [
+                ("metadata".to_string(), token_meta("token_1")),
+                ("approved_account_ids".to_string(), json!({})),
+            ]
+            .into(),
+        }),
+    );
+}
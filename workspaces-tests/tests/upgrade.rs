@@ -11,6 +11,12 @@ const WASM_JSON: &[u8] =
 const WASM_RAW: &[u8] =
     include_bytes!("../../target/wasm32-unknown-unknown/release/upgrade_old_raw.wasm");
 
+const WASM_RAW_DERIVE: &[u8] =
+    include_bytes!("../../target/wasm32-unknown-unknown/release/upgrade_old_raw_derive.wasm");
+
+const WASM_MIGRATE_ARGS: &[u8] =
+    include_bytes!("../../target/wasm32-unknown-unknown/release/upgrade_old_migrate_args.wasm");
+
 const NEW_WASM: &[u8] =
     include_bytes!("../../target/wasm32-unknown-unknown/release/upgrade_new.wasm");
 
@@ -55,7 +61,8 @@ async fn setup(num_accounts: usize, wasm: &[u8]) -> Setup {
     Setup { contract, accounts }
 }
 
-async fn perform_upgrade_test(wasm: &[u8], args: Vec<u8>) {
+/// Returns the gas burnt by the `upgrade` function call itself.
+async fn perform_upgrade_test(wasm: &[u8], args: Vec<u8>) -> near_workspaces::types::Gas {
     let Setup { contract, accounts } = setup(1, wasm).await;
 
     let alice = &accounts[0];
@@ -77,14 +84,16 @@ async fn perform_upgrade_test(wasm: &[u8], args: Vec<u8>) {
 
     assert_eq!(val, 1);
 
-    alice
+    let upgrade_gas = alice
         .call(contract.id(), "upgrade")
         .max_gas()
         .args(args)
         .transact()
         .await
         .unwrap()
-        .unwrap();
+        .unwrap()
+        .outcome()
+        .gas_burnt;
 
     let new_val = alice
         .call(contract.id(), "get_bar")
@@ -95,6 +104,8 @@ async fn perform_upgrade_test(wasm: &[u8], args: Vec<u8>) {
         .unwrap();
 
     assert_eq!(new_val, 1);
+
+    upgrade_gas
 }
 
 #[tokio::test]
@@ -109,6 +120,43 @@ async fn upgrade_borsh() {
     .await;
 }
 
+#[tokio::test]
+async fn upgrade_with_migrate_args() {
+    let Setup { contract, accounts } = setup(1, WASM_MIGRATE_ARGS).await;
+    let alice = &accounts[0];
+
+    alice
+        .call(contract.id(), "increment_foo")
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    alice
+        .call(contract.id(), "upgrade")
+        .max_gas()
+        .args(
+            near_sdk::borsh::to_vec(&ArgsBorsh {
+                code: NEW_WASM.to_vec(),
+            })
+            .unwrap(),
+        )
+        .transact()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let new_val = contract
+        .view("get_bar")
+        .await
+        .unwrap()
+        .json::<u64>()
+        .unwrap();
+
+    // `migrate_with_offset` received `{ "offset": 100 }`.
+    assert_eq!(new_val, 101);
+}
+
 #[tokio::test]
 #[ignore]
 async fn upgrade_jsonbase64() {
@@ -132,12 +180,76 @@ async fn upgrade_raw() {
     perform_upgrade_test(WASM_RAW, NEW_WASM.to_vec()).await;
 }
 
+/// Appends a custom section of `len` zero bytes to a WebAssembly module,
+/// producing a valid module that is `len` bytes larger (plus overhead).
+fn pad_wasm(wasm: &[u8], len: usize) -> Vec<u8> {
+    fn leb128(mut n: usize, out: &mut Vec<u8>) {
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                out.push(byte);
+                return;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    let name = b"padding";
+    let mut payload = vec![];
+    leb128(name.len(), &mut payload);
+    payload.extend_from_slice(name);
+    payload.resize(payload.len() + len, 0);
+
+    let mut padded = wasm.to_vec();
+    padded.push(0); // custom section id
+    leb128(payload.len(), &mut padded);
+    padded.extend(payload);
+    padded
+}
+
+#[tokio::test]
+async fn upgrade_raw_derive() {
+    perform_upgrade_test(WASM_RAW_DERIVE, NEW_WASM.to_vec()).await;
+}
+
+#[tokio::test]
+async fn upgrade_raw_derive_large_code_zero_copy() {
+    // `read_register_byte` + `write_memory_byte` + `read_memory_byte` -
+    // `read_register_byte`, rounded down to leave room for fixed costs.
+    const MIN_SAVING_PER_BYTE: u64 = 6_000_000;
+
+    let code = pad_wasm(NEW_WASM, 512 * 1024);
+
+    let raw_gas = perform_upgrade_test(WASM_RAW_DERIVE, code.clone()).await;
+    let borsh_gas = perform_upgrade_test(
+        WASM_BORSH,
+        near_sdk::borsh::to_vec(&ArgsBorsh { code }).unwrap(),
+    )
+    .await;
+
+    assert!(
+        borsh_gas.as_gas().saturating_sub(raw_gas.as_gas()) >= MIN_SAVING_PER_BYTE * 512 * 1024,
+        "raw upgrade burnt {raw_gas}, borsh upgrade burnt {borsh_gas}",
+    );
+}
+
 #[tokio::test]
 #[should_panic = "Failed to deserialize input from Borsh."]
 async fn upgrade_failure_blank_wasm() {
     perform_upgrade_test(WASM_BORSH, vec![]).await;
 }
 
+#[tokio::test]
+#[should_panic = "Upgrade code must not be empty"]
+async fn upgrade_failure_empty_code() {
+    perform_upgrade_test(
+        WASM_BORSH,
+        near_sdk::borsh::to_vec(&ArgsBorsh { code: vec![] }).unwrap(),
+    )
+    .await;
+}
+
 #[tokio::test]
 #[should_panic = "MethodResolveError(MethodNotFound)"]
 async fn upgrade_failure_no_upgrade() {
@@ -215,3 +327,9 @@ async fn upgrade_failure_not_owner_jsonbase64() {
 async fn upgrade_failure_not_owner_raw() {
     fail_owner(WASM_RAW, NEW_WASM.to_vec()).await;
 }
+
+#[tokio::test]
+#[should_panic = "Smart contract panicked: Owner only"]
+async fn upgrade_failure_not_owner_raw_derive() {
+    fail_owner(WASM_RAW_DERIVE, NEW_WASM.to_vec()).await;
+}